@@ -17,6 +17,7 @@ macro_rules! define_data_enum {
 
         $(#[$attr])*
         #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         $v enum $name {
             $(
                 $(#[$field_attr])*
@@ -25,6 +26,16 @@ macro_rules! define_data_enum {
         }
 
         impl $name {
+            /// All variants, in declaration order.
+            $v const ALL: &'static [Self] = &[
+                $($name::$field),*
+            ];
+
+            /// Iterates over all variants, in declaration order.
+            $v fn all() -> impl Iterator<Item = Self> {
+                Self::ALL.iter().copied()
+            }
+
             /// Gets the entire associated data structure.
             #[must_use]
             $vd const fn data(self) -> &'static $data {
@@ -60,3 +71,19 @@ pub(crate) use define_data_enum;
 pub fn is_default<T: Default + PartialEq>(value: &T) -> bool {
     *value == T::default()
 }
+
+#[cfg(test)]
+mod test {
+    use crate::Faction;
+
+    #[test]
+    fn faction_all() {
+        let all: Vec<Faction> = Faction::all().collect();
+        assert_eq!(all.len(), Faction::ALL.len());
+        assert_eq!(all.first(), Some(&Faction::Unknown));
+        assert_eq!(all.last(), Some(&Faction::CollabSenranKagura));
+
+        // declaration order matches the derived order
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+    }
+}
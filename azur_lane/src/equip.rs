@@ -8,7 +8,8 @@ use crate::skill::*;
 use super::Faction;
 
 /// Represents a piece of equipment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Equip {
     pub equip_id: u32,
     pub name: String,
@@ -16,18 +17,24 @@ pub struct Equip {
     pub kind: EquipKind,
     pub rarity: EquipRarity,
     pub faction: Faction,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub weapons: Vec<Weapon>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skills: Vec<Skill>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub stat_bonuses: Vec<EquipStatBonus>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    /// The stat bonuses at each enhancement level, starting at +0.
+    ///
+    /// The last entry matches [`Equip::stat_bonuses`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<Vec<EquipStatBonus>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub hull_disallowed: Vec<HullType>,
 }
 
 /// A weapon that is part of [`Equip`] or [`Skill`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Weapon {
     pub weapon_id: u32,
     pub name: Option<String>,
@@ -38,7 +45,8 @@ pub struct Weapon {
 }
 
 /// A bullet barrage pattern for a [`Weapon`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Barrage {
     pub damage: f64,
     pub coefficient: f64,
@@ -51,7 +59,8 @@ pub struct Barrage {
 }
 
 /// Bullet information for a [`Barrage`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Bullet {
     pub bullet_id: u32,
 
@@ -67,7 +76,7 @@ pub struct Bullet {
     pub flags: BulletFlags,
 
     /// Buffs caused by the bullet hit.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub attach_buff: Vec<BuffInfo>,
 
     /// Extra data depending on the bullet type.
@@ -76,7 +85,8 @@ pub struct Bullet {
 }
 
 /// Additional bullet data.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum BulletExtra {
     /// No extra data.
     #[default] None,
@@ -87,7 +97,8 @@ pub enum BulletExtra {
 }
 
 /// How far a bullet's hit spread and AOE is. Only applicable to main gun fire and bombs.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BulletSpread {
     pub spread_x: f64,
     pub spread_y: f64,
@@ -95,14 +106,16 @@ pub struct BulletSpread {
 }
 
 /// Additional information about a beam.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BulletBeam {
     pub duration: f64,
     pub tick_delay: f64,
 }
 
 /// Aircraft data for a [`Weapon`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Aircraft {
     pub aircraft_id: u32,
     pub amount: u32,
@@ -113,7 +126,8 @@ pub struct Aircraft {
 }
 
 /// The possible data a [`Weapon`] can hold.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum WeaponData {
     /// The weapon fires bullets as a [`Barrage`].
     Bullets(Barrage),
@@ -125,17 +139,20 @@ pub enum WeaponData {
 
 /// Armor modifiers to apply to the damage.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ArmorModifiers(pub f64, pub f64, pub f64);
 
 /// Bonus stats gained by equipping the associated equipment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EquipStatBonus {
     pub stat_kind: StatKind,
     pub amount: f64
 }
 
 /// Represents an Augment Module.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Augment {
     pub augment_id: u32,
     pub name: String,
@@ -149,7 +166,8 @@ pub struct Augment {
 }
 
 /// Represents who an Augment Module can be used on.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AugmentUsability {
     /// Only certain hull types are allowed.
     HullTypes(Vec<HullType>),
@@ -158,7 +176,8 @@ pub enum AugmentUsability {
 }
 
 /// Bonus stats gained by equipping the associated augment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AugmentStatBonus {
     pub stat_kind: StatKind,
     pub amount: f64,
@@ -166,7 +185,8 @@ pub struct AugmentStatBonus {
 }
 
 /// A skill upgraded by an augment module.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AugmentSkillUpgrade {
     pub original_id: u32,
     pub skill: Skill,
@@ -189,27 +209,30 @@ bitflags::bitflags! {
 define_data_enum! {
     /// The possible kinds of equipment.
     pub enum EquipKind for EquipKindData {
-        pub name: &'static str;
-
-        DestroyerGun("DD Gun"),
-        LightCruiserGun("CL Gun"),
-        HeavyCruiserGun("CA Gun"),
-        LargeCruiserGun("CB Gun"),
-        BattleshipGun("BB Gun"),
-        SurfaceTorpedo("Torpedo (Surface)"),
-        SubmarineTorpedo("Torpedo (Submarine)"),
-        AntiAirGun("Anti-Air Gun"),
-        FuzeAntiAirGun("Anti-Air Gun (Fuze)"),
-        Fighter("Fighter"),
-        DiveBomber("Dive Bomber"),
-        TorpedoBomber("Torpedo Bomber"),
-        SeaPlane("Seaplane"),
-        AntiSubWeapon("Anti-Sub Weapon"),
-        AntiSubAircraft("Anti-Sub Aircraft"),
-        Helicopter("Helicopter"),
-        Missile("Missile"),
-        Cargo("Cargo"),
-        Auxiliary("Auxiliary")
+        /// The display name of the equipment kind.
+        pub name: &'static str,
+        /// A shorter name, used when listing what an equipment slot allows.
+        pub slot_name: &'static str;
+
+        DestroyerGun("DD Gun", "DD Gun"),
+        LightCruiserGun("CL Gun", "CL Gun"),
+        HeavyCruiserGun("CA Gun", "CA Gun"),
+        LargeCruiserGun("CB Gun", "CB Gun"),
+        BattleshipGun("BB Gun", "BB Gun"),
+        SurfaceTorpedo("Torpedo (Surface)", "Torpedo"),
+        SubmarineTorpedo("Torpedo (Submarine)", "Torpedo"),
+        AntiAirGun("Anti-Air Gun", "AA Gun"),
+        FuzeAntiAirGun("Anti-Air Gun (Fuze)", "AA Gun (Fuze)"),
+        Fighter("Fighter", "Fighter"),
+        DiveBomber("Dive Bomber", "Dive Bomber"),
+        TorpedoBomber("Torpedo Bomber", "Torpedo Bomber"),
+        SeaPlane("Seaplane", "Seaplane"),
+        AntiSubWeapon("Anti-Sub Weapon", "ASW"),
+        AntiSubAircraft("Anti-Sub Aircraft", "ASW Aircraft"),
+        Helicopter("Helicopter", "Helicopter"),
+        Missile("Missile", "Missile"),
+        Cargo("Cargo", "Cargo"),
+        Auxiliary("Auxiliary", "Auxiliary")
     }
 }
 
@@ -339,6 +362,90 @@ define_data_enum! {
     }
 }
 
+impl Equip {
+    /// Whether this equipment may be equipped by a ship of the given hull type.
+    ///
+    /// This only checks [`Equip::hull_disallowed`]. Use [`EquipSlot::accepts`] to check the kind.
+    #[must_use]
+    pub fn can_equip_on(&self, hull_type: HullType) -> bool {
+        !self.hull_disallowed.contains(&hull_type)
+    }
+}
+
+impl Weapon {
+    /// The effective reload time in seconds.
+    ///
+    /// Strike aircraft take 2.2 times their listed reload time.
+    #[must_use]
+    pub fn effective_reload_time(&self) -> f64 {
+        match self.kind {
+            WeaponKind::StrikeAircraft => self.reload_time * 2.2,
+            _ => self.reload_time,
+        }
+    }
+
+    /// The delay in seconds added to every cycle after reloading.
+    ///
+    /// For bullet barrages, this includes the time the salvo takes to fire.
+    #[must_use]
+    pub fn cycle_delay(&self) -> f64 {
+        match &self.data {
+            WeaponData::Bullets(barrage) => self.fixed_delay + barrage.salvo_time,
+            WeaponData::Aircraft(_) | WeaponData::AntiAir(_) => self.fixed_delay,
+        }
+    }
+
+    /// The time in seconds of a full firing cycle, that is reload time plus delay.
+    #[must_use]
+    pub fn cycle_time(&self) -> f64 {
+        self.effective_reload_time() + self.cycle_delay()
+    }
+
+    /// How often the weapon fires per minute.
+    #[must_use]
+    pub fn fires_per_minute(&self) -> f64 {
+        60.0 / self.cycle_time()
+    }
+
+    /// The raw damage per second of a single copy of the weapon.
+    ///
+    /// This is the listed damage of a full salvo divided by [`Weapon::cycle_time`],
+    /// ignoring stat scaling and armor. Returns [`None`] for aircraft.
+    #[must_use]
+    pub fn dps(&self) -> Option<f64> {
+        let (barrage, hits) = match &self.data {
+            WeaponData::Bullets(barrage) => (barrage, barrage.bullets.iter().map(Bullet::hits).sum()),
+            WeaponData::AntiAir(barrage) => (barrage, 1.0),
+            WeaponData::Aircraft(_) => return None,
+        };
+
+        Some(hits * barrage.damage * barrage.coefficient / self.cycle_time())
+    }
+
+    /// The raw damage per second of the weapon when equipped in a slot with the given mount.
+    ///
+    /// Like [`Weapon::dps`], but scaled by the mount's efficiency and multiplied by
+    /// its amount of mounts and parallel loads.
+    #[must_use]
+    pub fn dps_with_mount(&self, mount: &EquipWeaponMount) -> Option<f64> {
+        let copies = f64::from(mount.mounts) * f64::from(mount.parallel.max(1));
+        Some(self.dps()? * mount.efficiency * copies)
+    }
+}
+
+impl Bullet {
+    /// How often this bullet hits per salvo, that is its amount times the ticks for beams.
+    #[must_use]
+    fn hits(&self) -> f64 {
+        let ticks = match &self.extra {
+            BulletExtra::Beam(beam) => beam.duration / beam.tick_delay,
+            _ => 1.0,
+        };
+
+        f64::from(self.amount) * ticks
+    }
+}
+
 impl BulletExtra {
     pub fn is_none(&self) -> bool {
         matches!(self, BulletExtra::None)
@@ -353,6 +460,18 @@ impl BulletFlags {
     }
 }
 
+/// Serialized as the flag names separated by `|`.
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for BulletFlags {
+    fn schema_name() -> String {
+        "BulletFlags".to_owned()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
 impl AugmentUsability {
     /// If restricted by hull types, gets the hull types. Otherwise, returns [`None`].
     pub fn hull_types(&self) -> Option<&[HullType]> {
@@ -409,3 +528,95 @@ impl From<(f64, f64, f64)> for ArmorModifiers {
         Self(l, m, h)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::test_support::*;
+    use super::*;
+
+    #[test]
+    fn equip_rarity_colors() {
+        for a in EquipRarity::all() {
+            assert_ne!(a.color_rgb(), 0, "{a:?} has no color");
+
+            // the common rarities share both their name and color
+            for b in EquipRarity::all().filter(|b| b.name() != a.name()) {
+                assert_ne!(a.color_rgb(), b.color_rgb(), "{a:?} and {b:?} share a color");
+            }
+        }
+    }
+
+    #[test]
+    fn equip_kind_names() {
+        for kind in EquipKind::all() {
+            assert!(!kind.name().is_empty(), "{kind:?}");
+            assert!(!kind.slot_name().is_empty(), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn weapon_cycle_time() {
+        let mut weapon = weapon();
+        assert_eq!(weapon.effective_reload_time(), 1.5);
+        assert_eq!(weapon.cycle_delay(), 0.25);
+        assert_eq!(weapon.cycle_time(), 1.75);
+
+        weapon.fixed_delay = 0.75;
+        assert_eq!(weapon.cycle_delay(), 1.0);
+        assert_eq!(weapon.cycle_time(), 2.5);
+        assert_eq!(weapon.fires_per_minute(), 24.0);
+
+        // anti-air barrages don't add their salvo time
+        let WeaponData::Bullets(barrage) = weapon.data.clone() else { unreachable!() };
+        weapon.data = WeaponData::AntiAir(barrage);
+        assert_eq!(weapon.cycle_time(), 2.25);
+    }
+
+    #[test]
+    fn weapon_dps() {
+        let weapon = weapon();
+
+        // 3 bullets x 13.5 damage over a 1.75s cycle
+        let dps = weapon.dps().unwrap();
+        assert!((dps - 3.0 * 13.5 / 1.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn weapon_dps_with_mount() {
+        let weapon = weapon();
+        let dps = weapon.dps().unwrap();
+
+        let mount = EquipWeaponMount { efficiency: 1.25, mounts: 2, parallel: 1, preload: 0 };
+        let mounted = weapon.dps_with_mount(&mount).unwrap();
+        assert!((mounted - dps * 1.25 * 2.0).abs() < 1e-9);
+
+        let mount = EquipWeaponMount { efficiency: 1.0, mounts: 1, parallel: 2, preload: 0 };
+        let mounted = weapon.dps_with_mount(&mount).unwrap();
+        assert!((mounted - dps * 2.0).abs() < 1e-9);
+
+        // no parallel loads is treated like a single one
+        let mount = EquipWeaponMount { efficiency: 1.0, mounts: 1, parallel: 0, preload: 0 };
+        let mounted = weapon.dps_with_mount(&mount).unwrap();
+        assert!((mounted - dps).abs() < 1e-9);
+    }
+
+    #[test]
+    fn weapon_dps_aircraft() {
+        let weapon = Weapon {
+            kind: WeaponKind::StrikeAircraft,
+            data: WeaponData::Aircraft(Aircraft {
+                aircraft_id: 4000,
+                amount: 2,
+                speed: 40.0,
+                health: ShipStat::new(),
+                dodge_limit: 0,
+                weapons: vec![weapon()],
+            }),
+            .. weapon()
+        };
+
+        let mount = EquipWeaponMount { efficiency: 1.0, mounts: 1, parallel: 1, preload: 0 };
+        assert!(weapon.dps().is_none());
+        assert!(weapon.dps_with_mount(&mount).is_none());
+    }
+}
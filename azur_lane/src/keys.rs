@@ -0,0 +1,275 @@
+//! Support for the compact key scheme of [`DefinitionData`](crate::DefinitionData).
+//!
+//! In the compact scheme, every struct field name in the model is replaced by a short numeric key.
+//! The data is otherwise identical. Since the mapping can't be expressed with static serde renames
+//! alone, writers and readers rename the keys on a generic JSON value before serializing or after parsing,
+//! using [`compact_keys`] and [`expand_keys`].
+//!
+//! Numeric keys are never valid Rust identifiers, so they cannot be confused with field or enum
+//! variant names. This also means that expanding keys of data in the full scheme is a no-op.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Serialize, Deserialize};
+
+/// The key scheme used by serialized definition data.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum KeyScheme {
+    /// Keys are the Rust field names.
+    #[default]
+    Full,
+    /// Keys are short numeric strings. See [`compact_key`] and [`expand_key`].
+    Compact,
+}
+
+/// The field name of the key scheme marker in [`DefinitionData`](crate::DefinitionData).
+///
+/// This key is never renamed, so readers can check it before expanding any keys.
+pub const KEY_SCHEME_FIELD: &str = "key_scheme";
+
+/// Maps full field names to their compact keys.
+///
+/// Entries must only ever be appended. Changing existing compact keys breaks reading data written
+/// with an older table.
+const KEYS: &[(&str, &str)] = &[
+    ("equip_id", "0"),
+    ("name", "1"),
+    ("description", "2"),
+    ("kind", "3"),
+    ("rarity", "4"),
+    ("faction", "5"),
+    ("weapons", "6"),
+    ("skills", "7"),
+    ("stat_bonuses", "8"),
+    ("tiers", "9"),
+    ("hull_disallowed", "10"),
+    ("weapon_id", "11"),
+    ("reload_time", "12"),
+    ("fixed_delay", "13"),
+    ("data", "14"),
+    ("damage", "15"),
+    ("coefficient", "16"),
+    ("scaling", "17"),
+    ("scaling_stat", "18"),
+    ("range", "19"),
+    ("firing_angle", "20"),
+    ("salvo_time", "21"),
+    ("bullets", "22"),
+    ("bullet_id", "23"),
+    ("amount", "24"),
+    ("ammo", "25"),
+    ("pierce", "26"),
+    ("velocity", "27"),
+    ("modifiers", "28"),
+    ("flags", "29"),
+    ("attach_buff", "30"),
+    ("extra", "31"),
+    ("spread_x", "32"),
+    ("spread_y", "33"),
+    ("hit_range", "34"),
+    ("duration", "35"),
+    ("tick_delay", "36"),
+    ("aircraft_id", "37"),
+    ("speed", "38"),
+    ("health", "39"),
+    ("dodge_limit", "40"),
+    ("stat_kind", "41"),
+    ("augment_id", "42"),
+    ("usability", "43"),
+    ("effect", "44"),
+    ("skill_upgrade", "45"),
+    ("random", "46"),
+    ("original_id", "47"),
+    ("skill", "48"),
+    ("ships", "49"),
+    ("equips", "50"),
+    ("augments", "51"),
+    ("language", "52"),
+    ("image_format", "53"),
+    ("group_id", "54"),
+    ("hull_type", "55"),
+    ("stars", "56"),
+    ("enhance_kind", "57"),
+    ("stats", "58"),
+    ("default_skin_id", "59"),
+    ("equip_slots", "60"),
+    ("shadow_equip", "61"),
+    ("depth_charges", "62"),
+    ("retrofits", "63"),
+    ("skins", "64"),
+    ("hp", "65"),
+    ("armor", "66"),
+    ("rld", "67"),
+    ("fp", "68"),
+    ("trp", "69"),
+    ("eva", "70"),
+    ("aa", "71"),
+    ("avi", "72"),
+    ("acc", "73"),
+    ("asw", "74"),
+    ("spd", "75"),
+    ("lck", "76"),
+    ("cost", "77"),
+    ("oxy", "78"),
+    ("amo", "79"),
+    ("allowed", "80"),
+    ("mount", "81"),
+    ("efficiency", "82"),
+    ("mounts", "83"),
+    ("parallel", "84"),
+    ("preload", "85"),
+    ("skin_id", "86"),
+    ("image_key", "87"),
+    ("words", "88"),
+    ("words_extra", "89"),
+    ("illustrator", "90"),
+    ("voice_actor", "91"),
+    ("introduction", "92"),
+    ("acquisition", "93"),
+    ("login", "94"),
+    ("details", "95"),
+    ("main_screen", "96"),
+    ("touch", "97"),
+    ("special_touch", "98"),
+    ("rub", "99"),
+    ("mission_reminder", "100"),
+    ("mission_complete", "101"),
+    ("mail_reminder", "102"),
+    ("return_to_port", "103"),
+    ("commission_complete", "104"),
+    ("enhance", "105"),
+    ("flagship_fight", "106"),
+    ("victory", "107"),
+    ("defeat", "108"),
+    ("low_health", "109"),
+    ("disappointed", "110"),
+    ("stranger", "111"),
+    ("friendly", "112"),
+    ("crush", "113"),
+    ("love", "114"),
+    ("oath", "115"),
+    ("couple_encourage", "116"),
+    ("line", "117"),
+    ("condition", "118"),
+    ("buff_id", "119"),
+    ("category", "120"),
+    ("barrages", "121"),
+    ("new_weapons", "122"),
+    ("icon_key", "123"),
+    ("level_descriptions", "124"),
+    ("cooldown", "125"),
+    ("effects", "126"),
+    ("value", "127"),
+    ("skill_id", "128"),
+    ("attacks", "129"),
+    ("target", "130"),
+    ("weapon", "131"),
+    ("probability", "132"),
+    ("level", "133"),
+];
+
+fn compact_map() -> &'static HashMap<&'static str, &'static str> {
+    static MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    MAP.get_or_init(|| KEYS.iter().copied().collect())
+}
+
+fn expand_map() -> &'static HashMap<&'static str, &'static str> {
+    static MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    MAP.get_or_init(|| KEYS.iter().map(|&(full, compact)| (compact, full)).collect())
+}
+
+/// Gets the compact key for a full field name.
+///
+/// Returns [`None`] if the key isn't renamed, in which case it should be kept as is.
+#[must_use]
+pub fn compact_key(key: &str) -> Option<&'static str> {
+    compact_map().get(key).copied()
+}
+
+/// Gets the full field name for a compact key.
+///
+/// Returns [`None`] if the key isn't a known compact key, in which case it should be kept as is.
+#[must_use]
+pub fn expand_key(key: &str) -> Option<&'static str> {
+    expand_map().get(key).copied()
+}
+
+/// Replaces all known field names in a JSON value with their compact keys.
+///
+/// This applies to the keys of nested objects, including ones within arrays.
+pub fn compact_keys(value: &mut serde_json::Value) {
+    rename_keys(value, compact_key);
+}
+
+/// Replaces all known compact keys in a JSON value with their full field names.
+///
+/// This applies to the keys of nested objects, including ones within arrays.
+pub fn expand_keys(value: &mut serde_json::Value) {
+    rename_keys(value, expand_key);
+}
+
+fn rename_keys(value: &mut serde_json::Value, rename: fn(&str) -> Option<&'static str>) {
+    match value {
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| rename_keys(v, rename)),
+        serde_json::Value::Object(map) => {
+            for (key, mut value) in std::mem::take(map) {
+                rename_keys(&mut value, rename);
+                map.insert(rename(&key).map_or(key, str::to_owned), value);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keys_are_unique() {
+        assert_eq!(compact_map().len(), KEYS.len());
+        assert_eq!(expand_map().len(), KEYS.len());
+    }
+
+    #[test]
+    fn keys_are_numeric() {
+        for &(full, compact) in KEYS {
+            assert!(compact.bytes().all(|b| b.is_ascii_digit()), "compact key for {full} is not numeric: {compact}");
+            assert!(full.bytes().any(|b| !b.is_ascii_digit()), "full key {full} is numeric");
+        }
+    }
+
+    #[test]
+    fn key_scheme_is_not_renamed() {
+        assert_eq!(compact_key(KEY_SCHEME_FIELD), None);
+        assert_eq!(expand_key(KEY_SCHEME_FIELD), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(compact_key("skin_id"), Some("86"));
+        assert_eq!(expand_key("86"), Some("skin_id"));
+        assert_eq!(compact_key("WebP"), None);
+        assert_eq!(expand_key("skin_id"), None);
+    }
+
+    #[test]
+    fn rename_nested_keys() {
+        let full = serde_json::json!({
+            "ships": [{ "skin_id": 1, "hull_type": "Destroyer" }],
+            "key_scheme": "Compact",
+        });
+
+        let mut value = full.clone();
+        compact_keys(&mut value);
+        assert_eq!(value, serde_json::json!({
+            "49": [{ "86": 1, "55": "Destroyer" }],
+            "key_scheme": "Compact",
+        }));
+
+        expand_keys(&mut value);
+        assert_eq!(value, full);
+    }
+}
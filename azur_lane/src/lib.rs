@@ -1,16 +1,26 @@
 //! Defines a data model that a subset of Azur Lane's game data can be represented as.
+//!
+//! All model types implement [`PartialEq`]. Only types that don't hold any floating point values,
+//! directly or transitively, also implement [`Eq`]. This means that f.e. [`ship::ShipData`],
+//! [`equip::Equip`], [`equip::Weapon`], [`equip::Augment`], and [`skill::Skill`] are [`PartialEq`] only,
+//! while [`ship::ShipSkin`] and its dialogue types are also [`Eq`].
 
 use serde::{Serialize, Deserialize};
 
 mod data_def;
 pub mod equip;
+pub mod keys;
 pub mod ship;
 pub mod skill;
+#[cfg(any(test, feature = "test-support"))]
+#[doc(hidden)]
+pub mod test_support;
 
 use data_def::define_data_enum;
 
 /// Definition data to be saved/loaded in bulk.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DefinitionData {
     /// All known ships.
     pub ships: Vec<ship::ShipData>,
@@ -18,6 +28,25 @@ pub struct DefinitionData {
     pub equips: Vec<equip::Equip>,
     /// All known augments.
     pub augments: Vec<equip::Augment>,
+    /// The language of the contained text, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// The format of the extracted chibi and skill icon images.
+    #[serde(default, skip_serializing_if = "data_def::is_default")]
+    pub image_format: ImageFormat,
+    /// The key scheme the data was serialized with.
+    ///
+    /// Typed deserialization only understands [`keys::KeyScheme::Full`].
+    /// Readers need to check this first and expand the keys if needed.
+    #[serde(default, skip_serializing_if = "data_def::is_default")]
+    pub key_scheme: keys::KeyScheme,
+}
+
+/// Generates a JSON schema describing [`DefinitionData`].
+#[cfg(feature = "schema")]
+#[must_use]
+pub fn definition_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(DefinitionData)
 }
 
 define_data_enum! {
@@ -55,3 +84,175 @@ define_data_enum! {
         CollabSenranKagura("Senran Kagura", None)
     }
 }
+
+define_data_enum! {
+    /// A language the game data may be available in.
+    pub enum Language for LanguageData {
+        /// The display name of the language.
+        pub name: &'static str,
+        /// The code of the game server region that uses the language.
+        pub code: &'static str;
+
+        English("English", "EN"),
+        Japanese("Japanese", "JP"),
+        ChineseSimplified("Chinese (Simplified)", "CN"),
+        ChineseTraditional("Chinese (Traditional)", "TW"),
+        Korean("Korean", "KR")
+    }
+}
+
+define_data_enum! {
+    /// An image format for extracted images.
+    pub enum ImageFormat for ImageFormatData {
+        /// The file extension, without the leading dot.
+        pub extension: &'static str;
+
+        WebP("webp"),
+        Png("png")
+    }
+}
+
+impl ImageFormat {
+    /// Gets a format by its file extension, ignoring case.
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::all().find(|f| f.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+/// Defaults to [`ImageFormat::WebP`], which data without a specified format uses.
+impl Default for ImageFormat {
+    fn default() -> Self {
+        Self::WebP
+    }
+}
+
+impl Language {
+    /// Gets a language by its code, ignoring case.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().find(|l| l.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Gets the file name used for definition data in this language.
+    ///
+    /// English data uses `main.json`. Other languages include their code, f.e. `main.jp.json`.
+    #[must_use]
+    pub fn definition_file_name(self) -> String {
+        match self {
+            Self::English => "main.json".to_owned(),
+            _ => format!("main.{}.json", self.code().to_ascii_lowercase()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::equip::*;
+    use crate::ship::*;
+    use crate::test_support::*;
+    use super::*;
+
+    #[test]
+    fn ship_round_trip() {
+        let mut ship = ship();
+
+        let mut retrofit = ship.clone();
+        retrofit.rarity = ShipRarity::UR;
+        retrofit.enhance_kind = EnhanceKind::Research;
+        retrofit.skins.clear();
+        ship.retrofits.push(retrofit);
+
+        let json = serde_json::to_string(&ship).unwrap();
+        let result: ShipData = serde_json::from_str(&json).unwrap();
+        assert_eq!(ship, result);
+    }
+
+    fn definition() -> DefinitionData {
+        DefinitionData {
+            ships: vec![ship()],
+            equips: vec![equip()],
+            augments: vec![Augment {
+                augment_id: 7000,
+                name: "Test Augment".to_owned(),
+                rarity: AugmentRarity::SR,
+                stat_bonuses: vec![AugmentStatBonus { stat_kind: StatKind::EVA, amount: 20.0, random: 5.0 }],
+                usability: AugmentUsability::UniqueShipId(10001),
+                effect: None,
+                skill_upgrade: Some(AugmentSkillUpgrade {
+                    original_id: 4000,
+                    skill: ship().skills.remove(0),
+                }),
+            }],
+            language: Some(Language::Japanese),
+            image_format: ImageFormat::Png,
+            key_scheme: keys::KeyScheme::Full,
+        }
+    }
+
+    fn all_keys<'a>(value: &'a serde_json::Value, keys: &mut Vec<&'a str>) {
+        match value {
+            serde_json::Value::Array(items) => items.iter().for_each(|v| all_keys(v, keys)),
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    keys.push(key);
+                    all_keys(value, keys);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn definition_round_trip() {
+        let data = definition();
+
+        let json = serde_json::to_string(&data).unwrap();
+        let result: DefinitionData = serde_json::from_str(&json).unwrap();
+        assert_eq!(data, result);
+    }
+
+    #[test]
+    fn definition_compact_round_trip() {
+        let data = definition();
+        let full = serde_json::to_value(&data).unwrap();
+
+        let mut compact = full.clone();
+        keys::compact_keys(&mut compact);
+
+        // every remaining key should be an enum variant, so any field missing from the key table shows up here
+        let mut remaining = Vec::new();
+        all_keys(&compact, &mut remaining);
+        assert!(
+            remaining.iter().all(|k| k.starts_with(|c: char| c.is_ascii_digit() || c.is_ascii_uppercase())),
+            "keys without compact form: {remaining:?}"
+        );
+
+        assert!(compact.to_string().len() < full.to_string().len());
+
+        let mut expanded = compact;
+        keys::expand_keys(&mut expanded);
+        assert_eq!(expanded, full);
+
+        let result: DefinitionData = serde_json::from_value(expanded).unwrap();
+        assert_eq!(data, result);
+    }
+
+    #[test]
+    fn image_format_defaults_to_webp() {
+        let data: DefinitionData = serde_json::from_str(r#"{"ships":[],"equips":[],"augments":[]}"#).unwrap();
+        assert_eq!(data.image_format, ImageFormat::WebP);
+        assert!(!serde_json::to_string(&data).unwrap().contains("image_format"));
+
+        assert_eq!(ImageFormat::from_extension("PNG"), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_extension("jpg"), None);
+    }
+
+    #[test]
+    fn language_file_name() {
+        assert_eq!(Language::from_code("jp"), Some(Language::Japanese));
+        assert_eq!(Language::from_code("XX"), None);
+        assert_eq!(Language::English.definition_file_name(), "main.json");
+        assert_eq!(Language::ChineseSimplified.definition_file_name(), "main.cn.json");
+    }
+}
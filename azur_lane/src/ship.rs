@@ -1,5 +1,6 @@
 //! Data structures relating directly to ships.
 
+use std::borrow::Cow;
 use std::fmt::Display;
 
 use serde::{Serialize, Deserialize};
@@ -10,7 +11,8 @@ use super::skill::*;
 use super::Faction;
 
 /// Provides data for a singular ship or a retrofit.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipData {
     pub group_id: u32,
     pub name: String,
@@ -23,19 +25,20 @@ pub struct ShipData {
     pub stats: ShipStatBlock,
     pub default_skin_id: u32,
     pub equip_slots: Vec<EquipSlot>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub shadow_equip: Vec<ShadowEquip>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub depth_charges: Vec<Equip>,
     pub skills: Vec<Skill>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub retrofits: Vec<ShipData>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skins: Vec<ShipSkin>,
 }
 
 /// Provides stat block information for a ship.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipStatBlock {
     pub hp: ShipStat,
     pub armor: ShipArmor,
@@ -55,11 +58,13 @@ pub struct ShipStatBlock {
 }
 
 /// Represents a single ship stat. Its value can be calculated on demand.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipStat(f64, f64, f64);
 
 /// A singular normal equipment slot of a ship.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EquipSlot {
     /// Which kinds of equipment can be equipped in the slot.
     pub allowed: Vec<EquipKind>,
@@ -69,7 +74,8 @@ pub struct EquipSlot {
 }
 
 /// Mount information for an [`EquipSlot`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EquipWeaponMount {
     /// The mount efficiency, as displayed in-game.
     pub efficiency: f64,
@@ -86,7 +92,8 @@ pub struct EquipWeaponMount {
 }
 
 /// Provides information about "shadow" equipment; inherent gear that is not displayed in-game.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShadowEquip {
     /// The name of the associated equipment.
     pub name: String,
@@ -97,7 +104,8 @@ pub struct ShadowEquip {
 }
 
 /// Data for a ship skin. This may represent the default skin.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipSkin {
     pub skin_id: u32,
     pub image_key: String,
@@ -107,11 +115,18 @@ pub struct ShipSkin {
     pub words: ShipSkinWords,
     /// Replacement dialogue lines, usually after oath.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub words_extra: Option<Box<ShipSkinWords>>
+    pub words_extra: Option<Box<ShipSkinWords>>,
+    /// The illustrator of the skin, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub illustrator: Option<String>,
+    /// The voice actor for the skin's lines, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice_actor: Option<String>,
 }
 
 /// The block of dialogue for a given skin.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipSkinWords {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -123,7 +138,7 @@ pub struct ShipSkinWords {
     pub login: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub main_screen: Vec<ShipMainScreenLine>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub touch: Option<String>,
@@ -166,18 +181,20 @@ pub struct ShipSkinWords {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub oath: Option<String>,
     /// Voices lines that may be played when sortieing other specific ships.
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub couple_encourage: Vec<ShipCoupleEncourage>
 }
 
 /// Information about a ship line that may be displayed on the main screen.
 ///
 /// Also see [`ShipSkinWords::main_screen`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipMainScreenLine(usize, String);
 
 /// Data for voices lines that may be played when sortieing other specific ships.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ShipCoupleEncourage {
     pub line: String,
     pub amount: u32,
@@ -185,7 +202,8 @@ pub struct ShipCoupleEncourage {
 }
 
 /// Condition for [`ShipCoupleEncourage`].
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ShipCouple {
     /// Triggered when other specific ships are present.
     /// Holds a vector of ship group IDs.
@@ -202,6 +220,40 @@ pub enum ShipCouple {
     Illustrator
 }
 
+define_data_enum! {
+    /// A kind of line in [`ShipSkinWords`], excluding main screen and couple lines.
+    pub enum LineKind for LineKindData {
+        /// The display name for the kind of line.
+        pub name: &'static str;
+
+        Description("Description"),
+        Profile("Profile"),
+        Acquisition("Acquisition"),
+        Login("Login"),
+        Details("Details"),
+        Touch("Touch"),
+        SpecialTouch("Special Touch"),
+        Rub("Rub"),
+        MissionReminder("Mission Reminder"),
+        MissionComplete("Mission Complete"),
+        MailReminder("Mail Reminder"),
+        ReturnToPort("Return to Port"),
+        CommissionComplete("Commission Complete"),
+        Enhance("Enhance"),
+        FlagshipFight("Flagship Fight"),
+        Victory("Victory"),
+        Defeat("Defeat"),
+        Skill("Skill"),
+        LowHealth("Low Health"),
+        Disappointed("Disappointed"),
+        Stranger("Stranger"),
+        Friendly("Friendly"),
+        Crush("Crush"),
+        Love("Love"),
+        Oath("Oath")
+    }
+}
+
 define_data_enum! {
     /// The rarities for a ship.
     pub enum ShipRarity for ShipRarityData {
@@ -225,6 +277,7 @@ define_data_enum! {
 
 /// The enhancement mode kind.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EnhanceKind {
     /// Normal. Enhancement by feeding spare duplicate ships.
     #[default]
@@ -319,10 +372,49 @@ impl Display for ShipArmor {
 }
 
 impl ShipData {
+    /// Gets a skin of this ship by its ID.
     #[must_use]
     pub fn skin_by_id(&self, skin_id: u32) -> Option<&ShipSkin> {
         self.skins.iter().find(|s| s.skin_id == skin_id)
     }
+
+    /// Gets the skin referenced by [`ShipData::default_skin_id`].
+    ///
+    /// Retrofits don't hold skins themselves. For those, look up the ID on the base ship instead.
+    #[must_use]
+    pub fn default_skin(&self) -> Option<&ShipSkin> {
+        self.skin_by_id(self.default_skin_id)
+    }
+
+    /// Whether any retrofit of this ship is sortied in a different team than the base ship.
+    #[must_use]
+    pub fn retrofit_changes_team(&self) -> bool {
+        let team_type = self.hull_type.team_type();
+        self.retrofits.iter().any(|r| r.hull_type.team_type() != team_type)
+    }
+
+    /// Gets the name with the faction's prefix, f.e. "USS Enterprise".
+    ///
+    /// If the faction has no prefix or the name already starts with it, returns the name as-is.
+    #[must_use]
+    pub fn prefixed_name(&self) -> Cow<'_, str> {
+        match self.faction.prefix() {
+            Some(prefix) if !self.name.strip_prefix(prefix).is_some_and(|n| n.starts_with(' ')) => {
+                Cow::Owned(format!("{prefix} {}", self.name))
+            },
+            _ => Cow::Borrowed(&self.name),
+        }
+    }
+}
+
+impl EquipSlot {
+    /// Whether this slot accepts the given equipment's kind.
+    ///
+    /// This doesn't check the ship's hull type. Use [`Equip::can_equip_on`] for that.
+    #[must_use]
+    pub fn accepts(&self, equip: &Equip) -> bool {
+        self.allowed.contains(&equip.kind)
+    }
 }
 
 impl ShipStatBlock {
@@ -330,17 +422,68 @@ impl ShipStatBlock {
     #[must_use]
     pub fn calc_stat(&self, kind: StatKind, level: u32, affinity: f64) -> f64 {
         match kind {
-            StatKind::HP => self.hp.calc(level, affinity),
-            StatKind::RLD => self.rld.calc(level, affinity),
-            StatKind::FP => self.fp.calc(level, affinity),
-            StatKind::TRP => self.trp.calc(level, affinity),
-            StatKind::EVA => self.eva.calc(level, affinity),
-            StatKind::AA => self.aa.calc(level, affinity),
-            StatKind::AVI => self.avi.calc(level, affinity),
-            StatKind::ACC => self.acc.calc(level, affinity),
-            StatKind::ASW => self.asw.calc(level, affinity),
             StatKind::SPD => self.spd,
-            StatKind::LCK => self.lck
+            StatKind::LCK => self.lck,
+            _ => self.stat(kind).map_or(0.0, |s| s.calc(level, affinity)),
+        }
+    }
+
+    /// Gets a scaling stat by its kind.
+    ///
+    /// Speed and luck don't scale with level and affinity and are stored as plain numbers,
+    /// so this returns [`None`] for [`StatKind::SPD`] and [`StatKind::LCK`].
+    #[must_use]
+    pub fn stat(&self, kind: StatKind) -> Option<&ShipStat> {
+        Some(match kind {
+            StatKind::HP => &self.hp,
+            StatKind::RLD => &self.rld,
+            StatKind::FP => &self.fp,
+            StatKind::TRP => &self.trp,
+            StatKind::EVA => &self.eva,
+            StatKind::AA => &self.aa,
+            StatKind::AVI => &self.avi,
+            StatKind::ACC => &self.acc,
+            StatKind::ASW => &self.asw,
+            StatKind::SPD | StatKind::LCK => return None,
+        })
+    }
+
+    /// Gets a scaling stat by its kind, mutably.
+    ///
+    /// Like [`ShipStatBlock::stat`], returns [`None`] for speed and luck.
+    #[must_use]
+    pub fn stat_mut(&mut self, kind: StatKind) -> Option<&mut ShipStat> {
+        Some(match kind {
+            StatKind::HP => &mut self.hp,
+            StatKind::RLD => &mut self.rld,
+            StatKind::FP => &mut self.fp,
+            StatKind::TRP => &mut self.trp,
+            StatKind::EVA => &mut self.eva,
+            StatKind::AA => &mut self.aa,
+            StatKind::AVI => &mut self.avi,
+            StatKind::ACC => &mut self.acc,
+            StatKind::ASW => &mut self.asw,
+            StatKind::SPD | StatKind::LCK => return None,
+        })
+    }
+
+    /// Adds an equipment bonus to the matching stat as a fixed addition, unaffected by level and affinity.
+    ///
+    /// Speed and luck don't scale and are added to directly.
+    pub fn add_bonus(&mut self, bonus: &EquipStatBonus) {
+        self.add_to_stat(bonus.stat_kind, ShipStat::new().with_fixed(bonus.amount), bonus.amount);
+    }
+
+    /// Adds to a stat by its kind.
+    ///
+    /// `amount` is added to scaling stats, while `plain` is added to speed and luck instead.
+    pub fn add_to_stat(&mut self, kind: StatKind, amount: ShipStat, plain: f64) {
+        match kind {
+            StatKind::SPD => self.spd += plain,
+            StatKind::LCK => self.lck += plain,
+            _ => if let Some(stat) = self.stat_mut(kind) {
+                *stat += amount;
+            },
         }
     }
 }
@@ -412,6 +555,91 @@ impl std::ops::AddAssign<&Self> for ShipStat {
     }
 }
 
+utils::impl_op_via_assign!(ShipStatBlock, [std::ops::AddAssign]::add_assign, [std::ops::Add]::add);
+
+/// Adds another, usually partial, stat block.
+///
+/// Speed, luck, and the costs are added as-is. The armor type isn't additive and is kept.
+impl std::ops::AddAssign<&Self> for ShipStatBlock {
+    fn add_assign(&mut self, rhs: &Self) {
+        self.hp += rhs.hp;
+        self.rld += rhs.rld;
+        self.fp += rhs.fp;
+        self.trp += rhs.trp;
+        self.eva += rhs.eva;
+        self.aa += rhs.aa;
+        self.avi += rhs.avi;
+        self.acc += rhs.acc;
+        self.asw += rhs.asw;
+        self.spd += rhs.spd;
+        self.lck += rhs.lck;
+        self.cost += rhs.cost;
+        self.oxy += rhs.oxy;
+        self.amo += rhs.amo;
+    }
+}
+
+utils::impl_op_via_assign!(ShipStatBlock, Rhs=EquipStatBonus, [std::ops::AddAssign]::add_assign, [std::ops::Add]::add);
+
+/// Adds an equipment bonus. See [`ShipStatBlock::add_bonus`].
+impl std::ops::AddAssign<&EquipStatBonus> for ShipStatBlock {
+    fn add_assign(&mut self, rhs: &EquipStatBonus) {
+        self.add_bonus(rhs);
+    }
+}
+
+impl ShipSkinWords {
+    /// Whether there are no lines at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.line_count() == 0
+    }
+
+    /// The total amount of lines.
+    ///
+    /// Every main screen line and couple line is counted separately.
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        LineKind::all().filter(|&k| self.line(k).is_some()).count()
+            + self.main_screen.len()
+            + self.couple_encourage.len()
+    }
+
+    /// Gets the line of a specific kind, if present.
+    #[must_use]
+    pub fn line(&self, kind: LineKind) -> Option<&str> {
+        let line = match kind {
+            LineKind::Description => &self.description,
+            LineKind::Profile => &self.introduction,
+            LineKind::Acquisition => &self.acquisition,
+            LineKind::Login => &self.login,
+            LineKind::Details => &self.details,
+            LineKind::Touch => &self.touch,
+            LineKind::SpecialTouch => &self.special_touch,
+            LineKind::Rub => &self.rub,
+            LineKind::MissionReminder => &self.mission_reminder,
+            LineKind::MissionComplete => &self.mission_complete,
+            LineKind::MailReminder => &self.mail_reminder,
+            LineKind::ReturnToPort => &self.return_to_port,
+            LineKind::CommissionComplete => &self.commission_complete,
+            LineKind::Enhance => &self.enhance,
+            LineKind::FlagshipFight => &self.flagship_fight,
+            LineKind::Victory => &self.victory,
+            LineKind::Defeat => &self.defeat,
+            LineKind::Skill => &self.skill,
+            LineKind::LowHealth => &self.low_health,
+            LineKind::Disappointed => &self.disappointed,
+            LineKind::Stranger => &self.stranger,
+            LineKind::Friendly => &self.friendly,
+            LineKind::Crush => &self.crush,
+            LineKind::Love => &self.love,
+            LineKind::Oath => &self.oath,
+        };
+
+        line.as_deref()
+    }
+}
+
 impl ShipMainScreenLine {
     /// Creates a new instance.
     #[must_use]
@@ -449,4 +677,210 @@ impl ShipRarity {
             Self::SR | Self::UR => Self::UR,
         }
     }
+
+    /// Returns the next lower rarity.
+    ///
+    /// For [`ShipRarity::N`], returns itself.
+    #[must_use]
+    pub fn prev(self) -> Self {
+        match self {
+            Self::N | Self::R => Self::N,
+            Self::E => Self::R,
+            Self::SR => Self::E,
+            Self::UR => Self::SR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_support::*;
+    use super::*;
+
+    #[test]
+    fn equip_slot_validation() {
+        let equip = equip();
+
+        assert!(equip.can_equip_on(HullType::Destroyer));
+        assert!(!equip.can_equip_on(HullType::Submarine));
+
+        let gun_slot = EquipSlot { allowed: vec![EquipKind::DestroyerGun, EquipKind::LightCruiserGun], mount: None };
+        let torpedo_slot = EquipSlot { allowed: vec![EquipKind::SurfaceTorpedo], mount: None };
+
+        assert!(gun_slot.accepts(&equip));
+        assert!(!torpedo_slot.accepts(&equip));
+    }
+
+    #[test]
+    fn ship_rarity_prev() {
+        assert_eq!(ShipRarity::N.prev(), ShipRarity::N);
+        assert_eq!(ShipRarity::R.prev(), ShipRarity::N);
+        assert_eq!(ShipRarity::E.prev(), ShipRarity::R);
+        assert_eq!(ShipRarity::SR.prev(), ShipRarity::E);
+        assert_eq!(ShipRarity::UR.prev(), ShipRarity::SR);
+
+        for rarity in [ShipRarity::R, ShipRarity::E, ShipRarity::SR, ShipRarity::UR] {
+            assert_eq!(rarity.prev().next(), rarity);
+        }
+    }
+
+    #[test]
+    fn skin_without_credits() {
+        let mut skin = ship().skins.remove(0);
+        skin.illustrator = None;
+
+        // older data files don't have these keys at all
+        let json = serde_json::to_value(&skin).unwrap();
+        assert!(json.get("illustrator").is_none());
+        assert!(json.get("voice_actor").is_none());
+
+        let result: ShipSkin = serde_json::from_value(json).unwrap();
+        assert_eq!(skin, result);
+    }
+
+    #[test]
+    fn retrofit_team_change() {
+        let mut base = ship();
+        assert!(!base.retrofit_changes_team());
+
+        let mut retrofit = ship();
+        retrofit.hull_type = HullType::LightCruiser;
+        base.retrofits.push(retrofit);
+
+        // a different hull type in the same team isn't a team change
+        assert!(!base.retrofit_changes_team());
+
+        base.retrofits[0].hull_type = HullType::Battleship;
+        assert!(base.retrofit_changes_team());
+    }
+
+    #[test]
+    fn ship_prefixed_name() {
+        let mut ship = ship();
+        assert_eq!(ship.prefixed_name(), "USS Test Ship");
+
+        ship.faction = Faction::RoyalNavy;
+        assert_eq!(ship.prefixed_name(), "HMS Test Ship");
+
+        // no prefix for the faction
+        ship.faction = Faction::CollabHololive;
+        assert_eq!(ship.prefixed_name(), "Test Ship");
+
+        // don't prefix twice, but only match the whole prefix
+        ship.faction = Faction::EagleUnion;
+        ship.name = "USS Test Ship".to_owned();
+        assert_eq!(ship.prefixed_name(), "USS Test Ship");
+
+        ship.name = "USSR Ship".to_owned();
+        assert_eq!(ship.prefixed_name(), "USS USSR Ship");
+    }
+
+    #[test]
+    fn ship_stat_block_add() {
+        let base = ship().stats;
+        let bonus = ShipStatBlock {
+            hp: ShipStat::new().with_fixed(50.0),
+            armor: ShipArmor::Heavy,
+            rld: ShipStat::new(),
+            fp: ShipStat::new().with_base(10.0).with_growth(100.0),
+            trp: ShipStat::new(),
+            eva: ShipStat::new(),
+            aa: ShipStat::new(),
+            avi: ShipStat::new().with_fixed(7.0),
+            acc: ShipStat::new(),
+            asw: ShipStat::new(),
+            spd: 1.5,
+            lck: 2.0,
+            cost: 1,
+            oxy: 3,
+            amo: 1,
+        };
+
+        let sum = base.clone() + &bonus;
+        assert_eq!(sum.hp, ShipStat::new().with_base(100.0).with_growth(1500.0).with_fixed(54.0));
+        assert_eq!(sum.fp, ShipStat::new().with_base(110.0).with_growth(1600.0).with_fixed(4.0));
+        assert_eq!(sum.avi, ShipStat::new().with_fixed(7.0));
+        assert_eq!(sum.rld, base.rld);
+
+        // non-scaling fields are summed, except armor
+        assert_eq!(sum.armor, ShipArmor::Light);
+        assert_eq!(sum.spd, 44.75);
+        assert_eq!(sum.lck, 62.0);
+        assert_eq!((sum.cost, sum.oxy, sum.amo), (10, 3, 6));
+    }
+
+    #[test]
+    fn ship_stat_block_add_equip_bonus() {
+        let base = ship().stats;
+        let sum = base.clone()
+            + EquipStatBonus { stat_kind: StatKind::FP, amount: 45.0 }
+            + EquipStatBonus { stat_kind: StatKind::SPD, amount: 2.0 };
+
+        assert_eq!(sum.fp.fixed(), base.fp.fixed() + 45.0);
+        assert_eq!(sum.spd, base.spd + 2.0);
+        assert_eq!(sum.hp, base.hp);
+    }
+
+    #[test]
+    fn ship_stat_block_add_bonus() {
+        let mut stats = ship().stats;
+        stats.add_bonus(&EquipStatBonus { stat_kind: StatKind::AA, amount: 30.0 });
+        stats.add_bonus(&EquipStatBonus { stat_kind: StatKind::AA, amount: 15.0 });
+        stats.add_bonus(&EquipStatBonus { stat_kind: StatKind::LCK, amount: 5.0 });
+
+        assert_eq!(stats.aa, ShipStat::new().with_base(100.0).with_growth(1500.0).with_fixed(49.0));
+        assert_eq!(stats.lck, 65.0);
+
+        // fixed additions ignore affinity
+        let base = ship().stats;
+        assert_eq!(stats.aa.calc(1, 2.0) - base.aa.calc(1, 2.0), 45.0);
+    }
+
+    #[test]
+    fn ship_stat_block_by_kind() {
+        let mut stats = ship().stats;
+        assert_eq!(stats.stat(StatKind::AVI), Some(&ShipStat::new()));
+        assert_eq!(stats.stat(StatKind::HP), Some(&stats.hp));
+        assert_eq!(stats.stat(StatKind::SPD), None);
+        assert_eq!(stats.stat(StatKind::LCK), None);
+
+        *stats.stat_mut(StatKind::AVI).unwrap() = ShipStat::new().with_base(12.0);
+        assert_eq!(stats.avi, ShipStat::new().with_base(12.0));
+        assert!(stats.stat_mut(StatKind::SPD).is_none());
+
+        // speed and luck are still available through calc_stat
+        assert_eq!(stats.calc_stat(StatKind::SPD, 120, 1.0), 43.25);
+        assert_eq!(stats.calc_stat(StatKind::AVI, 120, 2.0), 24.0);
+    }
+
+    #[test]
+    fn skin_words_line_count() {
+        // 5 optional lines, 2 main screen lines, 3 couple lines
+        let words = words("Hello.");
+        assert_eq!(words.line_count(), 10);
+        assert!(!words.is_empty());
+
+        let words = ShipSkinWords { couple_encourage: Vec::new(), ..words };
+        assert_eq!(words.line_count(), 7);
+    }
+
+    #[test]
+    fn skin_words_line() {
+        let words = words("Hello.");
+        assert_eq!(words.line(LineKind::Login), Some("Hello."));
+        assert_eq!(words.line(LineKind::Oath), Some("Oath."));
+        assert_eq!(words.line(LineKind::Touch), None);
+    }
+
+    #[test]
+    fn skin_words_empty() {
+        // every field is optional, so this has no lines at all
+        let words: ShipSkinWords = serde_json::from_str("{}").unwrap();
+        assert_eq!(words.line_count(), 0);
+        assert!(words.is_empty());
+
+        let words = ShipSkinWords { main_screen: vec![ShipMainScreenLine::new(0, "Main 1".to_owned())], ..words };
+        assert_eq!(words.line_count(), 1);
+        assert!(!words.is_empty());
+    }
 }
@@ -6,34 +6,62 @@ use crate::define_data_enum;
 use crate::equip::Weapon;
 
 /// Represents a single skill.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Skill {
     pub buff_id: u32,
     pub name: String,
     pub description: String,
     pub category: SkillCategory,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub barrages: Vec<SkillBarrage>,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub new_weapons: Vec<BuffWeapon>,
+    /// The key for the skill's icon image, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_key: Option<String>,
+    /// The descriptions for every skill level, starting at level 1.
+    ///
+    /// Empty if the skill doesn't scale with its level. The last entry matches [`Skill::description`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub level_descriptions: Vec<String>,
+    /// The interval in seconds at which the skill periodically activates, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<f64>,
+    /// Raw numeric values of the skill's effects at its max level.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effects: Vec<SkillEffectValue>,
+}
+
+/// Represents a raw numeric value of a skill's effect.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkillEffectValue {
+    /// The game's internal name for the value, f.e. the affected stat.
+    pub name: String,
+    /// The value itself. Ratio effects are converted to fractions, so 0.15 means 15%.
+    pub value: f64,
 }
 
 /// Represents a skill barrage.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SkillBarrage {
     pub skill_id: u32,
     pub attacks: Vec<SkillAttack>,
 }
 
 /// Represents a skill barrage's attack.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SkillAttack {
     pub target: SkillAttackTarget,
     pub weapon: Weapon,
 }
 
 /// Represents a buff's bonus weapon.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BuffWeapon {
     pub duration: Option<f64>,
     pub weapon: Weapon,
@@ -71,11 +99,61 @@ define_data_enum! {
     }
 }
 
+impl Skill {
+    /// Gets the description at a level, starting at 1.
+    ///
+    /// Falls back to [`Skill::description`] if there is no description for that level.
+    #[must_use]
+    pub fn description_at(&self, level: usize) -> &str {
+        level.checked_sub(1)
+            .and_then(|i| self.level_descriptions.get(i))
+            .unwrap_or(&self.description)
+    }
+}
+
 /// Represents basic information about a buff, to be extended later if needed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BuffInfo {
     pub buff_id: u32,
     pub probability: f64,
     #[serde(default, skip_serializing_if = "crate::data_def::is_default")]
     pub level: u32,
 }
+
+#[cfg(test)]
+mod test {
+    use crate::test_support::*;
+    use super::*;
+
+    #[test]
+    fn skill_level_descriptions() {
+        let mut skill = ship().skills.remove(0);
+        assert_eq!(skill.description_at(1), "Does a thing.");
+        assert_eq!(skill.description_at(2), "Does things.");
+        assert_eq!(skill.description_at(0), "Does things.");
+        assert_eq!(skill.description_at(3), "Does things.");
+
+        // older data files only have the single description
+        skill.level_descriptions.clear();
+        let json = serde_json::to_value(&skill).unwrap();
+        assert!(json.get("level_descriptions").is_none());
+
+        let result: Skill = serde_json::from_value(json).unwrap();
+        assert_eq!(result.description_at(1), "Does things.");
+    }
+
+    #[test]
+    fn skill_effect_values() {
+        let mut skill = ship().skills.remove(0);
+        let json = serde_json::to_value(&skill).unwrap();
+        assert!(json.get("cooldown").is_none());
+        assert!(json.get("effects").is_none());
+
+        skill.cooldown = Some(20.0);
+        skill.effects.push(SkillEffectValue { name: "cannonPower".to_owned(), value: 0.15 });
+        let json = serde_json::to_value(&skill).unwrap();
+        let result: Skill = serde_json::from_value(json).unwrap();
+        assert_eq!(result, skill);
+    }
+}
@@ -0,0 +1,189 @@
+//! Shared model fixtures for tests, in this crate and its dependents.
+//!
+//! The values are arbitrary but internally consistent. Tests that care about a specific field
+//! should override it with struct update syntax rather than rely on the fixture's value.
+
+use crate::equip::*;
+use crate::ship::*;
+use crate::skill::*;
+use crate::Faction;
+
+/// Creates skin dialogue with the given login line and a mix of set and unset lines.
+pub fn words(login: &str) -> ShipSkinWords {
+    ShipSkinWords {
+        description: Some("A ship.".to_owned()),
+        introduction: None,
+        acquisition: Some("Build".to_owned()),
+        login: Some(login.to_owned()),
+        details: None,
+        main_screen: vec![
+            ShipMainScreenLine::new(0, "Main 1".to_owned()),
+            ShipMainScreenLine::new(2, "Main 3".to_owned()),
+        ],
+        touch: None,
+        special_touch: None,
+        rub: None,
+        mission_reminder: None,
+        mission_complete: None,
+        mail_reminder: None,
+        return_to_port: None,
+        commission_complete: None,
+        enhance: None,
+        flagship_fight: None,
+        victory: Some("Victory!".to_owned()),
+        defeat: None,
+        skill: None,
+        low_health: None,
+        disappointed: None,
+        stranger: None,
+        friendly: None,
+        crush: None,
+        love: None,
+        oath: Some("Oath.".to_owned()),
+        couple_encourage: vec![
+            ShipCoupleEncourage {
+                line: "Together!".to_owned(),
+                amount: 2,
+                condition: ShipCouple::ShipGroup(vec![10001, 10002]),
+            },
+            ShipCoupleEncourage {
+                line: "Destroyers!".to_owned(),
+                amount: 1,
+                condition: ShipCouple::HullType(vec![HullType::Destroyer]),
+            },
+            ShipCoupleEncourage {
+                line: "Same artist.".to_owned(),
+                amount: 1,
+                condition: ShipCouple::Illustrator,
+            },
+        ],
+    }
+}
+
+/// Creates a main gun weapon with a single spread bullet.
+pub fn weapon() -> Weapon {
+    Weapon {
+        weapon_id: 1000,
+        name: None,
+        reload_time: 1.5,
+        fixed_delay: 0.0,
+        kind: WeaponKind::MainGun,
+        data: WeaponData::Bullets(Barrage {
+            damage: 12.0,
+            coefficient: 1.125,
+            scaling: 1.0,
+            scaling_stat: StatKind::FP,
+            range: 50.0,
+            firing_angle: 360.0,
+            salvo_time: 0.25,
+            bullets: vec![Bullet {
+                bullet_id: 2000,
+                amount: 3,
+                kind: BulletKind::Cannon,
+                ammo: AmmoKind::HE,
+                pierce: 0,
+                velocity: 12.0,
+                modifiers: ArmorModifiers(1.375, 0.875, 0.75),
+                flags: BulletFlags::IGNORE_DIVE,
+                attach_buff: vec![BuffInfo { buff_id: 3000, probability: 0.0625, level: 0 }],
+                extra: BulletExtra::Spread(BulletSpread { spread_x: 5.0, spread_y: 5.0, hit_range: 8.0 }),
+            }],
+        }),
+    }
+}
+
+/// Creates a destroyer with one skin, one skill and a shadow equip.
+pub fn ship() -> ShipData {
+    let stat = ShipStat::new().with_base(100.0).with_growth(1500.0).with_fixed(4.0);
+    let skin = ShipSkin {
+        skin_id: 100010,
+        image_key: "testship".to_owned(),
+        name: "Test Ship".to_owned(),
+        description: "The default skin.".to_owned(),
+        words: words("Hello."),
+        words_extra: Some(Box::new(words("Hello again."))),
+        illustrator: Some("Artist".to_owned()),
+        voice_actor: None,
+    };
+
+    ShipData {
+        group_id: 10001,
+        name: "Test Ship".to_owned(),
+        rarity: ShipRarity::SR,
+        faction: Faction::EagleUnion,
+        hull_type: HullType::Destroyer,
+        stars: 5,
+        enhance_kind: EnhanceKind::Normal,
+        stats: ShipStatBlock {
+            hp: stat,
+            armor: ShipArmor::Light,
+            rld: stat,
+            fp: stat,
+            trp: stat,
+            eva: stat,
+            aa: stat,
+            avi: ShipStat::new(),
+            acc: stat,
+            asw: stat,
+            spd: 43.25,
+            lck: 60.0,
+            cost: 9,
+            oxy: 0,
+            amo: 5,
+        },
+        default_skin_id: 100010,
+        equip_slots: vec![
+            EquipSlot {
+                allowed: vec![EquipKind::DestroyerGun],
+                mount: Some(EquipWeaponMount { efficiency: 1.25, mounts: 2, parallel: 1, preload: 0 }),
+            },
+            EquipSlot {
+                allowed: vec![EquipKind::Auxiliary],
+                mount: None,
+            },
+        ],
+        shadow_equip: vec![ShadowEquip {
+            name: "Shadow Gun".to_owned(),
+            efficiency: 0.75,
+            weapons: vec![weapon()],
+        }],
+        depth_charges: Vec::new(),
+        skills: vec![Skill {
+            buff_id: 4000,
+            name: "Test Skill".to_owned(),
+            description: "Does things.".to_owned(),
+            category: SkillCategory::Offense,
+            barrages: vec![SkillBarrage {
+                skill_id: 5000,
+                attacks: vec![SkillAttack { target: SkillAttackTarget::Nearest, weapon: weapon() }],
+            }],
+            new_weapons: vec![BuffWeapon { duration: Some(10.0), weapon: weapon() }],
+            icon_key: Some("testskill".to_owned()),
+            level_descriptions: vec!["Does a thing.".to_owned(), "Does things.".to_owned()],
+            cooldown: None,
+            effects: Vec::new(),
+        }],
+        retrofits: Vec::new(),
+        skins: vec![skin],
+    }
+}
+
+/// Creates a destroyer gun with [`weapon`] and two tiers.
+pub fn equip() -> Equip {
+    Equip {
+        equip_id: 6000,
+        name: "Test Gun".to_owned(),
+        description: "A gun.".to_owned(),
+        kind: EquipKind::DestroyerGun,
+        rarity: EquipRarity::SR,
+        faction: Faction::Universal,
+        weapons: vec![weapon()],
+        skills: Vec::new(),
+        stat_bonuses: vec![EquipStatBonus { stat_kind: StatKind::FP, amount: 5.0 }],
+        tiers: vec![
+            vec![EquipStatBonus { stat_kind: StatKind::FP, amount: 1.0 }],
+            vec![EquipStatBonus { stat_kind: StatKind::FP, amount: 5.0 }],
+        ],
+        hull_disallowed: vec![HullType::Submarine],
+    }
+}
@@ -145,7 +145,12 @@ pub fn to_augment_rarity(num: u32) -> AugmentRarity {
 
 /// Converts a name to a stat kind.
 pub fn to_stat_kind(stat: &str) -> StatKind {
-    match stat {
+    try_to_stat_kind(stat).unwrap_or(StatKind::EVA)
+}
+
+/// Converts a name to a stat kind, if it is known.
+pub fn try_to_stat_kind(stat: &str) -> Option<StatKind> {
+    Some(match stat {
         "durability" => StatKind::HP,
         "cannon" => StatKind::FP,
         "torpedo" => StatKind::TRP,
@@ -157,8 +162,8 @@ pub fn to_stat_kind(stat: &str) -> StatKind {
         "speed" => StatKind::SPD,
         "luck" => StatKind::LCK,
         "antisub" => StatKind::ASW,
-        _ => StatKind::EVA
-    }
+        _ => return None,
+    })
 }
 
 /// Converts a weapon attack attribute number to a stat kind.
@@ -1,36 +1,30 @@
 //! Helper methods to apply enhance/strengthen data.
 
+use azur_lane::equip::EquipStatBonus;
 use azur_lane::ship::*;
 
+use crate::convert_al;
+
 pub mod blueprint;
 pub mod meta;
 pub mod retrofit;
 
 /// Adds to the base amount of the named stat.
 pub fn add_to_stats_base(stats: &mut ShipStatBlock, stat: &str, amount: f64) -> bool {
-    add_to_stats_intl(stats, stat, amount, ShipStat::new().with_base(amount))
+    let Some(stat_kind) = convert_al::try_to_stat_kind(stat) else {
+        return false;
+    };
+
+    stats.add_to_stat(stat_kind, ShipStat::new().with_base(amount), amount);
+    true
 }
 
 /// Adds to the fixed amount of the named stat.
 pub fn add_to_stats_fixed(stats: &mut ShipStatBlock, stat: &str, amount: f64) -> bool {
-    add_to_stats_intl(stats, stat, amount, ShipStat::new().with_fixed(amount))
-}
-
-fn add_to_stats_intl(stats: &mut ShipStatBlock, stat: &str, amount: f64, amount_as_stat: ShipStat) -> bool {
-    match stat {
-        "durability" => stats.hp += amount_as_stat,
-        "cannon" => stats.fp += amount_as_stat,
-        "torpedo" => stats.trp += amount_as_stat,
-        "antiaircraft" => stats.aa += amount_as_stat,
-        "air" => stats.avi += amount_as_stat,
-        "reload" => stats.rld += amount_as_stat,
-        "hit" => stats.acc += amount_as_stat,
-        "dodge" => stats.eva += amount_as_stat,
-        "speed" => stats.spd += amount,
-        "luck" => stats.lck += amount,
-        "antisub" => stats.asw += amount_as_stat,
-        _ => { return false; }
+    let Some(stat_kind) = convert_al::try_to_stat_kind(stat) else {
+        return false;
     };
 
+    stats.add_bonus(&EquipStatBonus { stat_kind, amount });
     true
 }
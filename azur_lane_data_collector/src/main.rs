@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::io::Write;
+use std::io::BufWriter;
 use std::path::Path;
 
 use clap::Parser;
@@ -12,15 +12,18 @@ use azur_lane::ship::*;
 mod convert_al;
 mod enhance;
 mod macros;
+mod manifest;
 mod model;
 mod parse;
+mod timing;
 
 use model::*;
+use timing::PhaseTimings;
 
 #[derive(Debug, Parser)]
 struct Cli {
     /// The path that the game scripts live in.
-    #[arg(short, long, num_args = 1.., required = true)]
+    #[arg(short, long, num_args = 1.., required_unless_present = "emit_schema")]
     inputs: Vec<String>,
     /// The output directory.
     #[arg(short, long)]
@@ -34,6 +37,36 @@ struct Cli {
     #[arg(short, long)]
     minimize: bool,
 
+    /// Use short numeric keys instead of field names in the output JSON file.
+    #[arg(long)]
+    compact_keys: bool,
+
+    /// The language code of the input data, f.e. "EN" or "JP".
+    /// Determines the output file name. Defaults to English.
+    #[arg(short, long, value_parser = parse_language)]
+    language: Option<Language>,
+
+    /// The format to write extracted images in, "webp" or "png". Defaults to webp.
+    #[arg(long, value_parser = parse_image_format)]
+    image_format: Option<ImageFormat>,
+
+    /// Only load and check the data. No files are written.
+    #[arg(long)]
+    check: bool,
+
+    /// Fail if any skill description has unresolved placeholders.
+    #[arg(long)]
+    strict: bool,
+
+    /// Write a JSON schema for the output data to the output directory and exit.
+    #[arg(long)]
+    emit_schema: bool,
+
+    /// Only build the ships with these group IDs. May be repeated.
+    /// Only augment modules unique to these ships are kept.
+    #[arg(long, value_name = "GROUP_ID")]
+    only: Vec<u32>,
+
     #[arg(long)]
     test: bool
 }
@@ -41,13 +74,20 @@ struct Cli {
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let start = std::time::Instant::now();
+    let mut timings = PhaseTimings::default();
+
+    if cli.emit_schema {
+        return emit_schema(cli.out.as_deref().unwrap_or("azur_lane_data"));
+    }
 
     let out_data = {
         // Expect at least 1 input
-        let mut out_data = load_definition(&cli.inputs[0], start)?;
+        let mut out_data = load_definition(&cli.inputs[0], &cli.only, start, &mut timings)?;
+        out_data.language = cli.language;
+        out_data.image_format = cli.image_format.unwrap_or_default();
         for input in cli.inputs.iter().skip(1) {
             println!("Loading more from '{}'...", input);
-            let next = load_definition(input, start)?;
+            let next = load_definition(input, &cli.only, start, &mut timings)?;
             merge_out_data(&mut out_data, next);
             println!("Merged data. ({:.2?})", start.elapsed());
         }
@@ -55,53 +95,178 @@ fn main() -> anyhow::Result<()> {
         out_data
     };
 
+    if cli.strict {
+        let unresolved = all_skills(&out_data)
+            .filter(|s| parse::skill::find_unresolved_placeholder(&s.description).is_some())
+            .count();
+
+        if unresolved != 0 {
+            anyhow::bail!("{unresolved} skill descriptions have unresolved placeholders.");
+        }
+    }
+
+    if cli.check {
+        timings.report();
+        return check_definition(&out_data);
+    }
+
     let out_dir = cli.out.as_deref().unwrap_or("azur_lane_data");
     {
         println!("Writing output...");
+        let phase_start = std::time::Instant::now();
 
         fs::create_dir_all(out_dir)?;
-        let f = fs::File::create(Path::new(out_dir).join("main.json"))?;
-        if cli.minimize {
-            serde_json::to_writer(&f, &out_data)?;
+        let file_name = cli.language.unwrap_or(Language::English).definition_file_name();
+        let f = fs::File::create(Path::new(out_dir).join(file_name))?;
+
+        // serde_json writes values as it goes, so the output is never held in memory in full.
+        // it does issue many tiny writes though, so buffer them rather than hitting the file each time.
+        let mut writer = BufWriter::new(f);
+        if cli.compact_keys {
+            // the keys can only be renamed on a generic value, so this does hold a full copy.
+            let mut value = serde_json::to_value(&out_data)?;
+            keys::compact_keys(&mut value);
+            if let Some(map) = value.as_object_mut() {
+                map.insert(keys::KEY_SCHEME_FIELD.to_owned(), serde_json::to_value(keys::KeyScheme::Compact)?);
+            }
+
+            write_json(&mut writer, &value, cli.minimize)?;
         } else {
-            serde_json::to_writer_pretty(&f, &out_data)?;
+            write_json(&mut writer, &out_data, cli.minimize)?;
         }
 
+        // this also flushes the remaining buffer
+        let f = writer.into_inner()?;
+
+        timings.add_since("Serialize", phase_start);
         println!("Written {} bytes. ({:.2?})", f.metadata()?.len(), start.elapsed());
     }
 
     if let Some(assets) = cli.assets.as_deref() {
-        // Extract and save chibis for all skins.
-        fs::create_dir_all(Path::new(out_dir).join("chibi"))?;
+        let image_format = out_data.image_format;
 
+        // Extract and save chibis for all skins.
         println!("Extracting chibis...");
+        let phase_start = std::time::Instant::now();
+
+        let image_keys = out_data.ships.iter()
+            .flat_map(|s| s.skins.iter())
+            .map(|s| s.image_key.as_str());
+
+        let counts = extract_images(
+            Path::new(out_dir).join("chibi"), image_keys, image_format,
+            |key| parse::image::read_chibi_source(assets, key),
+            |key, source| parse::image::load_chibi_image(source, key, image_format),
+        )?;
+
+        timings.add_since("Chibi extract", phase_start);
+        println!("Extracted chibis ({}/{}); {} new, {} updated. {:.2?}", counts.extracted, counts.total, counts.new, counts.updated, start.elapsed());
+
+        // Extract and save icons for all skills.
+        println!("Extracting skill icons...");
+        let phase_start = std::time::Instant::now();
+
+        // many skills share icons, so only extract each one once
+        let icon_keys: BTreeSet<&str> = all_skills(&out_data)
+            .filter_map(|s| s.icon_key.as_deref())
+            .collect();
+
+        let counts = extract_images(
+            Path::new(out_dir).join("skill_icons"), icon_keys, image_format,
+            |key| parse::image::read_skill_icon_source(assets, key),
+            |key, source| parse::image::load_skill_icon(source, key, image_format),
+        )?;
+
+        timings.add_since("Skill icon extract", phase_start);
+        println!("Extracted skill icons ({}/{}); {} new, {} updated; {} missing. {:.2?}", counts.extracted, counts.total, counts.new, counts.updated, counts.total - counts.extracted, start.elapsed());
+    }
 
-        let mut extract_count = 0usize;
-        let mut total_count = 0usize;
-        let mut new_count = 0usize;
+    timings.report();
+    Ok(())
+}
 
-        for skin in out_data.ships.iter().flat_map(|s| s.skins.iter()) {
-            total_count += 1;
+/// Counts of images processed by [`extract_images`].
+#[derive(Debug, Default)]
+struct ExtractCounts {
+    /// The amount of requested images.
+    total: usize,
+    /// The amount of images that are now present, including unchanged ones.
+    extracted: usize,
+    /// The amount of images that were written for the first time.
+    new: usize,
+    /// The amount of images that were overwritten because their source changed.
+    updated: usize,
+}
 
-            if let Some(image) = parse::image::load_chibi_image(assets, &skin.image_key)? {
-                extract_count += 1;
+/// Extracts images to a directory, skipping ones whose source didn't change since the last run.
+///
+/// The source hashes are tracked in an [`ExtractManifest`](manifest::ExtractManifest) within the directory.
+fn extract_images<'a>(
+    dir: std::path::PathBuf,
+    keys: impl IntoIterator<Item = &'a str>,
+    image_format: ImageFormat,
+    read_source: impl Fn(&str) -> anyhow::Result<Option<Vec<u8>>>,
+    load_image: impl Fn(&str, &[u8]) -> anyhow::Result<Option<Vec<u8>>>,
+) -> anyhow::Result<ExtractCounts> {
+    fs::create_dir_all(&dir)?;
+
+    let mut manifest = manifest::ExtractManifest::load(&dir);
+    let mut counts = ExtractCounts::default();
+
+    for key in keys {
+        counts.total += 1;
+
+        let Some(source) = read_source(key)? else {
+            continue;
+        };
 
-                let path = utils::join_path![out_dir, "chibi", &skin.image_key; "webp"];
-                if let Ok(mut f) = fs::OpenOptions::new().create_new(true).write(true).open(path) {
-                    new_count += 1;
+        let path = utils::join_path![&dir, key; image_format.extension()];
+        let exists = path.exists();
+        let hash = parse::image::hash_source(&source);
 
-                    f.write_all(&image)?;
-                }
-            }
+        if exists && manifest.is_current(key, hash) {
+            counts.extracted += 1;
+            continue;
         }
 
-        println!("Extracted chibis ({extract_count}/{total_count}); {new_count} new. {:.2?}", start.elapsed());
+        if let Some(image) = load_image(key, &source)? {
+            counts.extracted += 1;
+            if exists {
+                counts.updated += 1;
+            } else {
+                counts.new += 1;
+            }
+
+            fs::write(&path, image)?;
+            manifest.set(key, hash);
+        }
     }
 
+    manifest.save()?;
+    Ok(counts)
+}
+
+fn emit_schema(out_dir: &str) -> anyhow::Result<()> {
+    println!("Writing schema...");
+
+    fs::create_dir_all(out_dir)?;
+    let f = fs::File::create(Path::new(out_dir).join("main.schema.json"))?;
+    serde_json::to_writer_pretty(&f, &azur_lane::definition_schema())?;
+
+    println!("Written {} bytes.", f.metadata()?.len());
     Ok(())
 }
 
-fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionData, anyhow::Error> {
+fn write_json(writer: impl std::io::Write, value: &impl serde::Serialize, minimize: bool) -> serde_json::Result<()> {
+    if minimize {
+        serde_json::to_writer(writer, value)
+    } else {
+        serde_json::to_writer_pretty(writer, value)
+    }
+}
+
+fn load_definition(input: &str, only: &[u32], start: std::time::Instant, timings: &mut PhaseTimings) -> Result<DefinitionData, anyhow::Error> {
+    let phase_start = std::time::Instant::now();
     let lua = Lua::new();
 
     lua.globals().raw_set("AZUR_LANE_DATA_PATH", input)?;
@@ -110,11 +275,13 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
         .set_mode(mlua::ChunkMode::Text)
         .exec()?;
 
+    timings.add_since("Lua init", phase_start);
     println!("Init done. ({:.2?})", start.elapsed());
 
     let pg: LuaTable = lua.globals().get("pg").context("global pg")?;
 
     let ships = {
+        let phase_start = std::time::Instant::now();
         let ship_data_template: LuaTable = pg.get("ship_data_template").context("global pg.ship_data_template")?;
         let ship_data_template_all: LuaTable = ship_data_template.get("all").context("global pg.ship_data_template.all")?;
         let ship_data_statistics: LuaTable = pg.get("ship_data_statistics").context("global pg.ship_data_statistics")?;
@@ -142,13 +309,30 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
         let ship_skin_words_extra: LuaTable = pg.get("ship_skin_words_extra").context("global pg.ship_skin_words_extra")?;
 
         let mut groups = HashMap::new();
+        let mut skipped = 0usize;
         ship_data_template_all.for_each(|_: u32, id: u32| {
             if (900000..=900999).contains(&id) {
                 return Ok(())
             }
 
-            let template: LuaTable = ship_data_template.get(id).with_context(context!("ship_data_template with id {id}"))?;
-            let group_id: u32 = template.get("group_type").with_context(context!("group_type of ship_data_template with id {id}"))?;
+            // placeholder and unreleased entries may lack some of the data needed later.
+            // skip those here rather than failing the entire run when building the ship.
+            let template: Option<LuaTable> = ship_data_template.get(id).with_context(context!("ship_data_template with id {id}"))?;
+            let statistics: Option<LuaTable> = ship_data_statistics.get(id).with_context(context!("ship_data_statistics with id {id}"))?;
+
+            let group_id: Option<u32> = template.as_ref().and_then(|t| t.get("group_type").ok());
+            let strengthen_id: Option<u32> = template.as_ref().and_then(|t| t.get("strengthen_id").ok());
+            let has_strengthen = strengthen_id.is_some_and(|s| {
+                [&ship_data_strengthen, &ship_data_blueprint, &ship_strengthen_meta]
+                    .into_iter()
+                    .any(|t| t.contains_key(s).unwrap_or(false))
+            });
+
+            let (Some(group_id @ 1..), Some(_), true) = (group_id, statistics, has_strengthen) else {
+                eprintln!("Warning: skipping malformed ship entry {id}.");
+                skipped += 1;
+                return Ok(())
+            };
 
             groups.entry(group_id)
                 .or_insert_with(|| ShipGroup { id: group_id, members: Vec::new() })
@@ -157,7 +341,18 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
             Ok(())
         })?;
 
-        println!("Ship groups: {} ({:.2?})", groups.len(), start.elapsed());
+        println!("Ship groups: {} (skipped {} entries) ({:.2?})", groups.len(), skipped, start.elapsed());
+
+        if !only.is_empty() {
+            for id in only {
+                if !groups.contains_key(id) {
+                    eprintln!("Warning: requested ship group {id} not found.");
+                }
+            }
+
+            groups.retain(|id, _| only.contains(id));
+            println!("Only building ship groups: {}", groups.len());
+        }
 
         let make_ship_set = |id: u32| -> LuaResult<ShipSet> {
             let template: LuaTable = ship_data_template.get(id).with_context(context!("!ship_data_template with id {id}"))?;
@@ -190,6 +385,7 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
         };
 
         let config = &*CONFIG;
+        let mut skin_time = std::time::Duration::ZERO;
         let mut ships = groups.into_values().map(|group| {
             let members = group.members.into_iter()
                 .map(make_ship_set)
@@ -233,13 +429,20 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
                 }
             }
 
+            let skin_start = std::time::Instant::now();
             for raw_skin in raw_skins {
                 mlb.skins.push(parse::skin::load_skin(&raw_skin)?);
             }
 
+            skin_time += skin_start.elapsed();
+
+            verify_default_skins(&mlb);
             Ok(mlb)
         }).collect::<anyhow::Result<Vec<_>>>()?;
 
+        // skins are loaded while building ships, so split their time out
+        timings.add("Ship parse", phase_start.elapsed().saturating_sub(skin_time));
+        timings.add("Skin load", skin_time);
         println!("Built Ship data. ({:.2?})", start.elapsed());
 
         ships.sort_by_key(|t| t.group_id);
@@ -247,6 +450,7 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
     };
 
     let equips = {
+        let phase_start = std::time::Instant::now();
         let equip_data_template: LuaTable = pg.get("equip_data_template").context("global pg.equip_data_template")?;
         let equip_data_template_all: LuaTable = equip_data_template.get("all").context("global pg.equip_data_template.all")?;
         let equip_data_statistics: LuaTable = pg.get("equip_data_statistics").context("global pg.equip_data_statistics")?;
@@ -268,9 +472,14 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
         println!("Equips: {} ({:.2?})", equips.len(), start.elapsed());
 
         let mut equips = equips.into_iter().map(|id| {
-            parse::skill::load_equip(&lua, id)
+            let tiers = parse::skill::load_equip_tiers(&lua, id)?;
+            parse::skill::load_equip(&lua, id).map(|mut equip| {
+                equip.tiers = tiers;
+                equip
+            })
         }).collect::<LuaResult<Vec<_>>>()?;
 
+        timings.add_since("Equip parse", phase_start);
         println!("Built Equip data. ({:.2?})", start.elapsed());
 
         equips.sort_by_key(|t| t.faction);
@@ -278,6 +487,7 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
     };
 
     let augments = {
+        let phase_start = std::time::Instant::now();
         let spweapon_data_statistics: LuaTable = pg.get("spweapon_data_statistics").context("global pg.spweapon_data_statistics")?;
         let spweapon_data_statistics_all: LuaTable = spweapon_data_statistics.get("all").context("global pg.spweapon_data_statistics.all")?;
 
@@ -303,8 +513,13 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
             parse::augment::load_augment(&lua, &data)
         }).collect::<LuaResult<Vec<_>>>()?;
 
+        timings.add_since("Augment parse", phase_start);
         println!("Built Augment data. ({:.2?})", start.elapsed());
 
+        if !only.is_empty() {
+            augments.retain(|a| a.usability.unique_ship_id().is_some_and(|id| only.contains(&id)));
+        }
+
         augments.sort_by_key(|t| t.augment_id);
         augments
     };
@@ -319,10 +534,69 @@ fn load_definition(input: &str, start: std::time::Instant) -> Result<DefinitionD
     Ok(DefinitionData {
         ships,
         equips,
-        augments
+        augments,
+        language: None,
+        image_format: ImageFormat::default(),
+        key_scheme: keys::KeyScheme::Full,
     })
 }
 
+/// Iterates over all skills of ships, retrofits, equipment, and augment modules.
+fn all_skills(data: &DefinitionData) -> impl Iterator<Item = &azur_lane::skill::Skill> {
+    let ship_skills = data.ships.iter()
+        .flat_map(|s| std::iter::once(s).chain(&s.retrofits))
+        .flat_map(|s| &s.skills);
+
+    let equip_skills = data.equips.iter()
+        .flat_map(|e| &e.skills);
+
+    let augment_skills = data.augments.iter()
+        .flat_map(|a| a.effect.iter().chain(a.skill_upgrade.as_ref().map(|u| &u.skill)));
+
+    ship_skills.chain(equip_skills).chain(augment_skills)
+}
+
+/// Reports the loaded counts and any references to unknown entries.
+///
+/// Fails if there are any unknown references.
+fn check_definition(data: &DefinitionData) -> anyhow::Result<()> {
+    println!("Ships: {}, Equips: {}, Augments: {}", data.ships.len(), data.equips.len(), data.augments.len());
+
+    let ship_ids: HashSet<u32> = data.ships.iter().map(|s| s.group_id).collect();
+    let mut unknown = 0usize;
+
+    for ship in &data.ships {
+        if ship.default_skin().is_none() {
+            eprintln!("Error: ship {} ({}) references unknown default skin {}.", ship.group_id, ship.name, ship.default_skin_id);
+            unknown += 1;
+        }
+    }
+
+    for augment in &data.augments {
+        if let Some(ship_id) = augment.usability.unique_ship_id() {
+            if !ship_ids.contains(&ship_id) {
+                eprintln!("Error: augment {} ({}) references unknown ship {}.", augment.augment_id, augment.name, ship_id);
+                unknown += 1;
+            }
+        }
+    }
+
+    if unknown != 0 {
+        anyhow::bail!("Check failed with {unknown} unknown references.");
+    }
+
+    println!("Check passed.");
+    Ok(())
+}
+
+fn parse_language(code: &str) -> Result<Language, String> {
+    Language::from_code(code).ok_or_else(|| format!("unknown language code '{code}'"))
+}
+
+fn parse_image_format(extension: &str) -> Result<ImageFormat, String> {
+    ImageFormat::from_extension(extension).ok_or_else(|| format!("unknown image format '{extension}'"))
+}
+
 fn fix_up_retrofitted_data(ship: &mut ShipData, set: &ShipSet) -> LuaResult<()> {
     let buff_list_display: Vec<u32> = set.template.get("buff_list_display")?;
     ship.skills.sort_by_key(|s| {
@@ -335,6 +609,15 @@ fn fix_up_retrofitted_data(ship: &mut ShipData, set: &ShipSet) -> LuaResult<()>
     Ok(())
 }
 
+fn verify_default_skins(ship: &ShipData) {
+    // retrofits reference skins held by the base ship
+    for data in std::iter::once(ship).chain(&ship.retrofits) {
+        if ship.skin_by_id(data.default_skin_id).is_none() {
+            eprintln!("Warning: default skin {} of ship {} ({}) does not exist.", data.default_skin_id, data.group_id, data.name);
+        }
+    }
+}
+
 fn merge_out_data(main: &mut DefinitionData, next: DefinitionData) {
     for next_ship in next.ships {
         if let Some(main_ship) = main.ships.iter_mut().find(|s| s.group_id == next_ship.group_id) {
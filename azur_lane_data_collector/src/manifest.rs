@@ -0,0 +1,53 @@
+//! Tracks which asset data images were extracted from.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maps image keys to an FNV-1a hash of the asset file they were extracted from.
+///
+/// This is stored next to the extracted images, so later runs can skip images whose
+/// source didn't change and re-extract the ones that did.
+#[derive(Debug)]
+pub struct ExtractManifest {
+    path: PathBuf,
+    hashes: BTreeMap<String, u64>,
+}
+
+impl ExtractManifest {
+    /// The file name of the manifest within the image directory.
+    const FILE_NAME: &'static str = "manifest.json";
+
+    /// Loads the manifest in the image directory.
+    ///
+    /// If there is no manifest yet, or it cannot be read, it starts out empty.
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(Self::FILE_NAME);
+        let hashes = match fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data).unwrap_or_else(|err| {
+                eprintln!("Warning: Ignoring invalid manifest {}: {err}", path.display());
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+
+        Self { path, hashes }
+    }
+
+    /// Whether the image with the key was extracted from a source with the same hash.
+    pub fn is_current(&self, key: &str, hash: u64) -> bool {
+        self.hashes.get(key) == Some(&hash)
+    }
+
+    /// Records the source hash for an extracted image.
+    pub fn set(&mut self, key: &str, hash: u64) {
+        self.hashes.insert(key.to_owned(), hash);
+    }
+
+    /// Writes the manifest back to its file.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let f = fs::File::create(&self.path)?;
+        serde_json::to_writer_pretty(f, &self.hashes)?;
+        Ok(())
+    }
+}
@@ -1,42 +1,99 @@
 use std::io::Cursor;
 
-use image::{imageops, ImageFormat};
+use azur_lane::ImageFormat;
+use image::{imageops, DynamicImage};
 use unity_read::classes::{ClassID, Texture2D};
-use unity_read::unity_fs::{UnityFsData, UnityFsFile};
+use unity_read::unity_fs::UnityFsFile;
 
 // shipmodels: chibi sprites, 1:1
 // paintingface: alternative faces, 0/1:1
 // painting:
 // - tex: full sprite, background 1:1
 // - n_tex: full sprite, no background 0/1:1
+// skillicon: skill icons, 1:1
 
-pub fn load_chibi_image(dir: &str, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+/// Reads the asset file a chibi is loaded from.
+///
+/// Returns [`None`] if the file doesn't exist.
+pub fn read_chibi_source(dir: &str, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    read_texture_source(dir, "shipmodels", name)
+}
+
+/// Reads the asset file a skill icon is loaded from.
+///
+/// Returns [`None`] if the file doesn't exist.
+pub fn read_skill_icon_source(dir: &str, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    read_texture_source(dir, "skillicon", name)
+}
+
+fn read_texture_source(dir: &str, folder: &str, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
     let name = name.to_ascii_lowercase();
-    let Ok(mut file) = std::fs::File::open(utils::join_path!(dir, "shipmodels", &name)) else {
-        println!("Skin shipmodels file {name} not found.");
-        return Ok(None)
-    };
+    match std::fs::read(utils::join_path!(dir, folder, &name)) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            println!("{folder} file {name} not found.");
+            Ok(None)
+        },
+        Err(err) => Err(err)?,
+    }
+}
+
+/// Hashes the contents of an asset file for the extract manifest.
+///
+/// This uses FNV-1a, so the hash is stable across builds and can be persisted.
+#[must_use]
+pub fn hash_source(source: &[u8]) -> u64 {
+    use std::hash::Hasher;
+
+    let mut hasher = utils::Fnv1aHasher::new();
+    hasher.write(source);
+    hasher.finish()
+}
+
+pub fn load_chibi_image(source: &[u8], name: &str, format: ImageFormat) -> anyhow::Result<Option<Vec<u8>>> {
+    load_texture_image(source, "shipmodels", name, format)
+}
+
+pub fn load_skill_icon(source: &[u8], name: &str, format: ImageFormat) -> anyhow::Result<Option<Vec<u8>>> {
+    load_texture_image(source, "skillicon", name, format)
+}
+
+fn load_texture_image(source: &[u8], folder: &str, name: &str, format: ImageFormat) -> anyhow::Result<Option<Vec<u8>>> {
+    let name = name.to_ascii_lowercase();
+    let mut file = Cursor::new(source);
 
     let unity_fs = UnityFsFile::open(&mut file)?;
-    for entry in unity_fs.entries() {
-        if let UnityFsData::SerializedFile(ser_file) = entry.read()? {
-            let texture = ser_file.objects()
-                .filter_map(Result::ok)
-                .filter(|o| o.class_id() == ClassID::Texture2D)
-                .filter_map(|o| o.try_into_class::<Texture2D>().ok())
-                .find(|t| t.name.to_ascii_lowercase() == name);
-
-            if let Some(texture) = texture {
-                let mut image = texture.read_data(&unity_fs)?.decode()?;
-                imageops::flip_vertical_in_place(&mut image);
-
-                let mut writer = Cursor::new(Vec::new());
-                image.write_to(&mut writer, ImageFormat::WebP)?;
-                return Ok(Some(writer.into_inner()))
-            }
+    for ser_file in unity_fs.serialized_files() {
+        let ser_file = ser_file?;
+        let texture = ser_file.objects()
+            .filter_map(Result::ok)
+            .filter(|o| o.class_id() == ClassID::Texture2D)
+            .filter(|o| o.read_name().ok().flatten().is_some_and(|n| n.eq_ignore_ascii_case(&name)))
+            .find_map(|o| o.try_into_class::<Texture2D>().ok());
+
+        if let Some(texture) = texture {
+            let data = texture.read_data(&unity_fs)?;
+            let mut image = data.decode()?;
+            imageops::flip_vertical_in_place(&mut image);
+
+            // drop the alpha channel if it's unused so the output is smaller
+            let image = if data.has_alpha(&image) {
+                DynamicImage::ImageRgba8(image)
+            } else {
+                DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(image).into_rgb8())
+            };
+
+            let mut writer = Cursor::new(Vec::new());
+            let format = match format {
+                ImageFormat::WebP => image::ImageFormat::WebP,
+                ImageFormat::Png => image::ImageFormat::Png,
+            };
+
+            image.write_to(&mut writer, format)?;
+            return Ok(Some(writer.into_inner()))
         }
     }
 
-    println!("Skin shipmodels image {name} not present.");
+    println!("{folder} image {name} not present.");
     Ok(None)
 }
@@ -17,22 +17,47 @@ pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
 
     let skill: LuaTable = skill_data_template.get(skill_id).with_context(context!("skill with id {}", skill_id))?;
     let name: String = skill.get("name").with_context(context!("name of skill with id {}", skill_id))?;
-    let mut desc: String = skill.get("desc").with_context(context!("desc of skill with id {}", skill_id))?;
+    let desc_template: String = skill.get("desc").with_context(context!("desc of skill with id {}", skill_id))?;
     let desc_add: Vec<Vec<Vec<String>>> = skill.get("desc_add").with_context(context!("desc_add of skill with id {}", skill_id))?;
 
-    for (slot, data_set) in desc_add.iter().enumerate() {
-        if let Some(last) = data_set.last() {
-            if let Some(text) = last.first() {
+    // resolves the placeholders for a level index. slots without data for that level use their last entry.
+    let resolve_desc = |level: usize| {
+        let mut desc = desc_template.clone();
+        for (slot, data_set) in desc_add.iter().enumerate() {
+            if let Some(text) = data_set.get(level).or(data_set.last()).and_then(|l| l.first()) {
                 let placeholder = format!("${}", slot + 1);
                 desc = desc.replace(&placeholder, text);
             }
         }
+
+        desc
+    };
+
+    let level_count = desc_add.iter().map(Vec::len).max().unwrap_or(0);
+    let level_descriptions: Vec<String> = match level_count {
+        0 | 1 => Vec::new(),
+        _ => (0..level_count).map(resolve_desc).collect(),
+    };
+
+    let desc = resolve_desc(usize::MAX);
+
+    if let Some(placeholder) = find_unresolved_placeholder(&desc) {
+        eprintln!("Warning: skill {skill_id} ({name}) has unresolved placeholder {placeholder}.");
     }
 
+    // the icon is usually numeric, but is used as a string key
+    let icon_key = match skill.get("icon").with_context(context!("icon of skill with id {}", skill_id))? {
+        LuaValue::String(s) => Some(s.to_str()?.to_owned()),
+        LuaValue::Integer(i) => Some(i.to_string()),
+        _ => None,
+    };
+
     if let Some(skill) = CONFIG.predefined_skills.get(&skill_id) {
         let mut skill = skill.clone();
         skill.name = name;
         skill.description = desc;
+        skill.icon_key = icon_key;
+        skill.level_descriptions = level_descriptions;
 
         return Ok(skill);
     }
@@ -41,6 +66,8 @@ pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
     let category = convert_al::to_skill_category(category);
 
     let buff = require_buff_data(lua, skill_id)?;
+    let (cooldown, effects) = load_effect_values(&buff)?;
+
     let mut context = ReferencedWeaponsContext::default();
     search_referenced_weapons(&mut context, SkillContext {
         lua,
@@ -56,6 +83,52 @@ pub fn load_skill(lua: &Lua, skill_id: u32) -> LuaResult<Skill> {
         description: desc,
         barrages: context.barrages,
         new_weapons: context.new_weapons,
+        icon_key,
+        level_descriptions,
+        cooldown,
+        effects,
+    })
+}
+
+/// Loads the raw numeric values of the effects of a buff at its highest level.
+fn load_effect_values(buff: &LuaTable) -> LuaResult<(Option<f64>, Vec<SkillEffectValue>)> {
+    let mut cooldown = None;
+    let mut effects = Vec::new();
+
+    for entry in get_effect_list(buff)?.into_iter().flatten() {
+        let entry_type: String = entry.get("type").with_context(context!("skill/buff effect_list entry type: {:#?}", entry))?;
+        let Some(arg_list) = entry.get::<_, Option<LuaTable>>("arg_list").context("skill/buff effect_list entry arg_list")? else {
+            continue;
+        };
+
+        if matches!(entry_type.as_str(), "BattleBuffAddAttr" | "BattleBuffAddAttrRatio") {
+            let attr: Option<String> = arg_list.get("attr").context("skill/buff effect_list entry arg_list attr")?;
+            let number: Option<f64> = arg_list.get("number").context("skill/buff effect_list entry arg_list number")?;
+            if let (Some(name), Some(mut value)) = (attr, number) {
+                // ratios are specified in 1/10000ths
+                if entry_type == "BattleBuffAddAttrRatio" {
+                    value /= 10000.0;
+                }
+
+                effects.push(SkillEffectValue { name, value });
+            }
+        }
+
+        // periodic effects trigger on update, with the interval as their time
+        let trigger: Option<Vec<String>> = entry.get("trigger").context("skill/buff effect_list entry trigger")?;
+        if cooldown.is_none() && trigger.is_some_and(|t| t.iter().any(|t| t == "onUpdate")) {
+            cooldown = arg_list.get("time").context("skill/buff effect_list entry arg_list time")?;
+        }
+    }
+
+    Ok((cooldown, effects))
+}
+
+/// Finds the first `$N` placeholder left in a skill description.
+pub fn find_unresolved_placeholder(desc: &str) -> Option<&str> {
+    desc.match_indices('$').find_map(|(index, _)| {
+        let len = desc[index + 1..].bytes().take_while(u8::is_ascii_digit).count();
+        (len != 0).then(|| &desc[index..=index + len])
     })
 }
 
@@ -90,21 +163,6 @@ pub fn load_equip(lua: &Lua, equip_id: u32) -> LuaResult<Equip> {
         .map(|id| load_skill(lua, id))
         .collect::<LuaResult<Vec<_>>>()?;
 
-    macro_rules! stat_bonus {
-        ($index:literal) => {{
-            match statistics.get(concat!("attribute_", $index)).with_context(context!("attribute_{} for equip with id {equip_id}", $index))? {
-                Some(stat_kind) => {
-                    let stat_kind: String = stat_kind;
-                    Some(EquipStatBonus {
-                        stat_kind: convert_al::to_stat_kind(&stat_kind),
-                        amount: statistics.get(concat!("value_", $index)).with_context(context!("value_{} for equip with id {equip_id}", $index))?
-                    })
-                }
-                None => None
-            }
-        }};
-    }
-
     let hull_disallowed = match template {
         Some(template) => {
             let forbidden: Vec<u32> = template.get("ship_type_forbidden").with_context(context!("ship_type_forbidden for equip with id {equip_id}"))?;
@@ -123,10 +181,57 @@ pub fn load_equip(lua: &Lua, equip_id: u32) -> LuaResult<Equip> {
         hull_disallowed,
         weapons,
         skills,
-        stat_bonuses: [stat_bonus!(1), stat_bonus!(2), stat_bonus!(3)].into_iter().flatten().collect()
+        stat_bonuses: load_equip_stat_bonuses(&statistics, equip_id)?,
+        tiers: Vec::new(),
     })
 }
 
+/// Loads the stat bonuses for every enhancement level of a piece of equipment from the Lua state.
+///
+/// `equip_id` should be the highest level. The result starts at +0.
+pub fn load_equip_tiers(lua: &Lua, equip_id: u32) -> LuaResult<Vec<Vec<EquipStatBonus>>> {
+    // guards against cycles in the data. no equipment gets close to this.
+    const MAX_TIERS: usize = 32;
+
+    let pg: LuaTable = lua.globals().get("pg").context("global pg")?;
+    let equip_data_statistics: LuaTable = pg.get("equip_data_statistics").context("global pg.equip_data_statistics")?;
+    let equip_data_template: LuaTable = pg.get("equip_data_template").context("global pg.equip_data_template")?;
+
+    let mut tiers = Vec::new();
+    let mut id = equip_id;
+    while id != 0 && tiers.len() < MAX_TIERS {
+        let statistics: Option<LuaTable> = equip_data_statistics.get(id).with_context(context!("equip statistics for id {id}"))?;
+        let template: Option<LuaTable> = equip_data_template.get(id).with_context(context!("equip template for id {id}"))?;
+        let (Some(statistics), Some(template)) = (statistics, template) else { break };
+
+        tiers.push(load_equip_stat_bonuses(&statistics, id)?);
+        id = template.get::<_, Option<u32>>("prev").with_context(context!("prev for equip with id {id}"))?.unwrap_or(0);
+    }
+
+    tiers.reverse();
+    Ok(tiers)
+}
+
+/// Loads the stat bonuses from equipment statistics.
+fn load_equip_stat_bonuses(statistics: &LuaTable, equip_id: u32) -> LuaResult<Vec<EquipStatBonus>> {
+    macro_rules! stat_bonus {
+        ($index:literal) => {{
+            match statistics.get(concat!("attribute_", $index)).with_context(context!("attribute_{} for equip with id {equip_id}", $index))? {
+                Some(stat_kind) => {
+                    let stat_kind: String = stat_kind;
+                    Some(EquipStatBonus {
+                        stat_kind: convert_al::to_stat_kind(&stat_kind),
+                        amount: statistics.get(concat!("value_", $index)).with_context(context!("value_{} for equip with id {equip_id}", $index))?
+                    })
+                }
+                None => None
+            }
+        }};
+    }
+
+    Ok([stat_bonus!(1), stat_bonus!(2), stat_bonus!(3)].into_iter().flatten().collect())
+}
+
 /// Loads equipment pieces from the Lua state.
 pub fn load_equips(lua: &Lua, equip_ids: Vec<u32>) -> LuaResult<Vec<Equip>> {
     equip_ids.into_iter().map(|id| load_equip(lua, id)).collect()
@@ -388,24 +493,28 @@ fn search_referenced_weapons(
     rwc: &mut ReferencedWeaponsContext,
     sc: SkillContext,
 ) -> LuaResult<()> {
-    let len = sc.skill.len()?;
+    if let Some(effect_list) = get_effect_list(sc.skill)? {
+        search_referenced_weapons_in_effect_entry(rwc, sc, effect_list)?;
+    }
+
+    Ok(())
+}
+
+/// Gets the effect list of the highest level of a skill/buff,
+/// falling back to the top-level one if there are no level entries.
+fn get_effect_list<'a>(skill: &LuaTable<'a>) -> LuaResult<Option<Vec<LuaTable<'a>>>> {
+    let len = skill.len()?;
     if let Ok(len) = usize::try_from(len) {
         if len != 0 {
-            let level_entry: LuaTable = sc.skill.get(len).with_context(context!("level entry {len} of skill/buff"))?;
+            let level_entry: LuaTable = skill.get(len).with_context(context!("level entry {len} of skill/buff"))?;
             let effect_list: Option<Vec<LuaTable>> = level_entry.get("effect_list").with_context(context!("effect_list of skill/buff level entry {len}"))?;
-            if let Some(effect_list) = effect_list {
-                search_referenced_weapons_in_effect_entry(rwc, sc, effect_list)?;
-                return Ok(());
+            if effect_list.is_some() {
+                return Ok(effect_list);
             }
         }
     }
 
-    let effect_list: Option<Vec<LuaTable>> = sc.skill.get("effect_list").context("effect_list of skill/buff")?;
-    if let Some(effect_list) = effect_list {
-        search_referenced_weapons_in_effect_entry(rwc, sc, effect_list)?;
-    }
-
-    Ok(())
+    skill.get("effect_list").context("effect_list of skill/buff")
 }
 
 fn search_referenced_weapons_in_effect_entry(
@@ -20,6 +20,8 @@ pub fn load_skin(set: &SkinSet) -> LuaResult<ShipSkin> {
         description: get!("desc"),
         words: load_words(set)?,
         words_extra: None, // loaded below
+        illustrator: get_credit(&set.template, "illustrator", set.skin_id)?,
+        voice_actor: get_credit(&set.words, "voice_actor", set.skin_id)?,
     };
 
     if let Some(extra) = &set.words_extra {
@@ -29,6 +31,17 @@ pub fn load_skin(set: &SkinSet) -> LuaResult<ShipSkin> {
     Ok(skin)
 }
 
+/// Gets a credit name from a table.
+///
+/// Some entries hold numeric IDs or empty strings instead, which are treated as missing.
+fn get_credit(table: &LuaTable, key: &str, skin_id: u32) -> LuaResult<Option<String>> {
+    let value: LuaValue = table.get(key).with_context(context!("skin credit {} for skin {}", key, skin_id))?;
+    Ok(match value {
+        LuaValue::String(s) => Some(s.to_str()?.to_owned()).filter(|s| !s.is_empty()),
+        _ => None,
+    })
+}
+
 fn load_words(set: &SkinSet) -> LuaResult<ShipSkinWords> {
     macro_rules! get {
         ($key:literal) => {{
@@ -0,0 +1,37 @@
+//! Measures how long the collector's phases take.
+
+use std::time::{Duration, Instant};
+
+/// Accumulates the time spent in each phase, so bottlenecks can be reported at the end.
+///
+/// Phases are reported in the order they were first recorded.
+/// Recording the same phase again, f.e. for multiple inputs, adds to its time.
+#[derive(Debug, Default)]
+pub struct PhaseTimings {
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl PhaseTimings {
+    /// Adds time spent in a phase.
+    pub fn add(&mut self, phase: &'static str, duration: Duration) {
+        match self.phases.iter_mut().find(|(p, _)| *p == phase) {
+            Some((_, total)) => *total += duration,
+            None => self.phases.push((phase, duration)),
+        }
+    }
+
+    /// Adds the time elapsed since `start` to a phase.
+    pub fn add_since(&mut self, phase: &'static str, start: Instant) {
+        self.add(phase, start.elapsed());
+    }
+
+    /// Prints the time spent in each phase.
+    pub fn report(&self) {
+        let width = self.phases.iter().map(|(p, _)| p.len()).max().unwrap_or_default();
+
+        println!("Phase timings:");
+        for (phase, duration) in &self.phases {
+            println!("  {phase:<width$}  {duration:.2?}");
+        }
+    }
+}
@@ -25,7 +25,7 @@ impl View {
     }
 
     /// Modifies the create-reply with a preresolved augment.
-    pub fn modify_with_augment(mut self, data: &HBotData, create: CreateReply, augment: &Augment) -> CreateReply {
+    pub fn modify_with_augment(mut self, data: &HAzurLane, user_data: &HUserData, create: CreateReply, augment: &Augment) -> CreateReply {
         self.mode = ButtonMessageMode::Edit;
         let description = format!("{}", crate::fmt::azur::AugmentStats::new(augment));
 
@@ -51,8 +51,8 @@ impl View {
                 let label = utils::text::truncate(label, 25);
                 CreateButton::new("=dummy-usability").label(label).disabled(true)
             },
-            AugmentUsability::UniqueShipId(ship_id) => if let Some(ship) = data.azur_lane().ship_by_id(*ship_id) {
-                let view = super::ship::View::new(ship.group_id).new_message();
+            AugmentUsability::UniqueShipId(ship_id) => if let Some(ship) = data.ship_by_id(*ship_id) {
+                let view = super::ship::View::with_user_data(ship.group_id, user_data).new_message();
                 let label = utils::text::truncate(format!("For: {}", ship.name), 25);
                 CreateButton::new(view.to_custom_id()).label(label)
             } else {
@@ -66,15 +66,16 @@ impl View {
     /// Creates the field for a skill summary.
     fn get_skill_field(&self, label: &'static str, skill: Option<&Skill>) -> Option<SimpleEmbedFieldCreate> {
         skill.map(|s| {
-            (label, format!("{} **{}**", s.category.emoji(), s.name), false)
+            (label, format!("{} **{}**", s.category.emoji(), utils::text::escape_markdown(&s.name)), false)
         })
     }
 }
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        let augment = ctx.data.azur_lane().augment_by_id(self.augment_id).ok_or(AugmentParseError)?;
-        Ok(self.modify_with_augment(ctx.data, ctx.create_reply(), augment))
+        let azur_lane = ctx.azur_lane();
+        let augment = azur_lane.augment_by_id(self.augment_id).ok_or(AugmentParseError)?;
+        Ok(self.modify_with_augment(&azur_lane, &ctx.user_data(), ctx.create_reply(), augment))
     }
 
     fn message_mode(&self) -> ButtonMessageMode {
@@ -8,13 +8,15 @@ use super::EquipParseError;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct View {
     pub equip_id: u32,
+    /// The selected enhancement level. [`None`] is the highest level.
+    pub tier: Option<u8>,
     mode: ButtonMessageMode,
 }
 
 impl View {
     /// Creates a new instance.
     pub fn new(equip_id: u32) -> Self {
-        Self { equip_id, mode: ButtonMessageMode::Edit }
+        Self { equip_id, tier: None, mode: ButtonMessageMode::Edit }
     }
 
     /// Makes the button send a new message.
@@ -26,10 +28,14 @@ impl View {
     /// Modifies the create-reply with a preresolved equipment.
     pub fn modify_with_equip(mut self, create: CreateReply, equip: &Equip) -> CreateReply {
         self.mode = ButtonMessageMode::Edit;
+        let stat_bonuses = self.tier
+            .and_then(|t| equip.tiers.get(usize::from(t)))
+            .unwrap_or(&equip.stat_bonuses);
+
         let description = format!(
             "**{}**\n{}",
             equip.kind.name(),
-            crate::fmt::azur::EquipStats::new(equip)
+            crate::fmt::azur::EquipStats::from_bonuses(stat_bonuses)
         );
 
         let embed = CreateEmbed::new()
@@ -42,13 +48,38 @@ impl View {
                 true,
             )))
             .fields(equip.skills.iter().map(|skill| (
-                format!("{} {}", skill.category.emoji(), skill.name),
-                utils::text::truncate(&skill.description, 1000),
+                format!("{} {}", skill.category.emoji(), utils::text::escape_markdown(&skill.name)),
+                utils::text::truncate(utils::text::escape_markdown(&skill.description), 1000),
                 false,
             )))
             .fields(self.get_disallowed_field(equip));
 
-        create.embed(embed).components(vec![])
+        let mut components = Vec::new();
+        if equip.tiers.len() > 1 {
+            // select menus hold at most 25 options, so drop the lowest tiers if needed
+            let count = equip.tiers.len();
+            let options = CreateSelectMenuKind::String {
+                options: (count.saturating_sub(25)..count)
+                    .map(|index| self.select_with_tier(index, count))
+                    .collect()
+            };
+
+            let select = CreateSelectMenu::new(self.to_custom_id(), options)
+                .placeholder("Enhancement level");
+
+            components.push(CreateActionRow::SelectMenu(select));
+        }
+
+        create.embed(embed).components(components)
+    }
+
+    /// Creates a select option that redirects to a different enhancement level.
+    fn select_with_tier(&mut self, index: usize, count: usize) -> CreateSelectMenuOption {
+        // The highest level uses None so it matches the initial view.
+        // Just as-cast the index to u8 since we'd have problems long before an overflow.
+        #[allow(clippy::cast_possible_truncation)]
+        let tier = (index + 1 < count).then_some(index as u8);
+        self.new_select_option(format!("+{index}"), utils::field_mut!(Self: tier), tier)
     }
 
     fn get_disallowed_field(&self, equip: &Equip) -> Option<SimpleEmbedFieldCreate> {
@@ -63,7 +94,8 @@ impl View {
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        let equip = ctx.data.azur_lane().equip_by_id(self.equip_id).ok_or(EquipParseError)?;
+        let azur_lane = ctx.azur_lane();
+        let equip = azur_lane.equip_by_id(self.equip_id).ok_or(EquipParseError)?;
         Ok(self.modify_with_equip(ctx.create_reply(), equip))
     }
 
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::Write;
 
 use azur_lane::ship::*;
@@ -39,11 +40,16 @@ impl View {
     }
 
     /// Modifies the create-reply with preresolved ship and skin data.
-    pub fn modify_with_ship(mut self, data: &HBotData, mut create: CreateReply, ship: &ShipData, skin: &ShipSkin) -> CreateReply {
+    pub fn modify_with_ship(mut self, data: &HAzurLane, mut create: CreateReply, ship: &ShipData, skin: &ShipSkin) -> CreateReply {
         let words = match (&self, skin) {
-            (View { extra: true, .. }, ShipSkin { words_extra: Some(words), .. } ) => words.as_ref(),
-            _ => { self.extra = false; &skin.words }
+            // EX main screen lines replace the base lines with the same index
+            (View { extra: true, .. }, ShipSkin { words_extra: Some(words), .. } ) => Cow::Owned(ShipSkinWords {
+                main_screen: merge_main_screen(&skin.words.main_screen, &words.main_screen),
+                .. words.as_ref().clone()
+            }),
+            _ => { self.extra = false; Cow::Borrowed(&skin.words) }
         };
+        let words = words.as_ref();
 
         let mut embed = CreateEmbed::new()
             .color(ship.rarity.color_rgb())
@@ -87,9 +93,25 @@ impl View {
             components.push(CreateActionRow::SelectMenu(select));
         }
 
-        if let Some(image_data) = data.azur_lane().get_chibi_image(&skin.image_key) {
-            create = create.attachment(CreateAttachment::bytes(image_data.as_ref(), format!("{}.webp", skin.image_key)));
-            embed = embed.thumbnail(format!("attachment://{}.webp", skin.image_key));
+        if let Some(image_data) = data.get_chibi_image(&skin.image_key) {
+            let filename = format!("{}.{}", skin.image_key, data.image_extension());
+            embed = embed.thumbnail(format!("attachment://{filename}"));
+            create = create.attachment(CreateAttachment::bytes(image_data.as_ref(), filename));
+        }
+
+        if self.part == ViewPart::Combat {
+            let encouraging = join_natural_and(data.ships_encouraging(ship.group_id).map(|s| s.name.as_str()));
+            if !encouraging.is_empty() {
+                embed = embed.field(
+                    "Ships with lines for this ship",
+                    utils::text::truncate(encouraging, 1000),
+                    false
+                );
+            }
+        }
+
+        if let Some(credits) = get_credits(skin) {
+            embed = embed.footer(CreateEmbedFooter::new(credits));
         }
 
         create.embed(embed).components(components)
@@ -114,19 +136,39 @@ impl View {
     }
 }
 
+/// Merges main screen lines, with `extra` lines replacing `base` lines with the same index.
+///
+/// The result is ordered by index.
+fn merge_main_screen(base: &[ShipMainScreenLine], extra: &[ShipMainScreenLine]) -> Vec<ShipMainScreenLine> {
+    let mut lines: Vec<ShipMainScreenLine> = base.iter()
+        .filter(|b| !extra.iter().any(|e| e.index() == b.index()))
+        .chain(extra)
+        .cloned()
+        .collect();
+
+    lines.sort_by_key(ShipMainScreenLine::index);
+    lines
+}
+
+/// Gets the credits text for a skin, if it has any.
+fn get_credits(skin: &ShipSkin) -> Option<String> {
+    match (&skin.illustrator, &skin.voice_actor) {
+        (Some(illustrator), Some(voice_actor)) => Some(format!("Illustrator: {illustrator} \u{2E31} VA: {voice_actor}")),
+        (Some(illustrator), None) => Some(format!("Illustrator: {illustrator}")),
+        (None, Some(voice_actor)) => Some(format!("VA: {voice_actor}")),
+        (None, None) => None,
+    }
+}
+
 impl ViewPart {
     /// Creates the embed description for the current state.
-    fn get_description(self, data: &HBotData, words: &ShipSkinWords) -> String {
+    fn get_description(self, data: &HAzurLane, words: &ShipSkinWords) -> String {
         let mut result = String::new();
 
-        fn norm(input: &str) -> String {
-            input.replace('*', "\\*").replace('`', "\\`").replace('_', "\\_")
-        }
-
         macro_rules! add {
             ($label:literal, $key:ident) => {{
                 if let Some(ref text) = words.$key {
-                    write!(result, concat!("- **", $label, ":** {}\n"), norm(text)).discard();
+                    write!(result, concat!("- **", $label, ":** {}\n"), utils::text::escape_markdown(text)).discard();
                 }
             }};
             (dyn $label:literal, $($extra:tt)*) => {{
@@ -144,7 +186,7 @@ impl ViewPart {
                 add!("Login", login);
 
                 for line in &words.main_screen {
-                    add!(dyn "Main Screen {}", line.index() + 1, norm(line.text()));
+                    add!(dyn "Main Screen {}", line.index() + 1, utils::text::escape_markdown(line.text()));
                 }
 
                 add!("Touch", touch);
@@ -177,7 +219,7 @@ impl ViewPart {
 
                 for opt in &words.couple_encourage {
                     let label = get_label_for_ship_couple_encourage(data, opt);
-                    add!(dyn "{}", label, norm(&opt.line));
+                    add!(dyn "{}", label, utils::text::escape_markdown(&opt.line));
                 }
             }
         }
@@ -253,18 +295,19 @@ impl ViewPart {
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        let ship = ctx.data.azur_lane().ship_by_id(self.ship_id).ok_or(ShipParseError)?;
+        let azur_lane = ctx.azur_lane();
+        let ship = azur_lane.ship_by_id(self.ship_id).ok_or(ShipParseError)?;
         let skin = ship.skins.get(usize::from(self.skin_index)).ok_or(ShipParseError)?;
-        Ok(self.modify_with_ship(ctx.data, ctx.create_reply(), ship, skin))
+        Ok(self.modify_with_ship(&azur_lane, ctx.create_reply(), ship, skin))
     }
 }
 
 /// Creates a label for a couple line.
-fn get_label_for_ship_couple_encourage(data: &HBotData, opt: &ShipCoupleEncourage) -> String {
+fn get_label_for_ship_couple_encourage(data: &HAzurLane, opt: &ShipCoupleEncourage) -> String {
     match &opt.condition {
         ShipCouple::ShipGroup(ship_ids) => {
             let ships = ship_ids.iter()
-                .filter_map(|&id| data.azur_lane().ship_by_id(id))
+                .filter_map(|&id| data.ship_by_id(id))
                 .map(|ship| ship.name.as_str());
 
             if ship_ids.len() == opt.amount.try_into().unwrap_or(0) {
@@ -331,3 +374,29 @@ fn join_natural<'a>(iter: impl Iterator<Item = &'a str>, join: &str, join_last:
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lines(lines: &[(usize, &str)]) -> Vec<ShipMainScreenLine> {
+        lines.iter().map(|&(index, text)| ShipMainScreenLine::new(index, text.to_owned())).collect()
+    }
+
+    #[test]
+    fn merge_main_screen_overlapping() {
+        let base = lines(&[(0, "Base 1"), (1, "Base 2"), (2, "Base 3")]);
+        let extra = lines(&[(3, "EX 4"), (1, "EX 2")]);
+
+        let merged = merge_main_screen(&base, &extra);
+        assert_eq!(merged, lines(&[(0, "Base 1"), (1, "EX 2"), (2, "Base 3"), (3, "EX 4")]));
+    }
+
+    #[test]
+    fn merge_main_screen_empty() {
+        let base = lines(&[(0, "Base 1"), (1, "Base 2")]);
+
+        assert_eq!(merge_main_screen(&base, &[]), base);
+        assert_eq!(merge_main_screen(&[], &base), base);
+    }
+}
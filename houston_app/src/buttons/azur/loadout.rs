@@ -0,0 +1,264 @@
+use std::fmt::Write;
+
+use azur_lane::equip::*;
+use azur_lane::ship::*;
+use utils::Discard;
+
+use crate::buttons::*;
+use super::ShipParseError;
+
+/// Builds an equipment loadout for a ship.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub ship_id: u32,
+    pub retrofit: Option<u8>,
+    /// The chosen equipment per slot, in slot order.
+    equips: Vec<Option<u32>>,
+    /// The slot currently being chosen for.
+    slot: Option<u8>,
+    page: u16,
+    mode: ButtonMessageMode,
+}
+
+/// The level the stats are shown at. Keep in sync with the stats field title.
+const LEVEL: u32 = 120;
+/// The affinity multiplier the stats are shown at. This matches 100 affinity.
+const AFFINITY: f64 = 1.06;
+/// The amount of equipment listed per page.
+const PAGE_SIZE: usize = 25;
+/// The maximum amount of slots with buttons. Ships don't have more than this.
+const MAX_SLOTS: usize = 5;
+
+/// The stats shown in the summary, in display order.
+const STATS: [StatKind; 11] = [
+    StatKind::HP, StatKind::FP, StatKind::TRP,
+    StatKind::AA, StatKind::AVI, StatKind::RLD,
+    StatKind::EVA, StatKind::ACC, StatKind::ASW,
+    StatKind::SPD, StatKind::LCK,
+];
+
+impl View {
+    /// Creates a new instance.
+    pub fn new(ship_id: u32, retrofit: Option<u8>) -> Self {
+        Self { ship_id, retrofit, equips: Vec::new(), slot: None, page: 0, mode: ButtonMessageMode::Edit }
+    }
+
+    /// Makes the button send a new message.
+    pub fn new_message(mut self) -> Self {
+        self.mode = ButtonMessageMode::New;
+        self
+    }
+
+    /// Modifies the create-reply with preresolved ship data.
+    pub fn modify_with_ship(mut self, data: &HAzurLane, create: CreateReply, ship: &ShipData, base_ship: Option<&ShipData>) -> CreateReply {
+        self.mode = ButtonMessageMode::Edit;
+        let base_ship = base_ship.unwrap_or(ship);
+
+        // resolve the chosen equipment, ignoring any that no longer fit
+        let equips: Vec<Option<&Equip>> = ship.equip_slots.iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                self.equips.get(index).copied().flatten()
+                    .and_then(|id| data.equip_by_id(id))
+                    .filter(|e| slot.accepts(e) && e.can_equip_on(ship.hull_type))
+            })
+            .collect();
+
+        let embed = CreateEmbed::new()
+            .author(super::get_ship_wiki_url(base_ship))
+            .title("Loadout")
+            .description(self.get_slots_description(ship, &equips))
+            .fields(self.get_stats_field(ship, &equips))
+            .color(ship.rarity.color_rgb());
+
+        let mut rows = Vec::new();
+        self.add_slot_row(ship, &mut rows);
+        self.add_equip_rows(data, ship, &equips, &mut rows);
+        rows.push(CreateActionRow::Buttons(vec![self.new_share_button()]));
+
+        create.embed(embed).components(rows)
+    }
+
+    /// Creates the description listing every slot and its equipment.
+    fn get_slots_description(&self, ship: &ShipData, equips: &[Option<&Equip>]) -> String {
+        if ship.equip_slots.is_empty() {
+            return "This ship has no equipment slots.".to_owned();
+        }
+
+        let mut text = String::new();
+        for (index, (slot, equip)) in ship.equip_slots.iter().zip(equips).enumerate() {
+            if !text.is_empty() { text.push('\n'); }
+
+            write!(text, "**{}.** ", index + 1).discard();
+
+            // auxiliary slots don't have mounts
+            if let Some(mount) = &slot.mount {
+                write!(text, "`{: >3.0}%x{}` ", mount.efficiency * 100f64, mount.mounts).discard();
+            }
+
+            crate::fmt::write_join(&mut text, slot.allowed.iter().map(|k| k.slot_name()), "/").discard();
+
+            match equip {
+                Some(equip) => write!(text, "\n> **{}** [{}]", utils::text::escape_markdown(&equip.name), equip.rarity.name()).discard(),
+                None => text.push_str("\n> *Empty*"),
+            }
+
+            // raw damage per second of the equipment's weapons in this slot
+            if let (Some(mount), Some(equip)) = (&slot.mount, equip) {
+                let dps: f64 = equip.weapons.iter()
+                    .filter_map(|w| w.dps_with_mount(mount))
+                    .sum();
+
+                if dps > 0.0 {
+                    write!(text, "\n> -# **DPS:** ~{dps:.1}").discard();
+                }
+            }
+        }
+
+        text
+    }
+
+    /// Creates the embed field with the ship stats combined with the equipment bonuses.
+    fn get_stats_field(&self, ship: &ShipData, equips: &[Option<&Equip>]) -> [SimpleEmbedFieldCreate; 1] {
+        #[allow(clippy::cast_sign_loss)]
+        #[allow(clippy::cast_possible_truncation)]
+        fn f(n: f64) -> u32 { n.floor() as u32 }
+
+        let mut text = String::new();
+        for (index, kind) in STATS.into_iter().enumerate() {
+            if index != 0 { text.push_str(if index % 3 == 0 { "\n" } else { " \u{2E31} " }); }
+
+            let base = ship.stats.calc_stat(kind, LEVEL, AFFINITY);
+            let bonus: f64 = equips.iter()
+                .flatten()
+                .flat_map(|e| &e.stat_bonuses)
+                .filter(|b| b.stat_kind == kind)
+                .map(|b| b.amount)
+                .sum();
+
+            let name = kind.name();
+            write!(text, "**`{}:`**`{: >len$}`", name, f(base + bonus), len = 8 - name.len()).discard();
+        }
+
+        [("Stats (Lv.120, \u{2764}100)", text, false)]
+    }
+
+    /// Adds the row of buttons to choose a slot.
+    fn add_slot_row(&mut self, ship: &ShipData, rows: &mut Vec<CreateActionRow>) {
+        let slot_count = ship.equip_slots.len().min(MAX_SLOTS);
+        if slot_count == 0 {
+            return;
+        }
+
+        // switching slots should start at the first page
+        let page = std::mem::take(&mut self.page);
+        let buttons = (0..slot_count)
+            .filter_map(|index| {
+                let index = u8::try_from(index).ok()?;
+                let button = self.button_with_slot(Some(index))
+                    .label((index + 1).to_string())
+                    .style(ButtonStyle::Secondary);
+                Some(button)
+            })
+            .collect();
+
+        self.page = page;
+        rows.push(CreateActionRow::Buttons(buttons));
+    }
+
+    /// Adds the rows to choose equipment for the selected slot.
+    fn add_equip_rows(&self, data: &HAzurLane, ship: &ShipData, equips: &[Option<&Equip>], rows: &mut Vec<CreateActionRow>) {
+        let Some(slot_index) = self.slot else { return };
+        let Some(slot) = ship.equip_slots.get(usize::from(slot_index)) else { return };
+        let current = equips.get(usize::from(slot_index)).copied().flatten();
+
+        let mut compatible = data.equips().iter()
+            .filter(|e| slot.accepts(e) && e.can_equip_on(ship.hull_type))
+            .skip(PAGE_SIZE * usize::from(self.page));
+
+        let options: Vec<_> = compatible.by_ref()
+            .take(PAGE_SIZE)
+            .map(|equip| {
+                let custom_id = self.to_custom_id_with_equip(slot_index, Some(equip.equip_id));
+                CreateSelectMenuOption::new(&equip.name, custom_id)
+                    .description(format!("{} {}", equip.rarity.name(), equip.faction.name()))
+                    .default_selection(current.is_some_and(|c| c.equip_id == equip.equip_id))
+            })
+            .collect();
+
+        let has_next = compatible.next().is_some();
+
+        if !options.is_empty() {
+            let options = CreateSelectMenuKind::String { options };
+            let select = CreateSelectMenu::new(self.to_custom_id(), options)
+                .placeholder(format!("Equip slot {}...", slot_index + 1));
+
+            rows.push(CreateActionRow::SelectMenu(select));
+        }
+
+        let mut buttons = Vec::new();
+        if self.page > 0 || has_next {
+            buttons.push(if self.page > 0 {
+                CreateButton::new(self.to_custom_id_with_page(self.page - 1))
+            } else {
+                CreateButton::new("#no-back").disabled(true)
+            }.emoji('◀'));
+
+            buttons.push(if has_next {
+                CreateButton::new(self.to_custom_id_with_page(self.page + 1))
+            } else {
+                CreateButton::new("#no-forward").disabled(true)
+            }.emoji('▶'));
+        }
+
+        buttons.push(
+            CreateButton::new(self.to_custom_id_with_equip(slot_index, None))
+                .label("Clear")
+                .style(ButtonStyle::Danger)
+                .disabled(current.is_none())
+        );
+
+        rows.push(CreateActionRow::Buttons(buttons));
+    }
+
+    /// Gets a button that redirects to choosing a different slot.
+    fn button_with_slot(&mut self, slot: Option<u8>) -> CreateButton {
+        self.new_button(utils::field_mut!(Self: slot), slot, |u| u.map(u16::from).unwrap_or(u16::MAX))
+    }
+
+    /// Creates a custom ID that sets the equipment of a slot and returns to the overview.
+    fn to_custom_id_with_equip(&self, slot: u8, equip_id: Option<u32>) -> String {
+        let mut view = self.clone();
+        let slot = usize::from(slot);
+        if view.equips.len() <= slot {
+            view.equips.resize(slot + 1, None);
+        }
+
+        view.equips[slot] = equip_id;
+        view.slot = None;
+        view.page = 0;
+        view.to_custom_id()
+    }
+
+    /// Creates a custom ID that shows a different page of equipment.
+    fn to_custom_id_with_page(&self, page: u16) -> String {
+        let mut view = self.clone();
+        view.page = page;
+        view.to_custom_id()
+    }
+}
+
+impl ButtonMessage for View {
+    fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
+        let data = ctx.azur_lane();
+        let ship = data.ship_by_id(self.ship_id).ok_or(ShipParseError)?;
+        Ok(match self.retrofit.and_then(|index| ship.retrofits.get(usize::from(index))) {
+            None => self.modify_with_ship(&data, ctx.create_reply(), ship, None),
+            Some(retrofit) => self.modify_with_ship(&data, ctx.create_reply(), retrofit, Some(ship))
+        })
+    }
+
+    fn message_mode(&self) -> ButtonMessageMode {
+        self.mode
+    }
+}
@@ -3,16 +3,61 @@ use crate::buttons::*;
 pub mod augment;
 pub mod equip;
 pub mod lines;
+pub mod loadout;
 pub mod search_augment;
 pub mod search_equip;
 pub mod search_ship;
+pub mod search_weapon;
 pub mod shadow_equip;
 pub mod ship;
 pub mod skill;
+pub mod weapon;
 
 utils::define_simple_error!(ShipParseError: "unknown ship");
 utils::define_simple_error!(EquipParseError: "unknown equipment");
 utils::define_simple_error!(AugmentParseError: "unknown augment");
+utils::define_simple_error!(WeaponParseError: "unknown weapon");
+
+/// Collects the active parts of a search filter to describe them in prose.
+#[derive(Debug, Default)]
+struct FilterSummary {
+    parts: Vec<String>,
+}
+
+impl FilterSummary {
+    /// Adds a part if the filter value is set.
+    fn value(mut self, label: &str, value: Option<impl std::fmt::Display>) -> Self {
+        if let Some(value) = value {
+            self.parts.push(format!("{label} **{}**", utils::text::escape_markdown(&value.to_string())));
+        }
+        self
+    }
+
+    /// Adds a part if the filter flag is set, describing either state.
+    fn flag(mut self, if_true: &str, if_false: &str, flag: Option<bool>) -> Self {
+        if let Some(flag) = flag {
+            self.parts.push(if flag { if_true } else { if_false }.to_owned());
+        }
+        self
+    }
+
+    /// Formats the message to show when a search has no results.
+    fn no_results_message(&self) -> String {
+        match self.parts.as_slice() {
+            [] => "No results.".to_owned(),
+            [part] => format!("No results for {part}. Try loosening the filter."),
+            [first, last] => format!("No results for {first} and {last}. Try loosening the filter."),
+            [head @ .., last] => format!("No results for {}, and {last}. Try loosening the filter.", head.join(", ")),
+        }
+    }
+
+    /// Creates the embed to show when a search has no results.
+    fn no_results_embed(&self) -> CreateEmbed {
+        CreateEmbed::new()
+            .color(ERROR_EMBED_COLOR)
+            .description(self.no_results_message())
+    }
+}
 
 /// Gets the URL to a ship on the wiki.
 fn get_ship_wiki_url(base_ship: &azur_lane::ship::ShipData) -> CreateEmbedAuthor {
@@ -21,3 +66,50 @@ fn get_ship_wiki_url(base_ship: &azur_lane::ship::ShipData) -> CreateEmbedAuthor
 
     CreateEmbedAuthor::new(&base_ship.name).url(wiki_url)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filter_summary_empty() {
+        let summary = FilterSummary::default()
+            .value("name", None::<&str>)
+            .flag("with a retrofit", "without a retrofit", None);
+
+        assert_eq!(summary.no_results_message(), "No results.");
+    }
+
+    #[test]
+    fn filter_summary_single() {
+        let summary = FilterSummary::default()
+            .value("faction", Some("Eagle Union"));
+
+        assert_eq!(summary.no_results_message(), "No results for faction **Eagle Union**. Try loosening the filter.");
+    }
+
+    #[test]
+    fn filter_summary_two() {
+        let summary = FilterSummary::default()
+            .value("rarity", Some("SR"))
+            .flag("with a retrofit", "without a retrofit", Some(true));
+
+        assert_eq!(
+            summary.no_results_message(),
+            "No results for rarity **SR** and with a retrofit. Try loosening the filter."
+        );
+    }
+
+    #[test]
+    fn filter_summary_many() {
+        let summary = FilterSummary::default()
+            .value("name", Some("*star*"))
+            .value("rarity", Some("SR"))
+            .flag("with a retrofit", "without a retrofit", Some(false));
+
+        assert_eq!(
+            summary.no_results_message(),
+            "No results for name **\\*star\\***, rarity **SR**, and without a retrofit. Try loosening the filter."
+        );
+    }
+}
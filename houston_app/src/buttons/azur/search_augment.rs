@@ -9,6 +9,7 @@ use crate::buttons::*;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct View {
     page: u16,
+    generation: u16,
     filter: Filter
 }
 
@@ -24,10 +25,10 @@ const PAGE_SIZE: usize = 15;
 
 impl View {
     pub fn new(filter: Filter) -> Self {
-        View { page: 0, filter }
+        View { page: 0, generation: 0, filter }
     }
 
-    pub fn modify_with_iter<'a>(mut self, create: CreateReply, iter: impl Iterator<Item = &'a Augment>) -> CreateReply {
+    pub fn modify_with_iter<'a>(mut self, data: &HAzurLane, create: CreateReply, iter: impl Iterator<Item = &'a Augment>) -> CreateReply {
         let mut desc = String::new();
         let mut options = Vec::new();
         let mut has_next = false;
@@ -49,11 +50,7 @@ impl View {
         }
 
         if options.is_empty() {
-            let embed = CreateEmbed::new()
-                .color(ERROR_EMBED_COLOR)
-                .description("No results for that filter.");
-
-            return create.embed(embed);
+            return create.embed(self.filter.summary(data).no_results_embed());
         }
 
         let embed = CreateEmbed::new()
@@ -67,8 +64,9 @@ impl View {
             CreateActionRow::SelectMenu(CreateSelectMenu::new(self.to_custom_id(), options).placeholder("View augment module..."))
         ];
 
+        let mut nav = Vec::new();
         if self.page > 0 || has_next {
-            rows.insert(0, CreateActionRow::Buttons(vec![
+            nav.extend([
                 if self.page > 0 {
                     self.new_button(utils::field_mut!(Self: page), self.page - 1, |_| 1)
                 } else {
@@ -80,33 +78,59 @@ impl View {
                 } else {
                     CreateButton::new("#no-forward").disabled(true)
                 }.emoji('▶')
-            ]));
+            ]);
         }
 
+        nav.push(self.new_share_button());
+        rows.insert(0, CreateActionRow::Buttons(nav));
+
         create.embed(embed).components(rows)
     }
 
-    pub fn modify(self, data: &HBotData, create: CreateReply) -> CreateReply {
+    pub fn modify(mut self, data: &HBotData, user_data: &HUserData, create: CreateReply) -> CreateReply {
+        self.generation = data.data_generation();
+        let azur_lane = data.azur_lane_in(user_data.language);
         let filtered = self.filter
-            .iterate(data.azur_lane())
+            .iterate(&azur_lane)
             .skip(PAGE_SIZE * usize::from(self.page));
 
-        self.modify_with_iter(create, filtered)
+        self.modify_with_iter(&azur_lane, create, filtered)
     }
 }
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        Ok(self.modify(ctx.data, ctx.create_reply()))
+        Ok(self.modify(ctx.data, &ctx.user_data(), ctx.create_reply()))
+    }
+
+    fn data_generation(&self) -> Option<u16> {
+        Some(self.generation)
     }
 }
 
 impl Filter {
+    /// Summarizes the active parts of the filter.
+    fn summary(&self, data: &HAzurLane) -> super::FilterSummary {
+        let unique_ship = self.unique_ship_id.map(|id| match data.ship_by_id(id) {
+            Some(ship) => ship.name.clone(),
+            None => format!("#{id}"),
+        });
+
+        super::FilterSummary::default()
+            .value("name", self.name.as_deref())
+            .value("hull type", self.hull_type.map(HullType::name))
+            .value("rarity", self.rarity.map(AugmentRarity::name))
+            .value("ship", unique_ship)
+    }
+
     fn iterate<'a>(&self, data: &'a HAzurLane) -> Box<dyn Iterator<Item = &'a Augment> + 'a> {
         let predicate = self.predicate(data);
-        match &self.name {
-            Some(name) => Box::new(data.augments_by_prefix(name.as_str()).filter(predicate)),
-            None => Box::new(data.augments().iter().filter(predicate))
+        match (&self.name, self.unique_ship_id, self.hull_type) {
+            (Some(name), _, _) => Box::new(data.augments_by_prefix(name.as_str()).filter(predicate)),
+            // narrow down the candidates with the indices first
+            (None, Some(ship_id), _) => Box::new(data.augments_by_ship_id(ship_id).filter(predicate)),
+            (None, None, Some(hull_type)) => Box::new(data.augments_by_hull_type(hull_type).filter(predicate)),
+            (None, None, None) => Box::new(data.augments().iter().filter(predicate)),
         }
     }
 
@@ -139,3 +163,65 @@ impl Filter {
         next_hull_type(self, data, |_| true)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use azur_lane::DefinitionData;
+
+    use super::*;
+
+    fn augment(augment_id: u32, rarity: AugmentRarity, usability: AugmentUsability) -> Augment {
+        Augment {
+            augment_id,
+            name: format!("Augment {augment_id}"),
+            rarity,
+            stat_bonuses: Vec::new(),
+            usability,
+            effect: None,
+            skill_upgrade: None,
+        }
+    }
+
+    fn data() -> HAzurLane {
+        use AugmentUsability as U;
+        use HullType as H;
+
+        let augments = vec![
+            augment(1, AugmentRarity::SR, U::HullTypes(vec![H::Destroyer, H::LightCruiser])),
+            augment(2, AugmentRarity::R, U::HullTypes(vec![H::Destroyer])),
+            augment(3, AugmentRarity::SR, U::HullTypes(vec![H::Battleship])),
+            augment(4, AugmentRarity::SR, U::UniqueShipId(10001)),
+            augment(5, AugmentRarity::E, U::UniqueShipId(10001)),
+            augment(6, AugmentRarity::SR, U::UniqueShipId(10002)),
+        ];
+
+        HAzurLane::from_definitions(PathBuf::new(), DefinitionData { augments, ..DefinitionData::default() })
+    }
+
+    fn filter(hull_type: Option<HullType>, rarity: Option<AugmentRarity>, unique_ship_id: Option<u32>) -> Filter {
+        Filter { name: None, hull_type, rarity, unique_ship_id }
+    }
+
+    #[test]
+    fn indexed_matches_scan() {
+        let data = data();
+        let filters = [
+            filter(None, None, None),
+            filter(Some(HullType::Destroyer), None, None),
+            filter(Some(HullType::Destroyer), Some(AugmentRarity::SR), None),
+            filter(Some(HullType::Battleship), Some(AugmentRarity::R), None),
+            filter(Some(HullType::Submarine), None, None),
+            filter(None, None, Some(10001)),
+            filter(None, Some(AugmentRarity::E), Some(10001)),
+            filter(Some(HullType::Destroyer), None, Some(10001)),
+        ];
+
+        for filter in filters {
+            let indexed: Vec<u32> = filter.iterate(&data).map(|a| a.augment_id).collect();
+            let scanned: Vec<u32> = data.augments().iter().filter(filter.predicate(&data)).map(|a| a.augment_id).collect();
+            assert_eq!(indexed, scanned, "{filter:?}");
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt::Write;
 
 use azur_lane::Faction;
 use azur_lane::equip::*;
+use azur_lane::ship::StatKind;
 use utils::Discard;
 
 use crate::buttons::*;
@@ -9,6 +10,7 @@ use crate::buttons::*;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct View {
     page: u16,
+    generation: u16,
     filter: Filter
 }
 
@@ -18,13 +20,14 @@ pub struct Filter {
     pub faction: Option<Faction>,
     pub kind: Option<EquipKind>,
     pub rarity: Option<EquipRarity>,
+    pub stat: Option<StatKind>,
 }
 
 const PAGE_SIZE: usize = 15;
 
 impl View {
     pub fn new(filter: Filter) -> Self {
-        View { page: 0, filter }
+        View { page: 0, generation: 0, filter }
     }
 
     pub fn modify_with_iter<'a>(mut self, create: CreateReply, iter: impl Iterator<Item = &'a Equip>) -> CreateReply {
@@ -49,11 +52,7 @@ impl View {
         }
 
         if options.is_empty() {
-            let embed = CreateEmbed::new()
-                .color(ERROR_EMBED_COLOR)
-                .description("No results for that filter.");
-
-            return create.embed(embed);
+            return create.embed(self.filter.summary().no_results_embed());
         }
 
         let embed = CreateEmbed::new()
@@ -67,8 +66,9 @@ impl View {
             CreateActionRow::SelectMenu(CreateSelectMenu::new(self.to_custom_id(), options).placeholder("View equipment..."))
         ];
 
+        let mut nav = Vec::new();
         if self.page > 0 || has_next {
-            rows.insert(0, CreateActionRow::Buttons(vec![
+            nav.extend([
                 if self.page > 0 {
                     self.new_button(utils::field_mut!(Self: page), self.page - 1, |_| 1)
                 } else {
@@ -80,15 +80,20 @@ impl View {
                 } else {
                     CreateButton::new("#no-forward").disabled(true)
                 }.emoji('▶')
-            ]));
+            ]);
         }
 
+        nav.push(self.new_share_button());
+        rows.insert(0, CreateActionRow::Buttons(nav));
+
         create.embed(embed).components(rows)
     }
 
-    pub fn modify(self, data: &HBotData, create: CreateReply) -> CreateReply {
+    pub fn modify(mut self, data: &HBotData, user_data: &HUserData, create: CreateReply) -> CreateReply {
+        self.generation = data.data_generation();
+        let azur_lane = data.azur_lane_in(user_data.language);
         let filtered = self.filter
-            .iterate(data.azur_lane())
+            .iterate(&azur_lane)
             .skip(PAGE_SIZE * usize::from(self.page));
 
         self.modify_with_iter(create, filtered)
@@ -97,11 +102,25 @@ impl View {
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        Ok(self.modify(ctx.data, ctx.create_reply()))
+        Ok(self.modify(ctx.data, &ctx.user_data(), ctx.create_reply()))
+    }
+
+    fn data_generation(&self) -> Option<u16> {
+        Some(self.generation)
     }
 }
 
 impl Filter {
+    /// Summarizes the active parts of the filter.
+    fn summary(&self) -> super::FilterSummary {
+        super::FilterSummary::default()
+            .value("name", self.name.as_deref())
+            .value("faction", self.faction.map(Faction::name))
+            .value("kind", self.kind.map(EquipKind::name))
+            .value("rarity", self.rarity.map(EquipRarity::name))
+            .value("stat", self.stat.map(StatKind::name))
+    }
+
     fn iterate<'a>(&self, data: &'a HAzurLane) -> Box<dyn Iterator<Item = &'a Equip> + 'a> {
         let predicate = self.predicate(data);
         match &self.name {
@@ -124,7 +143,14 @@ impl Filter {
 
         def_and_filter!(next_faction: faction => next_hull_type);
         def_and_filter!(next_hull_type: kind => next_rarity);
-        def_and_filter!(next_rarity: rarity => finish);
+        def_and_filter!(next_rarity: rarity => next_stat);
+
+        fn next_stat<'a>(f: &Filter, data: &'a HAzurLane, mut base: impl FnMut(&&Equip) -> bool + 'a) -> Box<dyn FnMut(&&Equip) -> bool + 'a> {
+            match f.stat {
+                Some(filter) => finish(f, data, move |s| base(s) && s.stat_bonuses.iter().any(|b| b.stat_kind == filter && b.amount > 0.0)),
+                None => finish(f, data, base)
+            }
+        }
 
         fn finish<'a>(_f: &Filter, _data: &'a HAzurLane, base: impl FnMut(&&Equip) -> bool + 'a) -> Box<dyn FnMut(&&Equip) -> bool + 'a> {
             Box::new(base)
@@ -133,3 +159,33 @@ impl Filter {
         next_faction(self, data, |_| true)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use azur_lane::DefinitionData;
+    use azur_lane::test_support;
+
+    use super::*;
+
+    fn equip(equip_id: u32, stat_bonuses: Vec<EquipStatBonus>) -> Equip {
+        Equip { equip_id, stat_bonuses, ..test_support::equip() }
+    }
+
+    #[test]
+    fn filter_by_stat() {
+        let equips = vec![
+            equip(1, vec![EquipStatBonus { stat_kind: StatKind::AVI, amount: 45.0 }]),
+            equip(2, vec![EquipStatBonus { stat_kind: StatKind::HP, amount: 500.0 }]),
+            equip(3, vec![EquipStatBonus { stat_kind: StatKind::HP, amount: 100.0 }, EquipStatBonus { stat_kind: StatKind::AVI, amount: 10.0 }]),
+            equip(4, Vec::new()),
+        ];
+
+        let data = HAzurLane::from_definitions(PathBuf::new(), DefinitionData { equips, ..DefinitionData::default() });
+        let filter = Filter { name: None, faction: None, kind: None, rarity: None, stat: Some(StatKind::AVI) };
+
+        let ids: Vec<u32> = filter.iterate(&data).map(|e| e.equip_id).collect();
+        assert_eq!(ids, [1, 3]);
+    }
+}
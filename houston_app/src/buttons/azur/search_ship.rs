@@ -9,6 +9,7 @@ use crate::buttons::*;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct View {
     page: u16,
+    generation: u16,
     filter: Filter
 }
 
@@ -18,17 +19,19 @@ pub struct Filter {
     pub faction: Option<Faction>,
     pub hull_type: Option<HullType>,
     pub rarity: Option<ShipRarity>,
-    pub has_augment: Option<bool>
+    pub has_augment: Option<bool>,
+    pub has_retrofit: Option<bool>,
+    pub enhance_kind: Option<EnhanceKind>,
 }
 
 const PAGE_SIZE: usize = 15;
 
 impl View {
     pub fn new(filter: Filter) -> View {
-        View { page: 0, filter }
+        View { page: 0, generation: 0, filter }
     }
 
-    pub fn modify_with_iter<'a>(mut self, data: &HBotData, create: CreateReply, iter: impl Iterator<Item = &'a ShipData>) -> CreateReply {
+    pub fn modify_with_iter<'a>(mut self, data: &HBotData, user_data: &HUserData, create: CreateReply, iter: impl Iterator<Item = &'a ShipData>) -> CreateReply {
         let mut desc = String::new();
         let mut options = Vec::new();
         let mut has_next = false;
@@ -39,24 +42,33 @@ impl View {
                 break
             }
 
-            let emoji = data.app_emojis().hull(ship.hull_type);
+            // without app emojis, fall back to the designation rather than a placeholder emoji
+            let emojis = data.app_emojis();
+            let emoji = emojis.is_loaded().then(|| emojis.hull(ship.hull_type));
+
+            desc.push_str("- ");
+            match emoji {
+                Some(emoji) => write!(desc, "{emoji} "),
+                None => write!(desc, "`{}` ", ship.hull_type.designation()),
+            }.discard();
 
             writeln!(
                 desc,
-                "- {emoji} **{}** [{} {} {}]",
+                "**{}** [{} {} {}]",
                 ship.name, ship.rarity.name(), ship.faction.prefix().unwrap_or("Col."), ship.hull_type.designation(),
             ).discard();
 
-            let view_ship = super::ship::View::new(ship.group_id).new_message();
-            options.push(CreateSelectMenuOption::new(&ship.name, view_ship.to_custom_id()).emoji(emoji.clone()));
+            let view_ship = super::ship::View::with_user_data(ship.group_id, user_data).new_message();
+            let mut option = CreateSelectMenuOption::new(&ship.name, view_ship.to_custom_id());
+            if let Some(emoji) = emoji {
+                option = option.emoji(emoji.clone());
+            }
+
+            options.push(option);
         }
 
         if options.is_empty() {
-            let embed = CreateEmbed::new()
-                .color(ERROR_EMBED_COLOR)
-                .description("No results for that filter.");
-
-            return create.embed(embed);
+            return create.embed(self.filter.summary().no_results_embed());
         }
 
         let embed = CreateEmbed::new()
@@ -70,8 +82,9 @@ impl View {
             CreateActionRow::SelectMenu(CreateSelectMenu::new(self.to_custom_id(), options).placeholder("View ship..."))
         ];
 
+        let mut nav = Vec::new();
         if self.page > 0 || has_next {
-            rows.insert(0, CreateActionRow::Buttons(vec![
+            nav.extend([
                 if self.page > 0 {
                     self.new_button(utils::field_mut!(Self: page), self.page - 1, |_| 1)
                 } else {
@@ -83,28 +96,55 @@ impl View {
                 } else {
                     CreateButton::new("#no-forward").disabled(true)
                 }.emoji('▶')
-            ]));
+            ]);
         }
 
+        nav.push(self.new_share_button());
+        rows.insert(0, CreateActionRow::Buttons(nav));
+
         create.embed(embed).components(rows)
     }
 
-    pub fn modify(self, data: &HBotData, create: CreateReply) -> CreateReply {
+    pub fn modify(mut self, data: &HBotData, user_data: &HUserData, create: CreateReply) -> CreateReply {
+        self.generation = data.data_generation();
+        let azur_lane = data.azur_lane_in(user_data.language);
         let filtered = self.filter
-            .iterate(data.azur_lane())
+            .iterate(&azur_lane)
             .skip(PAGE_SIZE * usize::from(self.page));
 
-        self.modify_with_iter(data, create, filtered)
+        self.modify_with_iter(data, user_data, create, filtered)
     }
 }
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        Ok(self.modify(ctx.data, ctx.create_reply()))
+        Ok(self.modify(ctx.data, &ctx.user_data(), ctx.create_reply()))
+    }
+
+    fn data_generation(&self) -> Option<u16> {
+        Some(self.generation)
     }
 }
 
 impl Filter {
+    /// Summarizes the active parts of the filter.
+    fn summary(&self) -> super::FilterSummary {
+        let enhance_kind = self.enhance_kind.map(|e| match e {
+            EnhanceKind::Normal => "Normal",
+            EnhanceKind::Research => "Research",
+            EnhanceKind::META => "META",
+        });
+
+        super::FilterSummary::default()
+            .value("name", self.name.as_deref())
+            .value("faction", self.faction.map(Faction::name))
+            .value("hull type", self.hull_type.map(HullType::name))
+            .value("rarity", self.rarity.map(ShipRarity::name))
+            .value("enhancement", enhance_kind)
+            .flag("with a unique augment", "without a unique augment", self.has_augment)
+            .flag("with a retrofit", "without a retrofit", self.has_retrofit)
+    }
+
     fn iterate<'a>(&self, data: &'a HAzurLane) -> Box<dyn Iterator<Item = &'a ShipData> + 'a> {
         let predicate = self.predicate(data);
         match &self.name {
@@ -127,7 +167,15 @@ impl Filter {
 
         def_and_filter!(next_faction: faction => next_hull_type);
         def_and_filter!(next_hull_type: hull_type => next_rarity);
-        def_and_filter!(next_rarity: rarity => finish);
+        def_and_filter!(next_rarity: rarity => next_enhance_kind);
+        def_and_filter!(next_enhance_kind: enhance_kind => next_has_retrofit);
+
+        fn next_has_retrofit<'a>(f: &Filter, data: &'a HAzurLane, mut base: impl FnMut(&&ShipData) -> bool + 'a) -> Box<dyn FnMut(&&ShipData) -> bool + 'a> {
+            match f.has_retrofit {
+                Some(filter) => finish(f, data, move |s| base(s) && !s.retrofits.is_empty() == filter),
+                None => finish(f, data, base)
+            }
+        }
 
         fn finish<'a>(f: &Filter, data: &'a HAzurLane, mut base: impl FnMut(&&ShipData) -> bool + 'a) -> Box<dyn FnMut(&&ShipData) -> bool + 'a> {
             match f.has_augment {
@@ -141,3 +189,63 @@ impl Filter {
         next_faction(self, data, |_| true)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use azur_lane::DefinitionData;
+    use azur_lane::test_support;
+
+    use super::*;
+
+    fn ship(group_id: u32, enhance_kind: EnhanceKind, has_retrofit: bool) -> ShipData {
+        let ship = ShipData { group_id, enhance_kind, ..test_support::ship() };
+        ShipData {
+            retrofits: if has_retrofit { vec![ship.clone()] } else { Vec::new() },
+            .. ship
+        }
+    }
+
+    fn data() -> HAzurLane {
+        let ships = vec![
+            ship(1, EnhanceKind::Normal, false),
+            ship(2, EnhanceKind::Normal, true),
+            ship(3, EnhanceKind::Research, false),
+            ship(4, EnhanceKind::META, false),
+        ];
+
+        HAzurLane::from_definitions(PathBuf::new(), DefinitionData { ships, ..DefinitionData::default() })
+    }
+
+    fn filter(has_retrofit: Option<bool>, enhance_kind: Option<EnhanceKind>) -> Filter {
+        Filter { name: None, faction: None, hull_type: None, rarity: None, has_augment: None, has_retrofit, enhance_kind }
+    }
+
+    fn ids(filter: &Filter) -> Vec<u32> {
+        let data = data();
+        filter.iterate(&data).map(|s| s.group_id).collect()
+    }
+
+    #[test]
+    fn filter_by_retrofit() {
+        assert_eq!(ids(&filter(None, None)), [1, 2, 3, 4]);
+        assert_eq!(ids(&filter(Some(true), None)), [2]);
+        assert_eq!(ids(&filter(Some(false), None)), [1, 3, 4]);
+    }
+
+    #[test]
+    fn filter_by_enhance_kind() {
+        assert_eq!(ids(&filter(None, Some(EnhanceKind::Normal))), [1, 2]);
+        assert_eq!(ids(&filter(None, Some(EnhanceKind::Research))), [3]);
+        assert_eq!(ids(&filter(None, Some(EnhanceKind::META))), [4]);
+    }
+
+    #[test]
+    fn filter_by_retrofit_and_enhance_kind() {
+        assert_eq!(ids(&filter(Some(true), Some(EnhanceKind::Normal))), [2]);
+        assert_eq!(ids(&filter(Some(false), Some(EnhanceKind::Normal))), [1]);
+        assert_eq!(ids(&filter(Some(true), Some(EnhanceKind::Research))), Vec::<u32>::new());
+        assert_eq!(ids(&filter(Some(false), Some(EnhanceKind::META))), [4]);
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Write;
+
+use azur_lane::equip::*;
+use utils::Discard;
+
+use crate::buttons::*;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    page: u16,
+    generation: u16,
+    filter: Filter
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Filter {
+    pub name: Option<String>,
+    pub kind: Option<WeaponKind>,
+}
+
+const PAGE_SIZE: usize = 15;
+
+impl View {
+    pub fn new(filter: Filter) -> Self {
+        View { page: 0, generation: 0, filter }
+    }
+
+    pub fn modify_with_iter<'a>(mut self, create: CreateReply, iter: impl Iterator<Item = &'a Weapon>) -> CreateReply {
+        let mut desc = String::new();
+        let mut options = Vec::new();
+        let mut has_next = false;
+
+        for weapon in iter {
+            if options.len() >= PAGE_SIZE {
+                has_next = true;
+                break
+            }
+
+            let name = weapon.name.as_deref().unwrap_or("Unnamed Weapon");
+            writeln!(
+                desc,
+                "- **{}** [{}]",
+                name, weapon.kind.name(),
+            ).discard();
+
+            let view_weapon = super::weapon::View::new(weapon.weapon_id).new_message();
+            options.push(CreateSelectMenuOption::new(name, view_weapon.to_custom_id()));
+        }
+
+        if options.is_empty() {
+            return create.embed(self.filter.summary().no_results_embed());
+        }
+
+        let embed = CreateEmbed::new()
+            .title("Weapons")
+            .footer(CreateEmbedFooter::new(format!("Page {}", self.page + 1)))
+            .description(desc)
+            .color(DEFAULT_EMBED_COLOR);
+
+        let options = CreateSelectMenuKind::String { options };
+        let mut rows = vec![
+            CreateActionRow::SelectMenu(CreateSelectMenu::new(self.to_custom_id(), options).placeholder("View weapon..."))
+        ];
+
+        let mut nav = Vec::new();
+        if self.page > 0 || has_next {
+            nav.extend([
+                if self.page > 0 {
+                    self.new_button(utils::field_mut!(Self: page), self.page - 1, |_| 1)
+                } else {
+                    CreateButton::new("#no-back").disabled(true)
+                }.emoji('◀'),
+
+                if has_next {
+                    self.new_button(utils::field_mut!(Self: page), self.page + 1, |_| 2)
+                } else {
+                    CreateButton::new("#no-forward").disabled(true)
+                }.emoji('▶')
+            ]);
+        }
+
+        nav.push(self.new_share_button());
+        rows.insert(0, CreateActionRow::Buttons(nav));
+
+        create.embed(embed).components(rows)
+    }
+
+    pub fn modify(mut self, data: &HBotData, user_data: &HUserData, create: CreateReply) -> CreateReply {
+        self.generation = data.data_generation();
+        let azur_lane = data.azur_lane_in(user_data.language);
+        let filtered = self.filter
+            .iterate(&azur_lane)
+            .skip(PAGE_SIZE * usize::from(self.page));
+
+        self.modify_with_iter(create, filtered)
+    }
+}
+
+impl ButtonMessage for View {
+    fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
+        Ok(self.modify(ctx.data, &ctx.user_data(), ctx.create_reply()))
+    }
+
+    fn data_generation(&self) -> Option<u16> {
+        Some(self.generation)
+    }
+}
+
+impl Filter {
+    /// Summarizes the active parts of the filter.
+    fn summary(&self) -> super::FilterSummary {
+        super::FilterSummary::default()
+            .value("name", self.name.as_deref())
+            .value("kind", self.kind.map(WeaponKind::name))
+    }
+
+    fn iterate<'a>(&self, data: &'a HAzurLane) -> Box<dyn Iterator<Item = &'a Weapon> + 'a> {
+        let kind = self.kind;
+        let predicate = move |w: &&Weapon| kind.map_or(true, |k| w.kind == k);
+        match &self.name {
+            Some(name) => Box::new(data.weapons_by_prefix(name.as_str()).filter(predicate)),
+            None => Box::new(data.weapons().iter().filter(predicate))
+        }
+    }
+}
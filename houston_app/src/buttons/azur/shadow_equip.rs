@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::Write;
 
 use azur_lane::equip::*;
@@ -28,9 +29,9 @@ impl View {
             .author(super::get_ship_wiki_url(base_ship))
             .color(ship.rarity.color_rgb());
 
-        fn format_weapons(weapons: &[Weapon]) -> Option<String> {
+        fn format_weapons(weapons: &[Weapon]) -> String {
             if weapons.is_empty() {
-                return None;
+                return "*No weapons.*".to_owned();
             }
 
             let mut value = String::new();
@@ -38,43 +39,63 @@ impl View {
                 write!(value, "{}\n\n", crate::fmt::azur::Details::new(weapon)).discard();
             }
 
-            Some(value)
+            value
         }
 
-        for mount in &ship.shadow_equip {
-            if let Some(value) = format_weapons(&mount.weapons) {
-                embed = embed.field(
-                    format!("**`{: >3.0}%`** {}", mount.efficiency * 100f64, mount.name),
-                    value,
-                    true
-                );
+        // select menus are limited to 25 options, and the same weapon may be on several mounts
+        let mut options = Vec::new();
+        let mut seen = HashSet::new();
+        let mut add_options = |name: &str, weapons: &[Weapon]| {
+            for weapon in weapons {
+                if options.len() >= 25 || !seen.insert(weapon.weapon_id) {
+                    continue;
+                }
+
+                let view_weapon = super::weapon::View::new(weapon.weapon_id).new_message();
+                let label = utils::text::truncate(format!("{name}: {}", weapon.kind.name()), 100);
+                options.push(CreateSelectMenuOption::new(label, view_weapon.to_custom_id()));
             }
+        };
+
+        for mount in &ship.shadow_equip {
+            add_options(&mount.name, &mount.weapons);
+            embed = embed.field(
+                format!("**`{: >3.0}%`** {}", mount.efficiency * 100f64, mount.name),
+                format_weapons(&mount.weapons),
+                true
+            );
         }
 
         for equip in &ship.depth_charges {
-            if let Some(value) = format_weapons(&equip.weapons) {
-                embed = embed.field(
-                    format!("**`ASW:`** {}", equip.name),
-                    value,
-                    true
-                );
-            }
+            add_options(&equip.name, &equip.weapons);
+            embed = embed.field(
+                format!("**`ASW:`** {}", equip.name),
+                format_weapons(&equip.weapons),
+                true
+            );
         }
 
-        let components = vec![
+        let mut components = vec![
             CreateActionRow::Buttons(vec![{
                 let back = self.inner.to_custom_id();
                 CreateButton::new(back).emoji('⏪').label("Back")
             }])
         ];
 
+        if !options.is_empty() {
+            let options = CreateSelectMenuKind::String { options };
+            let select = CreateSelectMenu::new(self.to_custom_id(), options).placeholder("View weapon...");
+            components.push(CreateActionRow::SelectMenu(select));
+        }
+
         create.embed(embed).components(components)
     }
 }
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        let ship = ctx.data.azur_lane().ship_by_id(self.inner.ship_id).ok_or(ShipParseError)?;
+        let azur_lane = ctx.azur_lane();
+        let ship = azur_lane.ship_by_id(self.inner.ship_id).ok_or(ShipParseError)?;
         Ok(match self.inner.retrofit.and_then(|index| ship.retrofits.get(usize::from(index))) {
             None => self.modify_with_ship(ctx.create_reply(), ship, None),
             Some(retrofit) => self.modify_with_ship(ctx.create_reply(), retrofit, Some(ship))
@@ -2,7 +2,7 @@ use std::fmt::Write;
 
 use azur_lane::equip::*;
 use azur_lane::ship::*;
-use utils::{Discard, join};
+use utils::Discard;
 
 use crate::buttons::*;
 use super::ShipParseError;
@@ -15,6 +15,10 @@ pub struct View {
     pub affinity: ViewAffinity,
     pub retrofit: Option<u8>,
     mode: ButtonMessageMode,
+    /// Whether to render the stats as a compact list.
+    /// This follows the user's preference and isn't part of the custom ID.
+    #[serde(skip)]
+    compact: bool,
 }
 
 /// The affinity used to calculate stat values.
@@ -25,10 +29,36 @@ pub enum ViewAffinity {
     Oath
 }
 
+/// The level used to calculate stat values unless the user prefers another one.
+pub const DEFAULT_LEVEL: u8 = 120;
+
+/// The affinity used to calculate stat values unless the user prefers another one.
+pub const DEFAULT_AFFINITY: ViewAffinity = ViewAffinity::Love;
+
 impl View {
     /// Creates a new instance.
     pub fn new(ship_id: u32) -> Self {
-        Self { ship_id, level: 120, affinity: ViewAffinity::Love, retrofit: None, mode: ButtonMessageMode::Edit }
+        Self { ship_id, level: DEFAULT_LEVEL, affinity: DEFAULT_AFFINITY, retrofit: None, mode: ButtonMessageMode::Edit, compact: false }
+    }
+
+    /// Creates a new instance that shows the stats the way the user prefers.
+    pub fn with_user_data(ship_id: u32, user_data: &HUserData) -> Self {
+        Self::new(ship_id)
+            .stats_at(user_data.ship_level, user_data.ship_affinity)
+            .compact(user_data.compact_stats)
+    }
+
+    /// Sets the level and affinity used to calculate stat values.
+    pub fn stats_at(mut self, level: u8, affinity: ViewAffinity) -> Self {
+        self.level = level;
+        self.affinity = affinity;
+        self
+    }
+
+    /// Sets whether to render the stats as a compact list.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
     }
 
     /// Makes the button send a new message.
@@ -38,7 +68,7 @@ impl View {
     }
 
     /// Modifies the create-reply with preresolved ship data.
-    pub fn modify_with_ship(mut self, data: &HBotData, mut create: CreateReply, ship: &ShipData, base_ship: Option<&ShipData>) -> CreateReply {
+    pub fn modify_with_ship(mut self, data: &HBotData, azur_lane: &HAzurLane, mut create: CreateReply, ship: &ShipData, base_ship: Option<&ShipData>) -> CreateReply {
         self.mode = ButtonMessageMode::Edit;
         let base_ship = base_ship.unwrap_or(ship);
 
@@ -50,7 +80,7 @@ impl View {
         );
 
         let mut embed = CreateEmbed::new()
-            .author(super::get_ship_wiki_url(base_ship))
+            .author(super::get_ship_wiki_url(base_ship).name(base_ship.prefixed_name()))
             .description(description)
             .color(ship.rarity.color_rgb())
             .fields(self.get_stats_field(ship))
@@ -60,12 +90,14 @@ impl View {
         let mut rows = Vec::new();
         self.add_upgrade_row(&mut rows);
         self.add_retro_state_row(base_ship, &mut rows);
-        self.add_nav_row(ship, &mut rows);
+        self.add_nav_row(ship, base_ship, &mut rows);
 
+        // retrofits don't hold skins, so look up their default skin on the base ship
         if let Some(skin) = base_ship.skin_by_id(ship.default_skin_id) {
-            if let Some(image_data) = data.azur_lane().get_chibi_image(&skin.image_key) {
-                create = create.attachment(CreateAttachment::bytes(image_data.as_ref(), format!("{}.webp", skin.image_key)));
-                embed = embed.thumbnail(format!("attachment://{}.webp", skin.image_key));
+            if let Some(image_data) = azur_lane.get_chibi_image(&skin.image_key) {
+                let filename = format!("{}.{}", skin.image_key, azur_lane.image_extension());
+                embed = embed.thumbnail(format!("attachment://{filename}"));
+                create = create.attachment(CreateAttachment::bytes(image_data.as_ref(), filename));
             }
         }
 
@@ -87,7 +119,7 @@ impl View {
         );
     }
 
-    fn add_nav_row(&self, ship: &ShipData, rows: &mut Vec<CreateActionRow>) {
+    fn add_nav_row(&self, ship: &ShipData, base_ship: &ShipData, rows: &mut Vec<CreateActionRow>) {
         let self_custom_data = self.to_custom_data();
 
         let mut row = Vec::new();
@@ -113,7 +145,11 @@ impl View {
             row.push(button);
         }
 
-        {
+        // skins are only listed on the base ship
+        let has_lines = base_ship.skins.iter()
+            .any(|s| !s.words.is_empty() || s.words_extra.as_ref().is_some_and(|w| !w.is_empty()));
+
+        if has_lines {
             let view_lines = super::lines::View::with_back(self.ship_id, self_custom_data);
             let button = CreateButton::new(view_lines.to_custom_id())
                 .label("Lines")
@@ -122,6 +158,15 @@ impl View {
             row.push(button);
         }
 
+        if !ship.equip_slots.is_empty() {
+            let view_loadout = super::loadout::View::new(self.ship_id, self.retrofit).new_message();
+            let button = CreateButton::new(view_loadout.to_custom_id())
+                .label("Loadout")
+                .style(ButtonStyle::Secondary);
+
+            row.push(button);
+        }
+
         if !row.is_empty() {
             rows.push(CreateActionRow::Buttons(row));
         }
@@ -136,7 +181,11 @@ impl View {
             1 => rows.push(CreateActionRow::Buttons(vec![
                 base_button,
                 self.button_with_retrofit(Some(0))
-                    .label("Retrofit")
+                    .label(if base_ship.retrofit_changes_team() {
+                        format!("Retrofit ({})", base_ship.retrofits[0].hull_type.team_type().name())
+                    } else {
+                        "Retrofit".to_owned()
+                    })
             ])),
             _ => rows.push(CreateActionRow::Buttons(
                 std::iter::once(base_button)
@@ -182,7 +231,25 @@ impl View {
             ($val:expr) => {{ f($val.calc(u32::from(self.level), affinity)) }};
         }
 
-        let content = if ship.hull_type.team_type() != TeamType::Submarine {
+        let content = if self.compact {
+            let mut content = format!(
+                "**HP:** {}\n**Armor:** {}\n**RLD:** {}\n\
+                 **FP:** {}\n**TRP:** {}\n**EVA:** {}\n\
+                 **AA:** {}\n**AVI:** {}\n**ACC:** {}\n",
+                s!(stats.hp), stats.armor.name(), s!(stats.rld),
+                s!(stats.fp), s!(stats.trp), s!(stats.eva),
+                s!(stats.aa), s!(stats.avi), s!(stats.acc),
+            );
+
+            if ship.hull_type.team_type() != TeamType::Submarine {
+                writeln!(content, "**ASW:** {}", s!(stats.asw)).discard();
+            } else {
+                writeln!(content, "**OXY:** {}\n**AMO:** {}", stats.oxy, stats.amo).discard();
+            }
+
+            write!(content, "**SPD:** {}\n**LCK:** {}\n**Cost:** {}", f(stats.spd), f(stats.lck), stats.cost).discard();
+            content
+        } else if ship.hull_type.team_type() != TeamType::Submarine {
             format!(
                 "**`HP:`**`{: >5}` \u{2E31} **`{: <7}`**` ` \u{2E31} **`RLD:`**`{: >4}`\n\
                  **`FP:`**`{: >5}` \u{2E31} **`TRP:`**`{: >4}` \u{2E31} **`EVA:`**`{: >4}`\n\
@@ -226,7 +293,7 @@ impl View {
 
             for (index, &kind) in allowed.iter().enumerate() {
                 if index != 0 { text.push('/'); }
-                text.push_str(to_equip_slot_display(kind));
+                write!(text, "[{}]({})", kind.slot_name(), to_equip_list_url(kind)).discard();
             }
 
             if mount.preload != 0 {
@@ -259,7 +326,7 @@ impl View {
                 let mut text = String::new();
                 for s in &ship.skills {
                     if !text.is_empty() { text.push('\n'); }
-                    write!(text, "{} **{}**", s.category.emoji(), s.name).discard();
+                    write!(text, "{} **{}**", s.category.emoji(), utils::text::escape_markdown(&s.name)).discard();
                 }
                 Some(("Skills", text, false))
             }
@@ -269,10 +336,12 @@ impl View {
 
 impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
-        let ship = ctx.data.azur_lane().ship_by_id(self.ship_id).ok_or(ShipParseError)?;
-        Ok(match self.retrofit.and_then(|index| ship.retrofits.get(usize::from(index))) {
-            None => self.modify_with_ship(ctx.data, ctx.create_reply(), ship, None),
-            Some(retrofit) => self.modify_with_ship(ctx.data, ctx.create_reply(), retrofit, Some(ship))
+        let azur_lane = ctx.azur_lane();
+        let ship = azur_lane.ship_by_id(self.ship_id).ok_or(ShipParseError)?;
+        let this = self.compact(ctx.user_data().compact_stats);
+        Ok(match this.retrofit.and_then(|index| ship.retrofits.get(usize::from(index))) {
+            None => this.modify_with_ship(ctx.data, &azur_lane, ctx.create_reply(), ship, None),
+            Some(retrofit) => this.modify_with_ship(ctx.data, &azur_lane, ctx.create_reply(), retrofit, Some(ship))
         })
     }
 
@@ -292,29 +361,29 @@ impl ViewAffinity {
     }
 }
 
-/// Converts the equip slot to a masked link to the appropriate wiki page.
-fn to_equip_slot_display(kind: EquipKind) -> &'static str {
+/// Gets the URL to the wiki's equipment list for an equipment kind.
+fn to_equip_list_url(kind: EquipKind) -> &'static str {
     use config::azur_lane::equip::*;
 
     match kind {
-        EquipKind::DestroyerGun => join!("[DD Gun](", DD_GUN_LIST_URL, ")"),
-        EquipKind::LightCruiserGun => join!("[CL Gun](", CL_GUN_LIST_URL, ")"),
-        EquipKind::HeavyCruiserGun => join!("[CA Gun](", CA_GUN_LIST_URL, ")"),
-        EquipKind::LargeCruiserGun => join!("[CB Gun](", CB_GUN_LIST_URL, ")"),
-        EquipKind::BattleshipGun => join!("[BB Gun](", BB_GUN_LIST_URL, ")"),
-        EquipKind::SurfaceTorpedo => join!("[Torpedo](", SURFACE_TORPEDO_LIST_URL, ")"),
-        EquipKind::SubmarineTorpedo => join!("[Torpedo](", SUB_TORPEDO_LIST_URL, ")"),
-        EquipKind::AntiAirGun => join!("[AA Gun](", AA_GUN_LIST_URL, ")"),
-        EquipKind::FuzeAntiAirGun => join!("[AA Gun (Fuze)](", FUZE_AA_GUN_LIST_URL, ")"),
-        EquipKind::Fighter => join!("[Fighter](", FIGHTER_LIST_URL, ")"),
-        EquipKind::DiveBomber => join!("[Dive Bomber](", DIVE_BOMBER_LIST_URL, ")"),
-        EquipKind::TorpedoBomber => join!("[Torpedo Bomber](", TORPEDO_BOMBER_LIST_URL, ")"),
-        EquipKind::SeaPlane => join!("[Seaplane](", SEAPLANE_LIST_URL, ")"),
-        EquipKind::AntiSubWeapon => join!("[ASW](", ANTI_SUB_LIST_URL, ")"),
-        EquipKind::AntiSubAircraft => join!("[ASW Aircraft](", ANTI_SUB_LIST_URL, ")"),
-        EquipKind::Helicopter => join!("[Helicopter](", AUXILIARY_LIST_URL, ")"),
-        EquipKind::Missile => join!("[Missile](", SURFACE_TORPEDO_LIST_URL, ")"),
-        EquipKind::Cargo => join!("[Cargo](", CARGO_LIST_URL, ")"),
-        EquipKind::Auxiliary => join!("[Auxiliary](", AUXILIARY_LIST_URL, ")"),
+        EquipKind::DestroyerGun => DD_GUN_LIST_URL,
+        EquipKind::LightCruiserGun => CL_GUN_LIST_URL,
+        EquipKind::HeavyCruiserGun => CA_GUN_LIST_URL,
+        EquipKind::LargeCruiserGun => CB_GUN_LIST_URL,
+        EquipKind::BattleshipGun => BB_GUN_LIST_URL,
+        EquipKind::SurfaceTorpedo => SURFACE_TORPEDO_LIST_URL,
+        EquipKind::SubmarineTorpedo => SUB_TORPEDO_LIST_URL,
+        EquipKind::AntiAirGun => AA_GUN_LIST_URL,
+        EquipKind::FuzeAntiAirGun => FUZE_AA_GUN_LIST_URL,
+        EquipKind::Fighter => FIGHTER_LIST_URL,
+        EquipKind::DiveBomber => DIVE_BOMBER_LIST_URL,
+        EquipKind::TorpedoBomber => TORPEDO_BOMBER_LIST_URL,
+        EquipKind::SeaPlane => SEAPLANE_LIST_URL,
+        EquipKind::AntiSubWeapon => ANTI_SUB_LIST_URL,
+        EquipKind::AntiSubAircraft => ANTI_SUB_LIST_URL,
+        EquipKind::Helicopter => AUXILIARY_LIST_URL,
+        EquipKind::Missile => SURFACE_TORPEDO_LIST_URL,
+        EquipKind::Cargo => CARGO_LIST_URL,
+        EquipKind::Auxiliary => AUXILIARY_LIST_URL,
     }
 }
@@ -1,6 +1,9 @@
+use std::fmt::Write;
+
 use azur_lane::equip::*;
 use azur_lane::ship::*;
 use azur_lane::skill::*;
+use utils::Discard;
 
 use crate::buttons::*;
 use super::AugmentParseError;
@@ -13,6 +16,8 @@ pub struct View {
     pub skill_index: Option<u8>,
     pub back: Option<CustomData>,
     augment_index: Option<u8>,
+    /// The level of the selected skill to show, starting at 1. If [`None`], shows the max level.
+    level: Option<u8>,
 }
 
 /// Where to load the skills from.
@@ -45,12 +50,13 @@ type OwnedCreateEmbedField = (String, String, bool);
 impl View {
     /// Creates a new instance including a button to go back with some custom ID.
     pub fn with_back(source: ViewSource, back: CustomData) -> Self {
-        Self { source, skill_index: None, back: Some(back), augment_index: None }
+        Self { source, skill_index: None, back: Some(back), augment_index: None, level: None }
     }
 
     /// Modifies the create-reply with a preresolved list of skills and a base embed.
-    fn modify_with_skills<'a>(mut self, iterator: impl Iterator<Item = &'a Skill>, mut embed: CreateEmbed) -> (CreateEmbed, CreateActionRow) {
+    fn modify_with_skills<'a>(mut self, data: &HAzurLane, mut create: CreateReply, iterator: impl Iterator<Item = &'a Skill>, mut embed: CreateEmbed) -> (CreateReply, CreateEmbed, Vec<CreateActionRow>) {
         let mut components = Vec::new();
+        let mut level_row = None;
 
         for (t_index, skill) in iterator.enumerate().take(5) {
             #[allow(clippy::cast_possible_truncation)]
@@ -59,24 +65,58 @@ impl View {
             if t_index == self.skill_index {
                 embed = embed.color(skill.category.color_rgb())
                     .fields(self.create_ex_skill_fields(skill));
+
+                if skill.level_descriptions.len() > 1 {
+                    level_row = Some(self.create_level_row(skill));
+                }
+
+                if let Some(icon_key) = &skill.icon_key {
+                    if let Some(image_data) = data.get_skill_icon(icon_key) {
+                        let filename = format!("{icon_key}.{}", data.image_extension());
+                        embed = embed.thumbnail(format!("attachment://{filename}"));
+                        create = create.attachment(CreateAttachment::bytes(image_data.as_ref(), filename));
+                    }
+                }
             } else {
                 embed = embed.fields(self.create_skill_field(skill));
             }
 
-            if !skill.barrages.is_empty() || !skill.new_weapons.is_empty() {
-                let button = self.button_with_skill(t_index)
-                    .label(utils::text::truncate(&skill.name, 25))
-                    .style(ButtonStyle::Secondary);
+            // buff-only skills are also selectable since the expanded view
+            // still highlights them and shows their icon
+            let button = self.button_with_skill(t_index)
+                .label(utils::text::truncate(&skill.name, 25))
+                .style(ButtonStyle::Secondary);
 
-                components.push(button);
-            }
+            components.push(button);
         }
 
-        (embed, CreateActionRow::Buttons(components))
+        let rows = std::iter::once(CreateActionRow::Buttons(components)).chain(level_row).collect();
+        (create, embed, rows)
+    }
+
+    /// Creates the row to select the level of a skill.
+    fn create_level_row(&mut self, skill: &Skill) -> CreateActionRow {
+        let level_count = skill.level_descriptions.len().min(25);
+        let current = self.level.map_or(level_count, usize::from);
+
+        let options = (1..=level_count)
+            .filter_map(|level| {
+                let value = u8::try_from(level).ok()?;
+                let option = self.new_select_option(format!("Level {level}"), utils::field_mut!(Self: level), Some(value))
+                    .default_selection(level == current);
+                Some(option)
+            })
+            .collect();
+
+        let options = CreateSelectMenuKind::String { options };
+        let select = CreateSelectMenu::new(self.to_custom_id(), options)
+            .placeholder(format!("Level {current}"));
+
+        CreateActionRow::SelectMenu(select)
     }
 
     /// Modifies the create-reply with preresolved ship data.
-    fn modify_with_ship(mut self, data: &HBotData, create: CreateReply, ship: &ShipData, base_ship: Option<&ShipData>) -> CreateReply {
+    fn modify_with_ship(mut self, data: &HAzurLane, create: CreateReply, ship: &ShipData, base_ship: Option<&ShipData>) -> CreateReply {
         let base_ship = base_ship.unwrap_or(ship);
 
         let mut skills: Vec<&Skill> = ship.skills.iter().take(4).collect();
@@ -87,7 +127,7 @@ impl View {
             components.push(CreateButton::new(back.to_custom_id()).emoji('⏪').label("Back"));
         }
 
-        for (a_index, augment) in data.azur_lane().augments_by_ship_id(ship.group_id).enumerate().take(4) {
+        for (a_index, augment) in data.augments_by_ship_id(ship.group_id).enumerate().take(4) {
             if a_index == 0 {
                 components.push(
                     self.button_with_augment(None)
@@ -123,12 +163,12 @@ impl View {
             }
         }
 
-        let (embed, row) = self.modify_with_skills(skills.into_iter(), embed);
-        create.embed(embed).components(rows_without_empty([CreateActionRow::Buttons(components), row]))
+        let (create, embed, rows) = self.modify_with_skills(data, create, skills.into_iter(), embed);
+        create.embed(embed).components(rows_without_empty(std::iter::once(CreateActionRow::Buttons(components)).chain(rows)))
     }
 
     /// Modifies the create-reply with preresolved augment data.
-    fn modify_with_augment(self, create: CreateReply, augment: &Augment) -> CreateReply {
+    fn modify_with_augment(self, data: &HAzurLane, create: CreateReply, augment: &Augment) -> CreateReply {
         let embed = CreateEmbed::new().color(ShipRarity::SR.color_rgb()).author(CreateEmbedAuthor::new(&augment.name));
         let skills = augment.effect.iter().chain(augment.skill_upgrade.as_ref().map(|s| &s.skill));
 
@@ -136,13 +176,17 @@ impl View {
             CreateButton::new(back.to_custom_id()).emoji('⏪').label("Back")
         ]));
 
-        let (embed, row) = self.modify_with_skills(skills, embed);
-        create.embed(embed).components(rows_without_empty([nav_row, Some(row)]))
+        let (create, embed, rows) = self.modify_with_skills(data, create, skills, embed);
+        create.embed(embed).components(rows_without_empty(nav_row.into_iter().chain(rows)))
     }
 
     /// Creates a button that redirects to a skill index.
     fn button_with_skill(&mut self, index: Option<u8>) -> CreateButton {
-        self.button_with_u8(utils::field_mut!(Self: skill_index), index)
+        // switching skills should start at the max level
+        let level = self.level.take();
+        let button = self.button_with_u8(utils::field_mut!(Self: skill_index), index);
+        self.level = level;
+        button
     }
 
     /// Creates a button that redirects to a skill index.
@@ -158,25 +202,47 @@ impl View {
     /// Creates the embed field for a skill.
     fn create_skill_field(&self, skill: &Skill) -> [OwnedCreateEmbedField; 1] {
         [(
-            format!("{} {}", skill.category.emoji(), skill.name),
-            utils::text::truncate(&skill.description, 1000).into_owned(),
+            format!("{} {}", skill.category.emoji(), utils::text::escape_markdown(&skill.name)),
+            utils::text::truncate(utils::text::escape_markdown(&skill.description), 1000).into_owned(),
             false
         )]
     }
 
     /// Creates the embed fields for the selected skill.
     fn create_ex_skill_fields(&self, skill: &Skill) -> Vec<OwnedCreateEmbedField> {
+        let description = match self.level {
+            Some(level) => skill.description_at(usize::from(level)),
+            None => &skill.description,
+        };
+
         let mut fields = vec![(
-            format!("{} __{}__", skill.category.emoji(), skill.name),
-            utils::text::truncate(&skill.description, 1000).into_owned(),
+            format!("{} __{}__", skill.category.emoji(), utils::text::escape_markdown(&skill.name)),
+            utils::text::truncate(utils::text::escape_markdown(description), 1000).into_owned(),
             false
         )];
 
+        if skill.cooldown.is_some() || !skill.effects.is_empty() {
+            let mut values = String::new();
+            if let Some(cooldown) = skill.cooldown {
+                writeln!(values, "**Cooldown:** {cooldown}s").discard();
+            }
+
+            for effect in &skill.effects {
+                writeln!(values, "**{}:** {}", utils::text::escape_markdown(&effect.name), effect.value).discard();
+            }
+
+            fields.push((
+                "__Values__".to_owned(),
+                utils::text::truncate(values, 1000),
+                false
+            ));
+        }
+
         if !skill.barrages.is_empty() {
             fields.push((
                 "__Barrage__".to_owned(),
                 {
-                    let m = get_skills_extra_summary(skill);
+                    let m = crate::fmt::azur::barrage::skill_summary(skill);
                     if m.len() <= 1024 { m } else { log::warn!("barrage:\n{m}"); "<barrage data too long>".to_owned() }
                 },
                 false
@@ -214,117 +280,16 @@ impl ButtonMessage for View {
     fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
         match &self.source {
             ViewSource::Ship(source) => {
-                let base_ship = ctx.data.azur_lane().ship_by_id(source.ship_id).ok_or(ShipParseError)?;
+                let azur_lane = ctx.azur_lane();
+                let base_ship = azur_lane.ship_by_id(source.ship_id).ok_or(ShipParseError)?;
                 let ship = source.retrofit.and_then(|i| base_ship.retrofits.get(usize::from(i))).unwrap_or(base_ship);
-                Ok(self.modify_with_ship(ctx.data, ctx.create_reply(), ship, Some(base_ship)))
+                Ok(self.modify_with_ship(&azur_lane, ctx.create_reply(), ship, Some(base_ship)))
             }
             ViewSource::Augment(augment_id) => {
-                let augment = ctx.data.azur_lane().augment_by_id(*augment_id).ok_or(AugmentParseError)?;
-                Ok(self.modify_with_augment(ctx.create_reply(), augment))
-            }
-        }
-    }
-}
-
-/// Constructs skill barrage display data.
-fn get_skills_extra_summary(skill: &Skill) -> String {
-    use utils::text::InlineStr;
-
-    return join("\n\n", skill.barrages.iter().filter_map(get_skill_barrage_summary)).unwrap_or_else(String::new);
-
-    macro_rules! idk {
-        ($opt:expr, $($arg:tt)*) => {
-            match $opt {
-                None => None,
-                Some(v) => Some(format!($($arg)*, sum = v))
-            }
-        };
-    }
-
-    fn get_skill_barrage_summary(barrage: &SkillBarrage) -> Option<String> {
-        idk!(
-            join("\n", barrage.attacks.iter().filter_map(get_skill_attack_summary)),
-            "__`Trgt. | Dmg.       | Ammo:  L / M / H  | Scaling  | Fl.`__\n{sum}"
-            // `Fix.  | 12 x  58.0 | Nor.: 120/ 80/ 80 | 100% AVI | ---`
-        )
-    }
-
-    fn get_skill_attack_summary(attack: &SkillAttack) -> Option<String> {
-        match &attack.weapon.data {
-            WeaponData::Bullets(bullets) => get_barrage_summary(bullets, Some(attack.target)),
-            WeaponData::Aircraft(aircraft) => idk!(
-                get_aircraft_summary(aircraft),
-                "`{: >5} |{: >3} x Aircraft                             |    `\n{sum}",
-                attack.target.short_name(), aircraft.amount
-            ),
-            _ => None
-        }
-    }
-
-    fn get_barrage_summary(barrage: &Barrage, target: Option<SkillAttackTarget>) -> Option<String> {
-        struct Value<'a> { amount: u32, bullet: &'a Bullet }
-
-        fn match_key(a: &Bullet, b: &Bullet) -> bool {
-            a.kind == b.kind &&
-            a.ammo == b.ammo &&
-            a.modifiers == b.modifiers
-        }
-
-        let mut sets: Vec<Value> = Vec::new();
-        for bullet in &barrage.bullets {
-            // find & modify, or insert
-            match sets.iter_mut().find(|i| match_key(i.bullet, bullet)) {
-                Some(entry) => entry.amount += bullet.amount,
-                None => sets.push(Value { amount: bullet.amount, bullet }),
+                let data = ctx.azur_lane();
+                let augment = data.augment_by_id(*augment_id).ok_or(AugmentParseError)?;
+                Ok(self.modify_with_augment(&data, ctx.create_reply(), augment))
             }
         }
-
-        join("\n", sets.into_iter().map(|Value { amount, bullet }| {
-            let ArmorModifiers(l, m, h) = bullet.modifiers;
-            let sprapnel_mark = if bullet.kind == BulletKind::Shrapnel { "*" } else { " " };
-            format!(
-                // damage with coeff |
-                // ammo type & mods |
-                // % of scaling stat |
-                // amount | totals
-                "`\
-                {: <5} |\
-                {: >3} x{: >6.1}{}|\
-                {: >5}: {: >3.0}/{: >3.0}/{: >3.0} |\
-                {: >4.0}% {: <3} | \
-                {}`",
-                target.map(|t| t.short_name()).unwrap_or(""),
-                amount, barrage.damage * barrage.coefficient, sprapnel_mark,
-                bullet.ammo.short_name(), l * 100f64, m * 100f64, h * 100f64,
-                barrage.scaling * 100f64, barrage.scaling_stat.name(),
-                get_bullet_flags(bullet),
-            )
-        }))
-    }
-
-    fn get_bullet_flags(bullet: &Bullet) -> InlineStr<3> {
-        let mut res = [b'-'; 3];
-        if bullet.pierce != 0 { res[0] = b'P'; }
-        if bullet.flags.contains(BulletFlags::IGNORE_SHIELD) { res[1] = b'I'; }
-        if bullet.flags.dive_filter().is_empty() { res[2] = b'D'; }
-
-        // SAFETY: Always ASCII here.
-        unsafe { InlineStr::from_utf8_unchecked(res) }
-    }
-
-    fn get_aircraft_summary(aircraft: &Aircraft) -> Option<String> {
-        join("\n", aircraft.weapons.iter().filter_map(|weapon| match &weapon.data {
-            WeaponData::Bullets(barrage) => get_barrage_summary(barrage, None),
-            _ => None
-        }))
-    }
-
-    fn join(separator: &str, mut items: impl Iterator<Item = String>) -> Option<String> {
-        let mut result = items.next()?;
-        for item in items {
-            result.push_str(separator);
-            result.push_str(&item);
-        }
-        Some(result)
     }
 }
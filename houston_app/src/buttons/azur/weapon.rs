@@ -0,0 +1,48 @@
+use azur_lane::equip::*;
+
+use crate::buttons::*;
+use super::WeaponParseError;
+
+/// Views a weapon.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct View {
+    pub weapon_id: u32,
+    mode: ButtonMessageMode,
+}
+
+impl View {
+    /// Creates a new instance.
+    pub fn new(weapon_id: u32) -> Self {
+        Self { weapon_id, mode: ButtonMessageMode::Edit }
+    }
+
+    /// Makes the button send a new message.
+    pub fn new_message(mut self) -> Self {
+        self.mode = ButtonMessageMode::New;
+        self
+    }
+
+    /// Modifies the create-reply with a preresolved weapon.
+    pub fn modify_with_weapon(mut self, create: CreateReply, weapon: &Weapon) -> CreateReply {
+        self.mode = ButtonMessageMode::Edit;
+
+        let embed = CreateEmbed::new()
+            .color(DEFAULT_EMBED_COLOR)
+            .author(CreateEmbedAuthor::new(weapon.name.as_deref().unwrap_or("Unnamed Weapon")))
+            .description(crate::fmt::azur::Details::new(weapon).to_string());
+
+        create.embed(embed).components(Vec::new())
+    }
+}
+
+impl ButtonMessage for View {
+    fn create_reply(self, ctx: ButtonContext<'_>) -> anyhow::Result<CreateReply> {
+        let azur_lane = ctx.azur_lane();
+        let weapon = azur_lane.weapon_by_id(self.weapon_id).ok_or(WeaponParseError)?;
+        Ok(self.modify_with_weapon(ctx.create_reply(), weapon))
+    }
+
+    fn message_mode(&self) -> ButtonMessageMode {
+        self.mode
+    }
+}
@@ -1,5 +1,7 @@
 //! Common button arg types.
 
 mod none;
+mod share;
 
 pub use none::None;
+pub use share::Share;
@@ -0,0 +1,31 @@
+use crate::buttons::*;
+
+/// Replies with a share code for another view.
+///
+/// The code can be imported again with `/import`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Share {
+    data: CustomData,
+}
+
+impl Share {
+    /// Creates a new instance for the view's data.
+    pub const fn new(data: CustomData) -> Self {
+        Self { data }
+    }
+}
+
+impl ButtonArgsReply for Share {
+    async fn reply(self, ctx: ButtonContext<'_>) -> HResult {
+        let embed = CreateEmbed::new()
+            .title("Share Code")
+            .description(format!("```\n{}\n```\nUse `/import` with this code to open the view again.", self.data.to_share_code()))
+            .color(DEFAULT_EMBED_COLOR);
+
+        let reply = CreateInteractionResponseMessage::new()
+            .ephemeral(true)
+            .embed(embed);
+
+        ctx.reply(CreateInteractionResponse::Message(reply)).await
+    }
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use serenity::prelude::*;
 use utils::fields::FieldMut;
@@ -9,6 +10,9 @@ pub mod azur;
 pub mod common;
 
 utils::define_simple_error!(InvalidInteractionError(()): "Invalid interaction.");
+utils::define_simple_error!(CorruptedCustomIdError(()): "The button data is corrupted.");
+utils::define_simple_error!(OutdatedViewError(()): "This view is outdated, re-run the command.");
+utils::define_simple_error!(InvalidShareCodeError(()): "The share code is invalid.");
 
 /// Helper macro that repeats needed code for every [`ButtonArgs`] variant.
 macro_rules! define_button_args {
@@ -93,13 +97,22 @@ define_button_args! {
     ViewSearchEquip(azur::search_equip::View),
     /// Open the augment search.
     ViewSearchAugment(azur::search_augment::View),
+    /// Open the ship loadout builder.
+    ViewLoadout(azur::loadout::View),
+    /// Open the weapon details.
+    ViewWeapon(azur::weapon::View),
+    /// Open the weapon search.
+    ViewSearchWeapon(azur::search_weapon::View),
+    /// Reply with a share code for a view.
+    Share(common::Share),
 }
 
 impl ButtonArgs {
     /// Constructs button arguments from a component custom ID.
     pub fn from_custom_id(id: &str) -> anyhow::Result<ButtonArgs> {
-        let bytes = utils::str_as_data::from_b65536(id)?;
-        CustomData(bytes).to_button_args()
+        let data = CustomData::from_custom_id(id)?;
+        data.to_button_args()
+            .inspect_err(|_| log::debug!("Invalid custom data: {}", data.to_debug_string()))
     }
 }
 
@@ -128,6 +141,9 @@ impl ButtonEventHandler {
     async fn interaction_dispatch(&self, ctx: &Context, interaction: &ComponentInteraction) -> HResult {
         use ComponentInteractionDataKind as Kind;
 
+        let start = Instant::now();
+        self.bot_data.metrics().inc_buttons();
+
         let custom_id = match &interaction.data.kind {
             Kind::StringSelect { values } if values.len() == 1 => &values[0],
             Kind::Button => &interaction.data.custom_id,
@@ -137,23 +153,39 @@ impl ButtonEventHandler {
         let args = ButtonArgs::from_custom_id(custom_id)?;
         log::trace!("{}: {:?}", interaction.user.name, args);
 
-        args.reply(ButtonContext {
+        let result = args.reply(ButtonContext {
             interaction,
             http: &ctx.http,
             data: &self.bot_data
-        }).await
+        }).await;
+
+        log::info!("{}: completed in {:.2?}", interaction.user.name, start.elapsed());
+        result
     }
 
     #[cold]
     async fn handle_dispatch_error(&self, ctx: Context, interaction: ComponentInteraction, err: anyhow::Error) {
-        if let Some(err) = err.downcast_ref::<serenity::Error>() {
-            log::warn!("Discord interaction error: {err}");
-            return;
+        self.bot_data.metrics().inc_button_errors();
+
+        let is_rate_limit = is_rate_limit_error(&err);
+        if !is_rate_limit {
+            if let Some(err) = err.downcast_ref::<serenity::Error>() {
+                log::warn!("Discord interaction error: {err}");
+                return;
+            }
         }
 
-        log::warn!("Component error: {err:?}");
+        let err_text = if is_rate_limit {
+            log::info!("Rate-limited in component interaction: {err}");
+            RATE_LIMITED_MESSAGE.to_owned()
+        } else if err.is::<OutdatedViewError>() {
+            log::trace!("Outdated view for {}", interaction.user.name);
+            err.to_string()
+        } else {
+            log::warn!("Component error: {err:?}");
+            format!("Button error: ```{err}```")
+        };
 
-        let err_text = format!("Button error: ```{err}```");
         let reply = CreateReply::default().ephemeral(true)
             .embed(CreateEmbed::new().description(err_text).color(ERROR_EMBED_COLOR));
         let response = reply.to_slash_initial_response(Default::default());
@@ -219,6 +251,19 @@ pub trait ToCustomData {
             .default_selection(default)
     }
 
+    /// Creates a button that replies with a share code for the current state.
+    ///
+    /// If the state is too large to be shared, the button is disabled.
+    fn new_share_button(&self) -> CreateButton {
+        let share = common::Share::new(self.to_custom_data());
+        let custom_id = share.to_custom_id();
+        if custom_id.chars().count() > MAX_CUSTOM_ID_LEN {
+            return CreateButton::new("#no-share").emoji('🔗').label("Share").disabled(true);
+        }
+
+        CreateButton::new(custom_id).emoji('🔗').label("Share")
+    }
+
     /// Creates a custom ID with one field replaced.
     #[must_use]
     fn to_custom_id_with<T>(&mut self, field: impl FieldMut<Self, T>, mut value: T) -> String {
@@ -253,15 +298,25 @@ pub struct ButtonContext<'a> {
     pub data: &'a HBotData,
 }
 
-impl ButtonContext<'_> {
+impl<'a> ButtonContext<'a> {
     /// Replies to the interaction.
     pub async fn reply(&self, create: CreateInteractionResponse) -> HResult {
         Ok(self.interaction.create_response(self.http, create).await?)
     }
 
+    /// Gets a copy of the user data for the interacting user.
+    pub fn user_data(&self) -> HUserData {
+        self.data.get_user_data(self.interaction.user.id)
+    }
+
     /// Creates a fitting base reply.
     pub fn create_reply(&self) -> CreateReply {
-        self.data.get_user_data(self.interaction.user.id).create_reply()
+        self.user_data().create_reply()
+    }
+
+    /// Gets the Azur Lane game data in the user's language.
+    pub fn azur_lane(&self) -> Arc<HAzurLane> {
+        self.data.azur_lane_in(self.user_data().language)
     }
 }
 
@@ -278,6 +333,13 @@ pub trait ButtonMessage: Sized {
 
     /// How to post the message. Defaults to [`ButtonMessageMode::Edit`].
     fn message_mode(&self) -> ButtonMessageMode { ButtonMessageMode::Edit }
+
+    /// The data generation this view was created with, if it should be checked.
+    ///
+    /// Views that depend on the exact data, like paged lists, may opt into this.
+    /// If it doesn't match [`HBotData::data_generation`], the view is rejected as outdated.
+    /// Defaults to [`None`].
+    fn data_generation(&self) -> Option<u16> { None }
 }
 
 /// The mode a [`ButtonMessage`] uses to post its message.
@@ -291,6 +353,10 @@ pub enum ButtonMessageMode {
 
 impl<T: ButtonMessage> ButtonArgsReply for T {
     async fn reply(self, ctx: ButtonContext<'_>) -> HResult {
+        if self.data_generation().is_some_and(|g| g != ctx.data.data_generation()) {
+            Err(OutdatedViewError(()))?
+        }
+
         let mode = self.message_mode();
         let reply = self.create_reply(ctx.clone())?;
         let reply = reply.to_slash_initial_response(Default::default());
@@ -304,6 +370,34 @@ impl<T: ButtonMessage> ButtonArgsReply for T {
     }
 }
 
+/// The first byte of custom IDs that hold a checksum.
+///
+/// Custom IDs without a checksum start with the [`ButtonArgs`] variant index
+/// encoded as a varint, which can never be this value.
+const CHECKSUM_MARKER: u8 = 0xFF;
+
+/// Calculates the CRC-16/CCITT-FALSE checksum of the data.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+
+    crc
+}
+
+/// The maximum length of a component custom ID, in characters.
+const MAX_CUSTOM_ID_LEN: usize = 100;
+
+/// The maximum length of a share code.
+///
+/// This is a generous upper bound that is checked before decoding.
+/// Codes are also rejected if their data wouldn't fit into a custom ID.
+const MAX_SHARE_CODE_LEN: usize = 4 * MAX_CUSTOM_ID_LEN;
+
 /// Represents custom data for another menu.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CustomData(Vec<u8>);
@@ -313,9 +407,88 @@ impl CustomData {
     pub const EMPTY: Self = Self(Vec::new());
 
     /// Converts this instance to a component custom ID.
+    ///
+    /// The data is prefixed with a checksum so corrupted IDs can be detected.
     #[must_use]
     pub fn to_custom_id(&self) -> String {
-        utils::str_as_data::to_b65536(&self.0)
+        utils::str_as_data::to_b65536(&self.to_checked_bytes())
+    }
+
+    /// Converts this instance to a code that users can share.
+    ///
+    /// This holds the same data as [`Self::to_custom_id`], but only uses URL-safe ASCII characters.
+    #[must_use]
+    pub fn to_share_code(&self) -> String {
+        utils::str_as_data::to_base64url(&self.to_checked_bytes())
+    }
+
+    /// Reverses the operation done by [`Self::to_share_code`].
+    ///
+    /// Unlike custom IDs, share codes are user input, so they must have a valid checksum,
+    /// fit into a custom ID, and hold valid [`ButtonArgs`]. Otherwise, returns an [`InvalidShareCodeError`].
+    pub fn from_share_code(code: &str) -> anyhow::Result<Self> {
+        let code = code.trim();
+        if code.len() > MAX_SHARE_CODE_LEN {
+            Err(InvalidShareCodeError(()))?
+        }
+
+        let bytes = utils::str_as_data::from_base64url(code).map_err(|_| InvalidShareCodeError(()))?;
+        if bytes.first() != Some(&CHECKSUM_MARKER) {
+            Err(InvalidShareCodeError(()))?
+        }
+
+        let data = Self::from_checked_bytes(bytes).map_err(|_| InvalidShareCodeError(()))?;
+        if data.to_custom_id().chars().count() > MAX_CUSTOM_ID_LEN || data.to_button_args().is_err() {
+            Err(InvalidShareCodeError(()))?
+        }
+
+        Ok(data)
+    }
+
+    /// Prefixes the data with a checksum.
+    fn to_checked_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.0.len() + 3);
+        buf.push(CHECKSUM_MARKER);
+        buf.extend(crc16(&self.0).to_le_bytes());
+        buf.extend(&self.0);
+        buf
+    }
+
+    /// Reverses the operation done by [`Self::to_checked_bytes`].
+    ///
+    /// Data without a checksum is accepted as-is.
+    fn from_checked_bytes(bytes: Vec<u8>) -> anyhow::Result<Self> {
+        match bytes.split_first() {
+            Some((&CHECKSUM_MARKER, rest)) => {
+                let Some((checksum, payload)) = rest.split_first_chunk::<2>() else {
+                    Err(CorruptedCustomIdError(()))?
+                };
+
+                if u16::from_le_bytes(*checksum) != crc16(payload) {
+                    Err(CorruptedCustomIdError(()))?
+                }
+
+                Ok(Self(payload.to_vec()))
+            },
+            _ => Ok(Self(bytes)),
+        }
+    }
+
+    /// Reverses the operation done by [`Self::to_custom_id`].
+    ///
+    /// If the ID has a checksum and it doesn't match, returns a [`CorruptedCustomIdError`].
+    /// IDs created before checksums were added are accepted without verification.
+    pub fn from_custom_id(id: &str) -> anyhow::Result<Self> {
+        let bytes = utils::str_as_data::from_b65536(id)?;
+        Self::from_checked_bytes(bytes)
+    }
+
+    /// Converts this instance to a readable string for logging.
+    ///
+    /// This isn't a valid custom ID. Use [`Self::to_custom_id`] for that.
+    #[must_use]
+    pub fn to_debug_string(&self) -> String {
+        utils::str_as_data::to_base64url(&self.0)
     }
 
     /// Converts this instance to [`ButtonArgs`].
@@ -335,3 +508,95 @@ impl CustomData {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn custom_data() -> CustomData {
+        ButtonArgs::None(common::None::new(1234, 5678)).to_custom_data()
+    }
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn custom_id_round_trip() {
+        let data = custom_data();
+        let id = data.to_custom_id();
+
+        let back = CustomData::from_custom_id(&id).unwrap();
+        assert_eq!(back.0, data.0);
+        assert!(matches!(back.to_button_args().unwrap(), ButtonArgs::None(_)));
+    }
+
+    #[test]
+    fn custom_id_legacy() {
+        let data = custom_data();
+        let id = utils::str_as_data::to_b65536(&data.0);
+
+        let back = CustomData::from_custom_id(&id).unwrap();
+        assert_eq!(back.0, data.0);
+    }
+
+    #[test]
+    fn custom_id_corrupted() {
+        let data = custom_data();
+        let id = data.to_custom_id();
+
+        // flip a bit in the payload
+        let mut bytes = utils::str_as_data::from_b65536(&id).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        let id = utils::str_as_data::to_b65536(&bytes);
+
+        let err = CustomData::from_custom_id(&id).unwrap_err();
+        assert!(err.is::<CorruptedCustomIdError>());
+    }
+
+    #[test]
+    fn custom_id_truncated() {
+        let id = utils::str_as_data::to_b65536(&[CHECKSUM_MARKER, 0]);
+
+        let err = CustomData::from_custom_id(&id).unwrap_err();
+        assert!(err.is::<CorruptedCustomIdError>());
+    }
+
+    #[test]
+    fn share_code_round_trip() {
+        let data = custom_data();
+        let code = data.to_share_code();
+        assert!(code.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+
+        let back = CustomData::from_share_code(&format!(" {code}\n")).unwrap();
+        assert_eq!(back.0, data.0);
+    }
+
+    #[test]
+    fn share_code_invalid() {
+        fn is_invalid(code: &str) -> bool {
+            CustomData::from_share_code(code).is_err_and(|e| e.is::<InvalidShareCodeError>())
+        }
+
+        let data = custom_data();
+
+        // malformed input
+        assert!(is_invalid(""));
+        assert!(is_invalid("not a code!"));
+
+        // missing checksum
+        assert!(is_invalid(&utils::str_as_data::to_base64url(&data.0)));
+
+        // corrupted payload
+        let mut bytes = data.to_checked_bytes();
+        *bytes.last_mut().unwrap() ^= 1;
+        assert!(is_invalid(&utils::str_as_data::to_base64url(&bytes)));
+
+        // oversized payload, even with a valid checksum
+        let oversized = CustomData(vec![0; 4 * MAX_CUSTOM_ID_LEN]);
+        assert!(is_invalid(&oversized.to_share_code()));
+        let too_long = CustomData(vec![0; 2 * MAX_CUSTOM_ID_LEN]);
+        assert!(is_invalid(&too_long.to_share_code()));
+    }
+}
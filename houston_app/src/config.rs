@@ -1,8 +1,10 @@
 #![allow(dead_code)]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use serde::Deserialize;
+use serenity::model::id::UserId;
 
 #[derive(Debug, Deserialize)]
 pub struct HConfig {
@@ -21,6 +23,36 @@ pub struct HDiscordConfig {
 #[derive(Debug, Deserialize, Default)]
 pub struct HBotConfig {
     pub azur_lane_data: Option<PathBuf>,
+    #[serde(default)]
+    pub command_registration: HRetryConfig,
+    /// The address to serve metrics on. If unset, metrics aren't served.
+    pub metrics_addr: Option<SocketAddr>,
+    /// Per-user cooldowns in seconds, keyed by qualified command name.
+    /// Commands without an entry have no cooldown.
+    #[serde(default)]
+    pub cooldowns: HashMap<String, u64>,
+    /// Additional users allowed to use owner-only commands.
+    /// The owners of the bot application are always included.
+    #[serde(default)]
+    pub owners: HashSet<UserId>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HRetryConfig {
+    /// The maximum amount of attempts.
+    pub attempts: u32,
+    /// The delay before the first retry, in milliseconds. Doubles after every retry.
+    pub backoff_ms: u64,
+}
+
+impl Default for HRetryConfig {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff_ms: 1000,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Default)]
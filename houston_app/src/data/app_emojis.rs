@@ -116,6 +116,14 @@ fn png_to_data_url(png: &[u8]) -> String {
 }
 
 impl<'a> HAppEmojis<'a> {
+    /// Whether the app emojis have been loaded.
+    ///
+    /// If not, every emoji getter returns a placeholder.
+    #[must_use]
+    pub fn is_loaded(self) -> bool {
+        self.0.is_some()
+    }
+
     #[must_use]
     pub fn hull(self, hull_type: HullType) -> &'a ReactionType {
         let Some(s) = self.0 else {
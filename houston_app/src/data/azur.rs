@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -7,6 +8,8 @@ use utils::fuzzy::Search;
 
 use azur_lane::equip::*;
 use azur_lane::ship::*;
+use azur_lane::keys::{self, KeyScheme};
+use azur_lane::{ImageFormat, Language};
 
 /// Extended Azur Lane game data for quicker access.
 #[derive(Debug, Default)]
@@ -15,29 +18,78 @@ pub struct HAzurLane {
     ships: Vec<ShipData>,
     equips: Vec<Equip>,
     augments: Vec<Augment>,
+    weapons: Vec<Weapon>,
     ship_id_to_index: HashMap<u32, usize>,
     ship_simsearch: Search<()>,
     equip_id_to_index: HashMap<u32, usize>,
     equip_simsearch: Search<()>,
     augment_id_to_index: HashMap<u32, usize>,
     augment_simsearch: Search<()>,
+    weapon_id_to_index: HashMap<u32, usize>,
+    weapon_simsearch: Search<()>,
     ship_id_to_augment_index: HashMap<u32, Vec<usize>>,
+    hull_type_to_augment_index: HashMap<HullType, Vec<usize>>,
+    ship_id_to_encouraging_index: HashMap<u32, Vec<usize>>,
+    generation: u16,
+    image_format: ImageFormat,
     chibi_sprite_cache: DashMap<String, Option<Arc<[u8]>>>,
+    skill_icon_cache: DashMap<String, Option<Arc<[u8]>>>,
 }
 
 impl HAzurLane {
-    /// Constructs extended data from definitions.
+    /// Constructs extended data from definitions in the specified language.
+    ///
+    /// If the data cannot be loaded, the error is logged and empty data is returned.
     #[must_use]
-    pub fn load_from(data_path: PathBuf) -> Self {
+    pub fn load_from(data_path: PathBuf, language: Language) -> Self {
+        match Self::try_load_from(data_path, language) {
+            Ok(data) => data,
+            Err(err) => {
+                log::error!("No Azur Lane data: {err:?}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Constructs extended data from definitions in the specified language.
+    ///
+    /// Unlike [`Self::load_from`], this returns an error if the data cannot be loaded.
+    pub fn try_load_from(data_path: PathBuf, language: Language) -> anyhow::Result<Self> {
         // loads the actual definition file from disk
         // the error is just a short description of the error
-        fn load_definitions(data_path: &Path) -> anyhow::Result<azur_lane::DefinitionData> {
+        fn load_definitions(data_path: &Path, language: Language) -> anyhow::Result<azur_lane::DefinitionData> {
             use anyhow::Context;
-            let f = std::fs::File::open(data_path.join("main.json")).context("Failed to read Azur Lane data.")?;
-            let data = simd_json::from_reader(f).context("Failed to parse Azur Lane data.")?;
+
+            /// Just the key scheme marker. Everything else is skipped.
+            #[derive(serde::Deserialize)]
+            struct Header {
+                #[serde(default)]
+                key_scheme: KeyScheme,
+            }
+
+            let mut buf = std::fs::read(data_path.join(language.definition_file_name())).context("Failed to read Azur Lane data.")?;
+            let header: Header = serde_json::from_slice(&buf).context("Failed to parse Azur Lane data.")?;
+
+            // only compact data needs to go through a generic value to expand its keys
+            let data = match header.key_scheme {
+                KeyScheme::Full => simd_json::from_slice(&mut buf).context("Failed to parse Azur Lane data.")?,
+                KeyScheme::Compact => {
+                    let mut value: serde_json::Value = serde_json::from_slice(&buf).context("Failed to parse Azur Lane data.")?;
+                    keys::expand_keys(&mut value);
+                    serde_json::from_value(value).context("Failed to parse Azur Lane data.")?
+                },
+            };
+
             Ok(data)
         }
 
+        let data = load_definitions(&data_path, language)?;
+        Ok(Self::from_definitions(data_path, data))
+    }
+
+    /// Constructs extended data from already loaded definitions.
+    #[must_use]
+    pub fn from_definitions(data_path: PathBuf, mut data: azur_lane::DefinitionData) -> Self {
         // this function should ensure we don't deal with empty paths, absolute or rooted paths,
         // or ones that refer to parent directories to detect potential path traversal attacks
         // when loading untrusted data. note: we only log this, we don't abort.
@@ -54,14 +106,6 @@ impl HAzurLane {
             }
         }
 
-        let mut data = match load_definitions(&data_path) {
-            Ok(data) => data,
-            Err(err) => {
-                log::error!("No Azur Lane data: {err:?}");
-                return Self::default();
-            }
-        };
-
         let mut ship_id_to_index = HashMap::with_capacity(data.ships.len());
         let mut ship_simsearch = Search::new();
 
@@ -71,6 +115,7 @@ impl HAzurLane {
         let mut augment_id_to_index = HashMap::with_capacity(data.augments.len());
         let mut augment_simsearch = Search::new();
         let mut ship_id_to_augment_index = HashMap::<u32, Vec<usize>>::with_capacity(data.augments.len());
+        let mut hull_type_to_augment_index = HashMap::<HullType, Vec<usize>>::new();
 
         // we trim away "hull_disallowed" equip values that never matter in practice to give nicer outputs
         // otherwise we'd have outputs that state that dive bombers cannot be equipped to frigates. like, duh.
@@ -85,14 +130,36 @@ impl HAzurLane {
             }
         }
 
+        // maps ship IDs to the ships that have couple lines referencing them.
+        // only explicit ship references are indexed, not conditions on hull type, rarity, etc.
+        let mut ship_id_to_encouraging_index = HashMap::<u32, Vec<usize>>::new();
+        fn insert_encouraging(ship_id_to_encouraging_index: &mut HashMap<u32, Vec<usize>>, data: &ShipData, index: usize) {
+            let ship_ids = data.skins.iter()
+                .flat_map(|s| std::iter::once(&s.words).chain(s.words_extra.as_deref()))
+                .flat_map(|w| &w.couple_encourage)
+                .filter_map(|c| match &c.condition { ShipCouple::ShipGroup(ids) => Some(ids), _ => None })
+                .flatten();
+
+            for &ship_id in ship_ids {
+                // skip the ship itself and ones already listed for this ship
+                let list = ship_id_to_encouraging_index.entry(ship_id).or_default();
+                if ship_id != data.group_id && list.last() != Some(&index) {
+                    list.push(index);
+                }
+            }
+        }
+
+        ship_simsearch.extend(data.ships.iter().map(|s| (&s.name, ())));
         for (index, data) in data.ships.iter().enumerate() {
             verify_ship(data);
 
             ship_id_to_index.insert(data.group_id, index);
-            ship_simsearch.insert(&data.name, ());
 
             // collect known "equip & hull" pairs
             insert_equip_exist(&mut actual_equip_exist, data);
+
+            // collect couple line references
+            insert_encouraging(&mut ship_id_to_encouraging_index, data, index);
         }
 
         for (index, data) in data.equips.iter_mut().enumerate() {
@@ -109,37 +176,132 @@ impl HAzurLane {
             data.hull_disallowed.retain(|h| actual_equip_exist.contains(&(data.kind, *h)));
         }
 
+        augment_simsearch.extend(data.augments.iter().map(|a| (&a.name, ())));
         for (index, data) in data.augments.iter().enumerate() {
             augment_id_to_index.insert(data.augment_id, index);
-            augment_simsearch.insert(&data.name, ());
 
             if let Some(ship_id) = data.usability.unique_ship_id() {
                 ship_id_to_augment_index.entry(ship_id)
                     .and_modify(|v| v.push(index))
                     .or_insert(vec![index]);
             }
+
+            for &hull_type in data.usability.hull_types().into_iter().flatten() {
+                hull_type_to_augment_index.entry(hull_type)
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        // weapons are collected from equipment and shadow equipment, deduplicated by their ID.
+        // unnamed weapons are named after the equipment they were first found on.
+        let mut weapons = Vec::new();
+        let mut weapon_id_to_index = HashMap::new();
+        fn insert_weapons(weapons: &mut Vec<Weapon>, weapon_id_to_index: &mut HashMap<u32, usize>, name: &str, source: &[Weapon]) {
+            for weapon in source {
+                if let Entry::Vacant(entry) = weapon_id_to_index.entry(weapon.weapon_id) {
+                    entry.insert(weapons.len());
+
+                    let mut weapon = weapon.clone();
+                    weapon.name.get_or_insert_with(|| name.to_owned());
+                    weapons.push(weapon);
+                }
+            }
+        }
+
+        fn insert_ship_weapons(weapons: &mut Vec<Weapon>, weapon_id_to_index: &mut HashMap<u32, usize>, data: &ShipData) {
+            for equip in &data.shadow_equip {
+                insert_weapons(weapons, weapon_id_to_index, &equip.name, &equip.weapons);
+            }
+
+            for equip in &data.depth_charges {
+                insert_weapons(weapons, weapon_id_to_index, &equip.name, &equip.weapons);
+            }
+
+            for retrofit in &data.retrofits {
+                insert_ship_weapons(weapons, weapon_id_to_index, retrofit);
+            }
+        }
+
+        for data in &data.equips {
+            insert_weapons(&mut weapons, &mut weapon_id_to_index, &data.name, &data.weapons);
+        }
+
+        for data in &data.ships {
+            insert_ship_weapons(&mut weapons, &mut weapon_id_to_index, data);
+        }
+
+        let mut weapon_simsearch = Search::new();
+        for data in &weapons {
+            weapon_simsearch.insert(&format!(
+                "{} {}",
+                data.name.as_deref().unwrap_or_default(),
+                data.kind.name()
+            ), ());
         }
 
         ship_simsearch.shrink_to_fit();
         equip_simsearch.shrink_to_fit();
         augment_simsearch.shrink_to_fit();
+        weapon_simsearch.shrink_to_fit();
+
+        let generation = data_generation(&data);
 
         HAzurLane {
             data_path,
+            image_format: data.image_format,
             ships: data.ships,
             equips: data.equips,
             augments: data.augments,
+            weapons,
             ship_id_to_index,
             ship_simsearch,
             equip_id_to_index,
             equip_simsearch,
             augment_id_to_index,
             augment_simsearch,
+            weapon_id_to_index,
+            weapon_simsearch,
             ship_id_to_augment_index,
-            chibi_sprite_cache: DashMap::new()
+            hull_type_to_augment_index,
+            ship_id_to_encouraging_index,
+            generation,
+            chibi_sprite_cache: DashMap::new(),
+            skill_icon_cache: DashMap::new(),
         }
     }
 
+    /// Loads the definitions for every language other than English that has data in `data_path`.
+    #[must_use]
+    pub fn load_localized(data_path: &Path) -> HashMap<Language, Self> {
+        Self::localized_languages(data_path)
+            .map(|l| (l, Self::load_from(data_path.to_owned(), l)))
+            .collect()
+    }
+
+    /// Loads the data for every language other than English that has a definition file.
+    ///
+    /// Unlike [`Self::load_localized`], this returns an error if any of the files cannot be loaded.
+    pub fn try_load_localized(data_path: &Path) -> anyhow::Result<HashMap<Language, Self>> {
+        Self::localized_languages(data_path)
+            .map(|l| Ok((l, Self::try_load_from(data_path.to_owned(), l)?)))
+            .collect()
+    }
+
+    fn localized_languages(data_path: &Path) -> impl Iterator<Item = Language> + '_ {
+        Language::all()
+            .filter(|&l| l != Language::English)
+            .filter(|l| data_path.join(l.definition_file_name()).is_file())
+    }
+
+    /// Gets the generation of this data.
+    ///
+    /// This is derived from the IDs and names of the contained entries,
+    /// so it stays the same across restarts and changes when the data does.
+    pub fn generation(&self) -> u16 {
+        self.generation
+    }
+
     /// Gets all known ships.
     pub fn ships(&self) -> &[ShipData] {
         &self.ships
@@ -155,6 +317,11 @@ impl HAzurLane {
         &self.augments
     }
 
+    /// Gets all known weapons on equipment and shadow equipment.
+    pub fn weapons(&self) -> &[Weapon] {
+        &self.weapons
+    }
+
     /// Gets a ship by its ID.
     pub fn ship_by_id(&self, id: u32) -> Option<&ShipData> {
         let index = *self.ship_id_to_index.get(&id)?;
@@ -188,46 +355,170 @@ impl HAzurLane {
         self.augment_simsearch.search(prefix).filter_map(|i| self.augments.get(i.index))
     }
 
+    /// Gets a weapon by its ID.
+    pub fn weapon_by_id(&self, id: u32) -> Option<&Weapon> {
+        let index = *self.weapon_id_to_index.get(&id)?;
+        self.weapons.get(index)
+    }
+
+    /// Gets all weapons by a name prefix.
+    pub fn weapons_by_prefix(&self, prefix: &str) -> impl Iterator<Item = &Weapon> {
+        self.weapon_simsearch.search(prefix).filter_map(|i| self.weapons.get(i.index))
+    }
+
     /// Gets unique augments by their associated ship ID.
     pub fn augments_by_ship_id(&self, ship_id: u32) -> impl Iterator<Item = &Augment> {
         self.ship_id_to_augment_index.get(&ship_id).into_iter().flatten().filter_map(|i| self.augments.get(*i))
     }
 
+    /// Gets augments usable by a hull type.
+    pub fn augments_by_hull_type(&self, hull_type: HullType) -> impl Iterator<Item = &Augment> {
+        self.hull_type_to_augment_index.get(&hull_type).into_iter().flatten().filter_map(|i| self.augments.get(*i))
+    }
+
+    /// Gets the ships that have couple lines when sortied with the ship with the given ID.
+    ///
+    /// This only considers lines that reference that ship explicitly.
+    pub fn ships_encouraging(&self, ship_id: u32) -> impl Iterator<Item = &ShipData> {
+        self.ship_id_to_encouraging_index.get(&ship_id).into_iter().flatten().filter_map(|i| self.ships.get(*i))
+    }
+
+    /// Gets the file extension of chibi and skill icon images, without the leading dot.
+    #[must_use]
+    pub fn image_extension(&self) -> &'static str {
+        self.image_format.extension()
+    }
+
     /// Gets a chibi's image data.
+    ///
+    /// Its file extension is [`Self::image_extension`].
     pub fn get_chibi_image(&self, image_key: &str) -> Option<Arc<[u8]>> {
-        // Consult the cache first. If the image has been seen already, it will be stored here.
-        // It may also have a None entry if the image was requested but not found.
-        match self.chibi_sprite_cache.get(image_key) {
-            Some(entry) => Option::clone(&entry),
-            _ => self.load_and_cache_chibi_image(image_key),
-        }
-    }
-
-    #[cold]
-    fn load_and_cache_chibi_image(&self, image_key: &str) -> Option<Arc<[u8]>> {
-        // IMPORTANT: the right-hand side of join may be absolute or relative and can therefore read
-        // files outside of `data_path`. Currently, this doesn't take user-input, but this should
-        // be considered for the future.
-        let path = utils::join_path![&self.data_path, "chibi", image_key; "webp"];
-        match std::fs::read(path) {
-            Ok(data) => {
-                // File read successfully, cache the data.
-                let data = Arc::from(data);
-                self.chibi_sprite_cache.insert(image_key.to_owned(), Some(Arc::clone(&data)));
-                Some(data)
-            },
-            Err(err) => {
-                // Reading failed. Check the error kind.
-                use std::io::ErrorKind::*;
-                match err.kind() {
-                    // Most errors aren't interesting and may be transient issues.
-                    // However, these ones imply permanent problems. Store None to prevent repeated attempts.
-                    NotFound | PermissionDenied => { self.chibi_sprite_cache.insert(image_key.to_owned(), None); },
-                    _ => ()
-                };
-
-                None
-            }
+        get_image(&self.chibi_sprite_cache, &self.data_path, "chibi", image_key, self.image_extension())
+    }
+
+    /// Gets a skill's icon image data.
+    ///
+    /// Its file extension is [`Self::image_extension`].
+    pub fn get_skill_icon(&self, icon_key: &str) -> Option<Arc<[u8]>> {
+        get_image(&self.skill_icon_cache, &self.data_path, "skill_icons", icon_key, self.image_extension())
+    }
+}
+
+/// Gets an image's data from a folder within the data path, using the cache when possible.
+fn get_image(cache: &DashMap<String, Option<Arc<[u8]>>>, data_path: &Path, folder: &str, key: &str, extension: &str) -> Option<Arc<[u8]>> {
+    // Consult the cache first. If the image has been seen already, it will be stored here.
+    // It may also have a None entry if the image was requested but not found.
+    match cache.get(key) {
+        Some(entry) => Option::clone(&entry),
+        _ => load_and_cache_image(cache, data_path, folder, key, extension),
+    }
+}
+
+#[cold]
+fn load_and_cache_image(cache: &DashMap<String, Option<Arc<[u8]>>>, data_path: &Path, folder: &str, key: &str, extension: &str) -> Option<Arc<[u8]>> {
+    // IMPORTANT: the right-hand side of join may be absolute or relative and can therefore read
+    // files outside of `data_path`. Currently, this doesn't take user-input, but this should
+    // be considered for the future.
+    let path = utils::join_path![data_path, folder, key; extension];
+    match std::fs::read(path) {
+        Ok(data) => {
+            // File read successfully, cache the data.
+            let data = Arc::from(data);
+            cache.insert(key.to_owned(), Some(Arc::clone(&data)));
+            Some(data)
+        },
+        Err(err) => {
+            // Reading failed. Check the error kind.
+            use std::io::ErrorKind::*;
+            match err.kind() {
+                // Most errors aren't interesting and may be transient issues.
+                // However, these ones imply permanent problems. Store None to prevent repeated attempts.
+                NotFound | PermissionDenied => { cache.insert(key.to_owned(), None); },
+                _ => ()
+            };
+
+            None
         }
     }
 }
+
+/// Calculates the data generation for [`HAzurLane::generation`].
+///
+/// This uses FNV-1a over explicit bytes, so the same data always has the same generation,
+/// even across restarts and builds.
+#[allow(clippy::cast_possible_truncation)] // intended, this only needs to detect changes
+fn data_generation(data: &azur_lane::DefinitionData) -> u16 {
+    use std::hash::Hasher;
+
+    fn write(hasher: &mut utils::Fnv1aHasher, id: u32, name: &str) {
+        hasher.write(&id.to_le_bytes());
+        hasher.write(name.as_bytes());
+        hasher.write_u8(0xFF);
+    }
+
+    let mut hasher = utils::Fnv1aHasher::new();
+    for ship in &data.ships {
+        write(&mut hasher, ship.group_id, &ship.name);
+    }
+
+    for equip in &data.equips {
+        write(&mut hasher, equip.equip_id, &equip.name);
+    }
+
+    for augment in &data.augments {
+        write(&mut hasher, augment.augment_id, &augment.name);
+    }
+
+    hasher.finish() as u16
+}
+
+#[cfg(test)]
+mod test {
+    use azur_lane::DefinitionData;
+    use azur_lane::test_support;
+
+    use super::*;
+
+    fn augment(augment_id: u32, name: &str) -> Augment {
+        Augment {
+            augment_id,
+            name: name.to_owned(),
+            rarity: AugmentRarity::R,
+            stat_bonuses: Vec::new(),
+            usability: AugmentUsability::HullTypes(Vec::new()),
+            effect: None,
+            skill_upgrade: None,
+        }
+    }
+
+    fn equip(equip_id: u32, name: &str, weapon_ids: &[u32]) -> Equip {
+        let weapons = weapon_ids.iter().map(|&weapon_id| Weapon { weapon_id, ..test_support::weapon() });
+        Equip { equip_id, name: name.to_owned(), weapons: weapons.collect(), ..test_support::equip() }
+    }
+
+    fn generation(augments: Vec<Augment>) -> u16 {
+        HAzurLane::from_definitions(PathBuf::new(), DefinitionData { augments, ..DefinitionData::default() }).generation()
+    }
+
+    #[test]
+    fn generation_tracks_data() {
+        let base = generation(vec![augment(1, "One"), augment(2, "Two")]);
+        assert_eq!(base, generation(vec![augment(1, "One"), augment(2, "Two")]));
+        assert_ne!(base, generation(vec![augment(1, "One"), augment(2, "Second")]));
+        assert_ne!(base, generation(vec![augment(2, "Two"), augment(1, "One")]));
+    }
+
+    #[test]
+    fn weapons_are_deduplicated() {
+        let equips = vec![
+            equip(1, "Gun T1", &[100]),
+            equip(2, "Gun T2", &[100, 200]),
+        ];
+
+        let data = HAzurLane::from_definitions(PathBuf::new(), DefinitionData { equips, ..DefinitionData::default() });
+        assert_eq!(data.weapons().len(), 2);
+        assert_eq!(data.weapon_by_id(100).and_then(|w| w.name.as_deref()), Some("Gun T1"));
+        assert_eq!(data.weapon_by_id(200).and_then(|w| w.name.as_deref()), Some("Gun T2"));
+        assert!(data.weapon_by_id(300).is_none());
+    }
+}
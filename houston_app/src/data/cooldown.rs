@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use serenity::all::UserId;
+
+/// Tracks per-user cooldowns for commands.
+///
+/// Only commands with a configured cooldown are tracked.
+#[derive(Debug, Default)]
+pub struct HCooldowns {
+    /// The cooldown per qualified command name.
+    durations: HashMap<String, Duration>,
+    /// When a user last used a command that has a cooldown.
+    last_use: DashMap<(UserId, String), Instant>,
+}
+
+impl HCooldowns {
+    /// Creates a new instance from cooldowns in seconds, keyed by qualified command name.
+    #[must_use]
+    pub fn new(cooldowns: &HashMap<String, u64>) -> Self {
+        Self {
+            durations: cooldowns.iter()
+                .filter_map(|(name, &secs)| (secs != 0).then(|| (name.clone(), Duration::from_secs(secs))))
+                .collect(),
+            last_use: DashMap::new(),
+        }
+    }
+
+    /// Checks whether the user may use the command at `now`, and if so, records the use.
+    ///
+    /// If the command is still on cooldown, returns the remaining time instead.
+    /// Uses whose cooldown has expired by `now` are forgotten, so the tracking doesn't grow unbounded.
+    pub fn check(&self, user_id: UserId, command: &str, now: Instant) -> Result<(), Duration> {
+        let Some(&duration) = self.durations.get(command) else {
+            return Ok(());
+        };
+
+        self.last_use.retain(|(_, name), last| {
+            self.durations.get(name).is_some_and(|&d| now.saturating_duration_since(*last) < d)
+        });
+
+        match self.last_use.entry((user_id, command.to_owned())) {
+            Entry::Occupied(mut entry) => {
+                let elapsed = now.saturating_duration_since(*entry.get());
+                if elapsed < duration {
+                    return Err(duration - elapsed);
+                }
+
+                entry.insert(now);
+            },
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+            },
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cooldown_bookkeeping() {
+        let cooldowns = HCooldowns::new(&HashMap::from([
+            ("calc".to_owned(), 5),
+            ("dice".to_owned(), 0),
+        ]));
+
+        let user = UserId::new(1);
+        let other = UserId::new(2);
+        let start = Instant::now();
+
+        assert_eq!(cooldowns.check(user, "calc", start), Ok(()));
+        assert_eq!(cooldowns.check(user, "calc", start + Duration::from_secs(2)), Err(Duration::from_secs(3)));
+
+        // other users and commands aren't affected
+        assert_eq!(cooldowns.check(other, "calc", start), Ok(()));
+        assert_eq!(cooldowns.check(user, "dice", start), Ok(()));
+        assert_eq!(cooldowns.check(user, "dice", start), Ok(()));
+        assert_eq!(cooldowns.check(user, "coin", start), Ok(()));
+
+        // usable again once elapsed, which restarts the cooldown
+        assert_eq!(cooldowns.check(user, "calc", start + Duration::from_secs(5)), Ok(()));
+        assert!(cooldowns.check(user, "calc", start + Duration::from_secs(6)).is_err());
+    }
+
+    #[test]
+    fn cooldown_prunes_expired() {
+        let cooldowns = HCooldowns::new(&HashMap::from([
+            ("calc".to_owned(), 5),
+            ("dice".to_owned(), 10),
+        ]));
+
+        let start = Instant::now();
+        for id in 1..=3 {
+            cooldowns.check(UserId::new(id), "calc", start).unwrap();
+        }
+
+        cooldowns.check(UserId::new(1), "dice", start).unwrap();
+        assert_eq!(cooldowns.last_use.len(), 4);
+
+        // only the still active dice cooldown and the new use remain
+        cooldowns.check(UserId::new(4), "calc", start + Duration::from_secs(5)).unwrap();
+        assert_eq!(cooldowns.last_use.len(), 2);
+    }
+}
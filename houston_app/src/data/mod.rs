@@ -1,14 +1,21 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use once_cell::sync::{Lazy, OnceCell};
 use poise::reply::CreateReply;
 use serenity::all::{Color, Http, UserId};
 
+use azur_lane::Language;
+
 mod app_emojis;
 mod azur;
+mod cooldown;
 
+use crate::buttons::azur::ship::ViewAffinity;
 use crate::config::HBotConfig;
+use crate::metrics::HMetrics;
 
 /// A general color that can be used for various embeds.
 pub const DEFAULT_EMBED_COLOR: Color = Color::new(0xDD_A0_DD);
@@ -25,6 +32,20 @@ pub type HResult = Result<(), HError>;
 
 pub use azur::HAzurLane;
 pub use app_emojis::HAppEmojis;
+pub use cooldown::HCooldowns;
+
+/// The Azur Lane game data for every loaded language.
+///
+/// This is swapped as a whole, so all languages always come from the same load.
+#[derive(Debug, Default)]
+struct HAzurLaneStore {
+    /// The English data, which is also the fallback for other languages.
+    english: Arc<HAzurLane>,
+    /// The data for languages other than English.
+    localized: HashMap<Language, Arc<HAzurLane>>,
+    /// The combined generation of all languages.
+    generation: u16,
+}
 
 /// The global bot data. Only one instance exists per bot.
 pub struct HBotData {
@@ -34,14 +55,29 @@ pub struct HBotData {
     app_emojis: OnceCell<app_emojis::HAppEmojiStore>,
     /// A concurrent hash map to user data.
     user_data: DashMap<UserId, HUserData>,
-    /// Lazily initialized Azur Lane data.
-    azur_lane: Lazy<HAzurLane, Box<dyn Send + FnOnce() -> HAzurLane>>,
+    /// Lazily initialized Azur Lane data for every language. Replaced when reloaded.
+    azur_lane: Lazy<ArcSwap<HAzurLaneStore>, Box<dyn Send + FnOnce() -> ArcSwap<HAzurLaneStore>>>,
+    /// Counters for bot activity.
+    metrics: HMetrics,
+    /// Per-user command cooldowns.
+    cooldowns: HCooldowns,
 }
 
 /// User-specific data.
 #[derive(Debug, Clone)]
 pub struct HUserData {
-    pub ephemeral: bool
+    pub ephemeral: bool,
+    pub language: Language,
+    /// The result of the user's last successful `calc` command, usable as `ans`.
+    ///
+    /// Concurrent invocations aren't synchronized; the last one to finish wins.
+    pub last_calc_result: Option<f64>,
+    /// Whether to show ship stats as a compact list that fits narrow screens.
+    pub compact_stats: bool,
+    /// The level that ship stats are shown at by default.
+    pub ship_level: u8,
+    /// The affinity that ship stats are shown at by default.
+    pub ship_affinity: ViewAffinity,
 }
 
 /// A simple error that can return any error message.
@@ -59,6 +95,21 @@ impl std::fmt::Display for HArgError {
     }
 }
 
+/// The message to show users when Discord is rate-limiting requests.
+pub const RATE_LIMITED_MESSAGE: &str = "Discord is rate-limiting, try again shortly.";
+
+/// Whether the error is Discord rejecting a request due to rate-limiting.
+#[must_use]
+pub fn is_rate_limit_error(err: &HError) -> bool {
+    const TOO_MANY_REQUESTS: u16 = 429;
+
+    matches!(
+        err.downcast_ref::<serenity::Error>(),
+        Some(serenity::Error::Http(serenity::http::HttpError::UnsuccessfulRequest(res)))
+            if res.status_code.as_u16() == TOO_MANY_REQUESTS
+    )
+}
+
 impl std::fmt::Debug for HBotData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct(stringify!(HBotData)).finish()
@@ -70,14 +121,20 @@ impl HBotData {
     #[must_use]
     pub fn new(config: HBotConfig) -> Self {
         let data_path = config.azur_lane_data.clone();
+        let cooldowns = HCooldowns::new(&config.cooldowns);
         HBotData {
             config,
             app_emojis: OnceCell::new(),
             user_data: DashMap::new(),
+            metrics: HMetrics::default(),
+            cooldowns,
             azur_lane: Lazy::new(match data_path {
-                Some(data_path) => Box::new(move || HAzurLane::load_from(data_path)),
-                None => Box::new(HAzurLane::default),
-            })
+                Some(data_path) => Box::new(move || ArcSwap::from_pointee(HAzurLaneStore::new(
+                    HAzurLane::load_from(data_path.clone(), Language::English),
+                    HAzurLane::load_localized(&data_path),
+                ))),
+                None => Box::new(ArcSwap::default),
+            }),
         }
     }
 
@@ -91,6 +148,18 @@ impl HBotData {
         &self.config
     }
 
+    /// Gets the counters for bot activity.
+    #[must_use]
+    pub fn metrics(&self) -> &HMetrics {
+        &self.metrics
+    }
+
+    /// Gets the per-user command cooldowns.
+    #[must_use]
+    pub fn cooldowns(&self) -> &HCooldowns {
+        &self.cooldowns
+    }
+
     #[must_use]
     pub fn app_emojis(&self) -> HAppEmojis {
         HAppEmojis(self.app_emojis.get())
@@ -120,16 +189,94 @@ impl HBotData {
     }
 
     /// Gets the Azur Lane game data.
+    ///
+    /// The returned data stays valid even if it is reloaded in the meantime.
+    #[must_use]
+    pub fn azur_lane(&self) -> Arc<HAzurLane> {
+        Arc::clone(&self.azur_lane.load().english)
+    }
+
+    /// Gets the Azur Lane game data in the specified language.
+    ///
+    /// If there is no data for that language, this explicitly falls back to the English data.
+    #[must_use]
+    pub fn azur_lane_in(&self, language: Language) -> Arc<HAzurLane> {
+        let store = self.azur_lane.load();
+        Arc::clone(store.localized.get(&language).unwrap_or(&store.english))
+    }
+
+    /// Whether there is Azur Lane game data in the specified language.
+    ///
+    /// English is assumed to always be available.
     #[must_use]
-    pub fn azur_lane(&self) -> &HAzurLane {
-        &self.azur_lane
+    pub fn has_azur_lane_in(&self, language: Language) -> bool {
+        language == Language::English || self.azur_lane.load().localized.contains_key(&language)
+    }
+
+    /// Gets the generation of the loaded Azur Lane game data.
+    ///
+    /// This covers the data of every loaded language, so a change to any of them changes the generation.
+    ///
+    /// Views that opt into [`ButtonMessage::data_generation`](crate::buttons::ButtonMessage::data_generation)
+    /// are rejected when this doesn't match the generation they were created with.
+    #[must_use]
+    pub fn data_generation(&self) -> u16 {
+        self.azur_lane.load().generation
+    }
+
+    /// Gets the Azur Lane game data, if it has already been loaded.
+    ///
+    /// Unlike [`Self::azur_lane`], this does not force it to load.
+    #[must_use]
+    pub fn azur_lane_if_loaded(&self) -> Option<Arc<HAzurLane>> {
+        Lazy::get(&self.azur_lane).map(|store| Arc::clone(&store.load().english))
+    }
+
+    /// Reloads the Azur Lane game data from disk and swaps it in.
+    ///
+    /// Data previously returned by [`Self::azur_lane`] and [`Self::azur_lane_in`] stays valid,
+    /// but isn't updated. Since [`Self::data_generation`] is derived from the data, views
+    /// created with the old data are detected as stale if the data changed.
+    ///
+    /// Returns `false` if no Azur Lane data is configured.
+    /// If any of the data cannot be loaded, the error is returned and the current data is kept.
+    pub fn reload_azur_lane(&self) -> anyhow::Result<bool> {
+        let Some(data_path) = self.config.azur_lane_data.clone() else {
+            return Ok(false);
+        };
+
+        let localized = HAzurLane::try_load_localized(&data_path)?;
+        let english = HAzurLane::try_load_from(data_path, Language::English)?;
+
+        self.azur_lane.store(Arc::new(HAzurLaneStore::new(english, localized)));
+        Ok(true)
+    }
+}
+
+impl HAzurLaneStore {
+    /// Creates a new store from the loaded data.
+    fn new(english: HAzurLane, localized: HashMap<Language, HAzurLane>) -> Self {
+        // addition doesn't depend on the map's iteration order
+        let generation = localized.values()
+            .fold(english.generation(), |generation, data| generation.wrapping_add(data.generation()));
+
+        Self {
+            english: Arc::new(english),
+            localized: localized.into_iter().map(|(language, data)| (language, Arc::new(data))).collect(),
+            generation,
+        }
     }
 }
 
 impl Default for HUserData {
     fn default() -> Self {
         HUserData {
-            ephemeral: true
+            ephemeral: true,
+            language: Language::English,
+            last_calc_result: None,
+            compact_stats: false,
+            ship_level: crate::buttons::azur::ship::DEFAULT_LEVEL,
+            ship_affinity: crate::buttons::azur::ship::DEFAULT_AFFINITY,
         }
     }
 }
@@ -159,6 +306,10 @@ pub trait HContextExtensions {
     /// Always creates an ephemeral reply.
     #[must_use]
     fn create_ephemeral_reply(&self) -> CreateReply;
+
+    /// Gets the Azur Lane game data in the user's language.
+    #[must_use]
+    fn azur_lane(&self) -> Arc<HAzurLane>;
 }
 
 impl HContextExtensions for HContext<'_> {
@@ -177,4 +328,8 @@ impl HContextExtensions for HContext<'_> {
     fn create_ephemeral_reply(&self) -> CreateReply {
         CreateReply::default().ephemeral(true)
     }
+
+    fn azur_lane(&self) -> Arc<HAzurLane> {
+        self.data().azur_lane_in(self.get_user_data().language)
+    }
 }
@@ -0,0 +1,235 @@
+//! Provides tabular summaries of barrages, i.e. the bullets fired by a weapon.
+
+use azur_lane::equip::*;
+use azur_lane::skill::*;
+use utils::text::InlineStr;
+
+macro_rules! map_sum {
+    ($opt:expr, $($arg:tt)*) => {
+        match $opt {
+            None => None,
+            Some(v) => Some(format!($($arg)*, sum = v))
+        }
+    };
+}
+
+/// Constructs the summary of all barrages of a skill.
+///
+/// Returns an empty string if the skill has no barrages with bullets.
+#[must_use]
+pub fn skill_summary(skill: &Skill) -> String {
+    join("\n\n", skill.barrages.iter().filter_map(skill_barrage_summary)).unwrap_or_default()
+}
+
+/// Constructs the summary of a skill barrage, including the table header.
+///
+/// Returns [`None`] if none of the attacks fire bullets.
+#[must_use]
+pub fn skill_barrage_summary(barrage: &SkillBarrage) -> Option<String> {
+    map_sum!(
+        join("\n", barrage.attacks.iter().filter_map(skill_attack_summary)),
+        "__`Trgt. | Dmg.       | Ammo:  L / M / H  | Scaling  | Fl.`__\n{sum}"
+        // `Fix.  | 12 x  58.0 | Nor.: 120/ 80/ 80 | 100% AVI | ---`
+    )
+}
+
+/// Constructs the summary of a single skill attack.
+///
+/// Returns [`None`] if the attack doesn't fire bullets.
+#[must_use]
+pub fn skill_attack_summary(attack: &SkillAttack) -> Option<String> {
+    match &attack.weapon.data {
+        WeaponData::Bullets(bullets) => barrage_summary(bullets, Some(attack.target)),
+        WeaponData::Aircraft(aircraft) => map_sum!(
+            aircraft_summary(aircraft),
+            "`{: >5} |{: >3} x Aircraft                             |    `\n{sum}",
+            attack.target.short_name(), aircraft.amount
+        ),
+        _ => None
+    }
+}
+
+/// Constructs the summary of a barrage, with one line per distinct kind of bullet.
+///
+/// Returns [`None`] if the barrage has no bullets.
+#[must_use]
+pub fn barrage_summary(barrage: &Barrage, target: Option<SkillAttackTarget>) -> Option<String> {
+    struct Value<'a> { amount: u32, bullet: &'a Bullet }
+
+    fn match_key(a: &Bullet, b: &Bullet) -> bool {
+        a.kind == b.kind &&
+        a.ammo == b.ammo &&
+        a.modifiers == b.modifiers
+    }
+
+    let mut sets: Vec<Value> = Vec::new();
+    for bullet in &barrage.bullets {
+        // find & modify, or insert
+        match sets.iter_mut().find(|i| match_key(i.bullet, bullet)) {
+            Some(entry) => entry.amount += bullet.amount,
+            None => sets.push(Value { amount: bullet.amount, bullet }),
+        }
+    }
+
+    join("\n", sets.into_iter().map(|Value { amount, bullet }| {
+        let ArmorModifiers(l, m, h) = bullet.modifiers;
+        let sprapnel_mark = if bullet.kind == BulletKind::Shrapnel { "*" } else { " " };
+        format!(
+            // damage with coeff |
+            // ammo type & mods |
+            // % of scaling stat |
+            // amount | totals
+            "`\
+            {: <5} |\
+            {: >3} x{: >6.1}{}|\
+            {: >5}: {: >3.0}/{: >3.0}/{: >3.0} |\
+            {: >4.0}% {: <3} | \
+            {}`",
+            target.map(|t| t.short_name()).unwrap_or(""),
+            amount, barrage.damage * barrage.coefficient, sprapnel_mark,
+            bullet.ammo.short_name(), l * 100f64, m * 100f64, h * 100f64,
+            barrage.scaling * 100f64, barrage.scaling_stat.name(),
+            bullet_flags(bullet),
+        )
+    }))
+}
+
+/// Constructs the summary of the barrages fired by an aircraft.
+///
+/// Returns [`None`] if the aircraft has no weapons that fire bullets.
+#[must_use]
+pub fn aircraft_summary(aircraft: &Aircraft) -> Option<String> {
+    join("\n", aircraft.weapons.iter().filter_map(|weapon| match &weapon.data {
+        WeaponData::Bullets(barrage) => barrage_summary(barrage, None),
+        _ => None
+    }))
+}
+
+fn bullet_flags(bullet: &Bullet) -> InlineStr<3> {
+    let mut res = [b'-'; 3];
+    if bullet.pierce != 0 { res[0] = b'P'; }
+    if bullet.flags.contains(BulletFlags::IGNORE_SHIELD) { res[1] = b'I'; }
+    if bullet.flags.dive_filter().is_empty() { res[2] = b'D'; }
+
+    // SAFETY: Always ASCII here.
+    unsafe { InlineStr::from_utf8_unchecked(res) }
+}
+
+fn join(separator: &str, items: impl Iterator<Item = String>) -> Option<String> {
+    let items: Vec<String> = items.collect();
+    (!items.is_empty()).then(|| items.join(separator))
+}
+
+#[cfg(test)]
+mod test {
+    use azur_lane::ship::{ShipStat, StatKind};
+
+    use super::*;
+
+    fn bullet(amount: u32, ammo: AmmoKind, flags: BulletFlags) -> Bullet {
+        Bullet {
+            bullet_id: 1,
+            amount,
+            kind: BulletKind::Cannon,
+            ammo,
+            pierce: 0,
+            velocity: 10.0,
+            modifiers: ArmorModifiers(1.0, 0.75, 0.5),
+            flags,
+            attach_buff: Vec::new(),
+            extra: BulletExtra::None,
+        }
+    }
+
+    fn barrage(bullets: Vec<Bullet>) -> Barrage {
+        Barrage {
+            damage: 10.0,
+            coefficient: 1.0,
+            scaling: 1.0,
+            scaling_stat: StatKind::FP,
+            range: 50.0,
+            firing_angle: 360.0,
+            salvo_time: 0.0,
+            bullets,
+        }
+    }
+
+    fn weapon(data: WeaponData) -> Weapon {
+        Weapon {
+            weapon_id: 1,
+            name: None,
+            reload_time: 1.0,
+            fixed_delay: 0.0,
+            kind: WeaponKind::Special,
+            data,
+        }
+    }
+
+    #[test]
+    fn barrage_merges_bullets() {
+        let barrage = barrage(vec![
+            bullet(3, AmmoKind::Normal, BulletFlags::empty()),
+            bullet(2, AmmoKind::Normal, BulletFlags::empty()),
+            bullet(1, AmmoKind::HE, BulletFlags::IGNORE_DIVE),
+        ]);
+
+        assert_eq!(
+            barrage_summary(&barrage, Some(SkillAttackTarget::Fixed)).as_deref(),
+            Some("`Fix.  |  5 x  10.0 | Nor.: 100/ 75/ 50 | 100% FP  | --D`\n\
+                `Fix.  |  1 x  10.0 |   HE: 100/ 75/ 50 | 100% FP  | ---`")
+        );
+    }
+
+    #[test]
+    fn barrage_empty() {
+        assert_eq!(barrage_summary(&barrage(Vec::new()), None), None);
+    }
+
+    #[test]
+    fn aircraft() {
+        let aircraft = Aircraft {
+            aircraft_id: 1,
+            amount: 2,
+            speed: 40.0,
+            health: ShipStat::new(),
+            dodge_limit: 0,
+            weapons: vec![
+                weapon(WeaponData::Bullets(barrage(vec![bullet(4, AmmoKind::Bomb, BulletFlags::empty())]))),
+            ],
+        };
+
+        assert_eq!(
+            aircraft_summary(&aircraft).as_deref(),
+            Some("`      |  4 x  10.0 | Bomb: 100/ 75/ 50 | 100% FP  | --D`")
+        );
+
+        let attack = SkillAttack {
+            target: SkillAttackTarget::Nearest,
+            weapon: weapon(WeaponData::Aircraft(aircraft)),
+        };
+
+        assert_eq!(
+            skill_attack_summary(&attack).as_deref(),
+            Some("`Near. |  2 x Aircraft                             |    `\n\
+                `      |  4 x  10.0 | Bomb: 100/ 75/ 50 | 100% FP  | --D`")
+        );
+    }
+
+    #[test]
+    fn skill_without_barrages() {
+        let skill = Skill {
+            buff_id: 1,
+            name: "Test".to_owned(),
+            description: String::new(),
+            category: SkillCategory::Offense,
+            barrages: Vec::new(),
+            new_weapons: Vec::new(),
+            icon_key: None,
+            level_descriptions: Vec::new(),
+            cooldown: None,
+            effects: Vec::new(),
+        };
+
+        assert_eq!(skill_summary(&skill), "");
+    }
+}
@@ -3,9 +3,11 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 use azur_lane::equip::*;
 use azur_lane::ship::StatKind;
 
+use crate::fmt::write_join_with;
+
 /// Implements [`Display`] to nicely format a equipment stats.
 #[must_use]
-pub struct EquipStats<'a>(&'a Equip);
+pub struct EquipStats<'a>(&'a [EquipStatBonus]);
 
 /// Implements [`Display`] to nicely format a augment stats.
 #[must_use]
@@ -13,7 +15,12 @@ pub struct AugmentStats<'a>(&'a Augment);
 
 impl<'a> EquipStats<'a> {
     pub fn new(equip: &'a Equip) -> Self {
-        Self(equip)
+        Self(&equip.stat_bonuses)
+    }
+
+    /// Formats specific stat bonuses, such as those of one of [`Equip::tiers`].
+    pub fn from_bonuses(stat_bonuses: &'a [EquipStatBonus]) -> Self {
+        Self(stat_bonuses)
     }
 }
 
@@ -25,7 +32,7 @@ impl<'a> AugmentStats<'a> {
 
 impl Display for EquipStats<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write_stats(&self.0.stat_bonuses, |i| (i.stat_kind, i.amount), f)
+        write_stats(self.0, |i| (i.stat_kind, i.amount), f)
     }
 }
 
@@ -39,19 +46,11 @@ fn write_stats<I, F>(iter: &[I], map: F, f: &mut Formatter<'_>) -> FmtResult
 where
     F: Fn(&I) -> (StatKind, f64),
 {
-    for (index, chunk) in iter.chunks(3).enumerate() {
-        if index != 0 {
-            f.write_str("\n")?;
-        }
-
-        for (index, stat) in chunk.iter().enumerate() {
+    write_join_with(f, iter.chunks(3), "\n", |f, chunk| {
+        write_join_with(f, chunk.iter(), " \u{2E31} ", |f, stat| {
             let (kind, amount) = map(stat);
-            if index != 0 { f.write_str(" \u{2E31} ")?; }
-
             let name = kind.name();
-            write!(f, "**`{}:`**`{: >len$}`", name, amount, len = 7 - name.len())?;
-        }
-    }
-
-    Ok(())
+            write!(f, "**`{}:`**`{: >len$}`", name, amount, len = 7 - name.len())
+        })
+    })
 }
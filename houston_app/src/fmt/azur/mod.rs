@@ -1,3 +1,4 @@
+pub mod barrage;
 mod equip;
 mod weapon;
 
@@ -61,17 +61,10 @@ impl Display for Details<'_> {
 }
 
 fn format_fire_rate(weapon: &Weapon, f: &mut Formatter<'_>) -> FmtResult {
-    let salvo_time = match &weapon.data {
-        WeaponData::Bullets(b) => b.salvo_time,
-        _ => 0.0
-    };
-
-    let reload_time = weapon.reload_time * if weapon.kind == WeaponKind::StrikeAircraft { 2.2 } else { 1.0 };
-    let fixed_delay = weapon.fixed_delay + salvo_time;
     writeln!(
         f,
-        "**FR:** {:.2} +{:.2}s (~{:.1}/min)",
-        reload_time, fixed_delay, 60.0 / (reload_time + fixed_delay)
+        "**FR:** {:.2} +{:.2}s \u{2E31} **Cycle:** {:.2}s (~{:.1}/min)",
+        weapon.effective_reload_time(), weapon.cycle_delay(), weapon.cycle_time(), weapon.fires_per_minute()
     )
 }
 
@@ -2,7 +2,7 @@
 
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
-use serenity::all::{ResolvedOption, ResolvedTarget, ResolvedValue, User};
+use serenity::all::{ResolvedOption, ResolvedTarget, ResolvedValue, Unresolved, User};
 
 /// Gets a unique username for this user.
 ///
@@ -47,23 +47,110 @@ fn fmt_resolved_options(options: &[ResolvedOption], f: &mut Formatter<'_>) -> Fm
 }
 
 fn fmt_resolved_option(option: &ResolvedOption, f: &mut Formatter<'_>) -> FmtResult {
-    match option.value {
+    fmt_resolved_value(&option.value, f)
+}
+
+fn fmt_resolved_value(value: &ResolvedValue, f: &mut Formatter<'_>) -> FmtResult {
+    match value {
         ResolvedValue::Boolean(v) => v.fmt(f),
         ResolvedValue::Integer(v) => v.fmt(f),
         ResolvedValue::Number(v) => v.fmt(f),
         ResolvedValue::String(v) => write!(f, "\"{v}\""),
         ResolvedValue::Attachment(v) => f.write_str(&v.filename),
-        ResolvedValue::Channel(v) => match &v.name { Some(name) => f.write_str(name), None => v.id.fmt(f) },
-        ResolvedValue::Role(v) => f.write_str(&v.name),
-        ResolvedValue::User(v, _) => f.write_str(&v.name),
+        ResolvedValue::Channel(v) => match &v.name { Some(name) => write!(f, "#{name}"), None => write!(f, "#{}", v.id) },
+        ResolvedValue::Role(v) => write!(f, "@{}", v.name),
+        ResolvedValue::User(v, _) => write!(f, "@{}", v.name),
+        // mentionables and options whose data wasn't resolved only provide the ID
+        ResolvedValue::Unresolved(Unresolved::Attachment(id)) => id.fmt(f),
+        ResolvedValue::Unresolved(Unresolved::Channel(id)) => write!(f, "#{id}"),
+        ResolvedValue::Unresolved(Unresolved::Mentionable(id)) => write!(f, "@{id}"),
+        ResolvedValue::Unresolved(Unresolved::RoleId(id)) => write!(f, "@&{id}"),
+        ResolvedValue::Unresolved(Unresolved::User(id)) => write!(f, "@{id}"),
         _ => f.write_str("<unknown>"),
     }
 }
 
 fn fmt_resolved_target(target: &ResolvedTarget, f: &mut Formatter<'_>) -> FmtResult {
     match target {
-        ResolvedTarget::User(v, _) => f.write_str(&v.name),
+        ResolvedTarget::User(v, _) => write!(f, "@{}", v.name),
         ResolvedTarget::Message(v) => v.id.fmt(f),
         _ => f.write_str("<unknown>"),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serenity::all::{Attachment, AttachmentId, ChannelId, GenericId, Role, RoleId, UserId};
+    use serenity::json::{from_value, json};
+
+    use super::*;
+
+    struct DisplayValue<'a>(ResolvedValue<'a>);
+
+    impl Display for DisplayValue<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+            fmt_resolved_value(&self.0, f)
+        }
+    }
+
+    fn fmt_value(value: ResolvedValue<'_>) -> String {
+        DisplayValue(value).to_string()
+    }
+
+    #[test]
+    fn primitives() {
+        assert_eq!(fmt_value(ResolvedValue::Boolean(true)), "true");
+        assert_eq!(fmt_value(ResolvedValue::Integer(-5)), "-5");
+        assert_eq!(fmt_value(ResolvedValue::Number(1.5)), "1.5");
+        assert_eq!(fmt_value(ResolvedValue::String("text")), "\"text\"");
+    }
+
+    #[test]
+    fn user() {
+        let mut user = User::default();
+        user.name = "houston".to_owned();
+
+        assert_eq!(fmt_value(ResolvedValue::User(&user, None)), "@houston");
+    }
+
+    #[test]
+    fn attachment() {
+        let attachment: Attachment = from_value(json!({
+            "id": "1",
+            "filename": "houston.png",
+            "size": 64,
+            "url": "https://cdn.discordapp.com/attachments/1/houston.png",
+            "proxy_url": "https://media.discordapp.net/attachments/1/houston.png",
+        })).unwrap();
+
+        assert_eq!(fmt_value(ResolvedValue::Attachment(&attachment)), "houston.png");
+    }
+
+    #[test]
+    fn role() {
+        let role: Role = from_value(json!({
+            "id": "2",
+            "name": "crew",
+            "color": 0,
+            "hoist": false,
+            "icon": null,
+            "unicode_emoji": null,
+            "managed": false,
+            "mentionable": false,
+            "permissions": "0",
+            "position": 0,
+            "flags": 0,
+        })).unwrap();
+
+        assert_eq!(fmt_value(ResolvedValue::Role(&role)), "@crew");
+    }
+
+    #[test]
+    fn unresolved() {
+        assert_eq!(fmt_value(ResolvedValue::Unresolved(Unresolved::User(UserId::new(12)))), "@12");
+        assert_eq!(fmt_value(ResolvedValue::Unresolved(Unresolved::Mentionable(GenericId::new(34)))), "@34");
+        assert_eq!(fmt_value(ResolvedValue::Unresolved(Unresolved::Channel(ChannelId::new(56)))), "#56");
+        assert_eq!(fmt_value(ResolvedValue::Unresolved(Unresolved::RoleId(RoleId::new(78)))), "@&78");
+        assert_eq!(fmt_value(ResolvedValue::Unresolved(Unresolved::Attachment(AttachmentId::new(90)))), "90");
+    }
+}
@@ -3,19 +3,58 @@ use std::fmt::{Write, Display};
 pub mod azur;
 pub mod discord;
 
-pub fn write_join<W, I>(mut f: W, mut iter: I, join: &str) -> std::fmt::Result
+/// Writes the items of an iterator to `f`, separated by `join`.
+pub fn write_join<W, I>(f: W, iter: I, join: &str) -> std::fmt::Result
 where
     W: Write,
     I: Iterator,
     I::Item: Display,
+{
+    write_join_with(f, iter, join, |f, item| write!(f, "{item}"))
+}
+
+/// Writes the items of an iterator to `f`, separated by `join`.
+///
+/// Unlike [`write_join`], every item is written by calling `fmt_item`.
+pub fn write_join_with<W, I, F>(mut f: W, mut iter: I, join: &str, mut fmt_item: F) -> std::fmt::Result
+where
+    W: Write,
+    I: Iterator,
+    F: FnMut(&mut W, I::Item) -> std::fmt::Result,
 {
     if let Some(item) = iter.next() {
-        write!(f, "{item}")?;
+        fmt_item(&mut f, item)?;
         for item in iter {
             f.write_str(join)?;
-            write!(f, "{item}")?;
+            fmt_item(&mut f, item)?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn join() {
+        let mut f = String::new();
+        write_join(&mut f, [1, 2, 3].into_iter(), ", ").unwrap();
+        assert_eq!(f, "1, 2, 3");
+    }
+
+    #[test]
+    fn join_with() {
+        let mut f = String::new();
+        write_join_with(&mut f, ["a", "b"].into_iter(), " | ", |f, item| write!(f, "**{item}**")).unwrap();
+        assert_eq!(f, "**a** | **b**");
+    }
+
+    #[test]
+    fn join_with_empty() {
+        let mut f = String::new();
+        write_join_with(&mut f, std::iter::empty::<u32>(), ", ", |_, _| unreachable!()).unwrap();
+        assert_eq!(f, "");
+    }
+}
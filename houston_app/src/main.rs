@@ -1,5 +1,6 @@
 use std::num::NonZero;
 use std::sync::Arc;
+use std::time::Duration;
 
 use serenity::model::prelude::*;
 use serenity::prelude::*;
@@ -9,6 +10,7 @@ mod slashies;
 mod config;
 mod data;
 mod fmt;
+mod metrics;
 mod prelude;
 mod poise_command_builder;
 
@@ -20,8 +22,7 @@ const INTENTS: GatewayIntents = GatewayIntents::empty();
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // SAFETY: No other code running that accesses this yet.
-    unsafe { utils::time::mark_startup_time(); }
+    utils::time::init_startup_time();
 
     let config = build_config()?;
     init_logging(config.log);
@@ -34,17 +35,28 @@ async fn main() -> anyhow::Result<()> {
         load_azur_lane(Arc::clone(&bot_data))
     );
 
+    if let Some(addr) = bot_data.config().metrics_addr {
+        tokio::task::spawn(
+            metrics::serve(addr, Arc::clone(&bot_data))
+        );
+    }
+
     let framework = HFramework::builder()
         .options(poise::FrameworkOptions {
             commands: slashies::get_commands(bot_data.config()),
+            command_check: Some(|ctx| Box::pin(slashies::command_check(ctx))),
             pre_command: |ctx| Box::pin(slashies::pre_command(ctx)),
+            post_command: |ctx| Box::pin(slashies::post_command(ctx)),
             on_error: |err| Box::pin(slashies::error_handler(err)),
+            owners: bot_data.config().owners.clone(),
+            // also adds the application's owners to the set above
+            initialize_owners: true,
             ..Default::default()
         })
         .setup({
             let bot_data = Arc::clone(&bot_data);
             move |ctx, ready, framework| Box::pin(async move {
-                create_commands(ctx, framework).await?;
+                create_commands(ctx, framework, &bot_data.config().command_registration).await?;
                 bot_data.load_app_emojis(ctx.http()).await?;
 
                 let discriminator = ready.user.discriminator.map_or(0u16, NonZero::get);
@@ -60,20 +72,43 @@ async fn main() -> anyhow::Result<()> {
         .event_handler(buttons::ButtonEventHandler::new(bot_data))
         .await?;
 
-    client.start().await?;
+    let shard_manager = Arc::clone(&client.shard_manager);
+    tokio::select! {
+        result = client.start() => result?,
+        result = tokio::signal::ctrl_c() => {
+            result?;
+            log::info!("Shutting down...");
+            shard_manager.shutdown_all().await;
+        },
+    }
+
+    log::trace!("Waiting for data loader to finish.");
     loader.await?;
 
+    log::info!("Shut down.");
     Ok(())
 }
 
-async fn create_commands(ctx: &Context, framework: &HFramework) -> HResult {
+async fn create_commands(ctx: &Context, framework: &HFramework, retry: &config::HRetryConfig) -> HResult {
     let cmds = poise_command_builder::build_commands(&framework.options().commands);
-    if let Err(err) = ctx.http().create_global_commands(&cmds).await {
-        log::error!("{err:?}");
-        return Err(err.into());
-    }
 
-    Ok(())
+    let mut backoff = Duration::from_millis(retry.backoff_ms);
+    let mut attempt = 1u32;
+    loop {
+        match ctx.http().create_global_commands(&cmds).await {
+            Ok(_) => return Ok(()),
+            Err(err) if attempt < retry.attempts => {
+                log::warn!("Failed to create commands (attempt {attempt}/{}): {err:?}", retry.attempts);
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+                attempt += 1;
+            },
+            Err(err) => {
+                log::error!("{err:?}");
+                return Err(err.into());
+            },
+        }
+    }
 }
 
 async fn load_azur_lane(bot_data: Arc<HBotData>) {
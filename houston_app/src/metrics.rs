@@ -0,0 +1,149 @@
+//! Provides simple counters and a minimal HTTP endpoint that serves them
+//! in the Prometheus text exposition format.
+//!
+//! The exposed metrics are:
+//! - `houston_commands_total`: The amount of slash and context menu commands invoked.
+//! - `houston_command_errors_total`: The amount of commands that failed.
+//! - `houston_buttons_total`: The amount of component interactions handled.
+//! - `houston_button_errors_total`: The amount of component interactions that failed.
+//! - `houston_uptime_seconds`: The time since the bot started.
+
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use utils::Discard;
+
+use crate::data::HBotData;
+
+/// Counters for bot activity.
+#[derive(Debug, Default)]
+pub struct HMetrics {
+    commands: AtomicU64,
+    command_errors: AtomicU64,
+    buttons: AtomicU64,
+    button_errors: AtomicU64,
+}
+
+impl HMetrics {
+    /// Counts an invoked command.
+    pub fn inc_commands(&self) {
+        self.commands.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a failed command.
+    pub fn inc_command_errors(&self) {
+        self.command_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a handled component interaction.
+    pub fn inc_buttons(&self) {
+        self.buttons.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a failed component interaction.
+    pub fn inc_button_errors(&self) {
+        self.button_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Formats the metrics in the Prometheus text exposition format.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        fn counter(f: &mut String, name: &str, help: &str, value: &AtomicU64) {
+            write!(
+                f,
+                "# HELP {name} {help}\n\
+                # TYPE {name} counter\n\
+                {name} {}\n",
+                value.load(Ordering::Relaxed),
+            ).discard();
+        }
+
+        let mut f = String::new();
+        counter(&mut f, "houston_commands_total", "Commands invoked.", &self.commands);
+        counter(&mut f, "houston_command_errors_total", "Commands that failed.", &self.command_errors);
+        counter(&mut f, "houston_buttons_total", "Component interactions handled.", &self.buttons);
+        counter(&mut f, "houston_button_errors_total", "Component interactions that failed.", &self.button_errors);
+
+        let uptime = utils::time::uptime().unwrap_or_default();
+        write!(
+            f,
+            "# HELP houston_uptime_seconds Time since the bot started.\n\
+            # TYPE houston_uptime_seconds gauge\n\
+            houston_uptime_seconds {}\n",
+            uptime.as_secs(),
+        ).discard();
+
+        f
+    }
+}
+
+/// Serves the metrics on the specified address.
+///
+/// Every request is answered with the metrics, regardless of its path.
+pub async fn serve(addr: SocketAddr, bot_data: Arc<HBotData>) {
+    let listener = match TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("Failed to bind metrics endpoint to {addr}: {err}");
+            return;
+        },
+    };
+
+    log::info!("Serving metrics on {addr}.");
+
+    loop {
+        let mut stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                log::warn!("Failed to accept metrics connection: {err}");
+                continue;
+            },
+        };
+
+        let bot_data = Arc::clone(&bot_data);
+        tokio::spawn(async move {
+            // the request itself is irrelevant, but read it so the client doesn't see a reset
+            let mut buf = [0u8; 1024];
+            _ = stream.read(&mut buf).await;
+
+            let body = bot_data.metrics().to_text();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\n\
+                Content-Type: text/plain; version=0.0.4\r\n\
+                Content-Length: {}\r\n\
+                Connection: close\r\n\
+                \r\n\
+                {body}",
+                body.len(),
+            );
+
+            if let Err(err) = stream.write_all(response.as_bytes()).await {
+                log::trace!("Failed to write metrics response: {err}");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_text() {
+        let metrics = HMetrics::default();
+        metrics.inc_commands();
+        metrics.inc_commands();
+        metrics.inc_button_errors();
+
+        let text = metrics.to_text();
+        assert!(text.contains("# TYPE houston_commands_total counter\nhouston_commands_total 2\n"));
+        assert!(text.contains("\nhouston_command_errors_total 0\n"));
+        assert!(text.contains("\nhouston_buttons_total 0\n"));
+        assert!(text.contains("\nhouston_button_errors_total 1\n"));
+        assert!(text.contains("# TYPE houston_uptime_seconds gauge\n"));
+    }
+}
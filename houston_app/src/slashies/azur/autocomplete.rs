@@ -1,13 +1,19 @@
-use serenity::all::AutocompleteChoice;
+use poise::ChoiceParameter;
+use serenity::all::{AutocompleteChoice, CommandDataOption, CommandDataOptionValue};
 
-use crate::data::HContext;
+use azur_lane::ship::HullType;
+
+use crate::data::{HContext, HContextExtensions};
+use super::choices::EHullType;
 
 macro_rules! make_autocomplete {
     ($fn_name:ident, $by_prefix:ident, $id:ident) => {
-        pub async fn $fn_name<'a>(ctx: HContext<'a>, partial: &'a str) -> impl Iterator<Item = AutocompleteChoice> + 'a {
-            ctx.data().azur_lane()
+        pub async fn $fn_name<'a>(ctx: HContext<'a>, partial: &'a str) -> impl Iterator<Item = AutocompleteChoice> {
+            ctx.azur_lane()
                 .$by_prefix(partial)
                 .map(|e| AutocompleteChoice::new(e.name.as_str(), format!("/id:{}", e.$id)))
+                .collect::<Vec<_>>()
+                .into_iter()
         }
     };
 }
@@ -15,3 +21,37 @@ macro_rules! make_autocomplete {
 make_autocomplete!(ship_name, ships_by_prefix, group_id);
 make_autocomplete!(equip_name, equips_by_prefix, equip_id);
 make_autocomplete!(augment_name, augments_by_prefix, augment_id);
+
+/// Autocompletes augment names for `search-augment`.
+///
+/// Unlike [`augment_name`], this completes to the name itself.
+/// If a hull type was already entered, only augments usable by it are suggested.
+pub async fn augment_search_name<'a>(ctx: HContext<'a>, partial: &'a str) -> impl Iterator<Item = AutocompleteChoice> {
+    let hull_type = entered_hull_type(ctx);
+    ctx.azur_lane()
+        .augments_by_prefix(partial)
+        .filter(move |e| hull_type.map_or(true, |f| e.usability.hull_types().is_some_and(|h| h.contains(&f))))
+        .map(|e| AutocompleteChoice::new(e.name.as_str(), e.name.as_str()))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Gets the `hull_type` option if it was already entered.
+fn entered_hull_type(ctx: HContext<'_>) -> Option<HullType> {
+    let poise::Context::Application(ctx) = ctx else { return None };
+    let index = find_option(&ctx.interaction.data.options, "hull_type")?.as_i64()?;
+    EHullType::from_index(usize::try_from(index).ok()?).map(EHullType::convert)
+}
+
+/// Finds an option of the invoked (sub)command by its name.
+fn find_option<'a>(options: &'a [CommandDataOption], name: &str) -> Option<&'a CommandDataOptionValue> {
+    for option in options {
+        match &option.value {
+            CommandDataOptionValue::SubCommand(nested) | CommandDataOptionValue::SubCommandGroup(nested) => return find_option(nested, name),
+            value if option.name == name => return Some(value),
+            _ => {},
+        }
+    }
+
+    None
+}
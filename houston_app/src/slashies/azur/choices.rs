@@ -1,7 +1,7 @@
 use poise::ChoiceParameter;
 
-use azur_lane::ship::{HullType, ShipRarity};
-use azur_lane::equip::{EquipKind, EquipRarity, AugmentRarity};
+use azur_lane::ship::{EnhanceKind, HullType, LineKind, ShipRarity, StatKind};
+use azur_lane::equip::{EquipKind, EquipRarity, AugmentRarity, WeaponKind};
 use azur_lane::Faction;
 
 macro_rules! make_choice {
@@ -78,6 +78,40 @@ make_choice!(EShipRarity for ShipRarity {
     N, R, E, SR, UR,
 });
 
+make_choice!(EEnhanceKind for EnhanceKind {
+    Normal,
+    Research,
+    META,
+});
+
+make_choice!(ELineKind for LineKind {
+    Description,
+    Profile,
+    Acquisition,
+    Login,
+    Details,
+    Touch,
+    #[name = "Special Touch"] SpecialTouch,
+    Rub,
+    #[name = "Mission Reminder"] MissionReminder,
+    #[name = "Mission Complete"] MissionComplete,
+    #[name = "Mail Reminder"] MailReminder,
+    #[name = "Return to Port"] ReturnToPort,
+    #[name = "Commission Complete"] CommissionComplete,
+    Enhance,
+    #[name = "Flagship Fight"] FlagshipFight,
+    Victory,
+    Defeat,
+    Skill,
+    #[name = "Low Health"] LowHealth,
+    Disappointed,
+    Stranger,
+    Friendly,
+    Crush,
+    Love,
+    Oath,
+});
+
 make_choice!(EEquipKind for EquipKind {
     #[name = "DD Gun"] DestroyerGun,
     #[name = "CL Gun"] LightCruiserGun,
@@ -114,3 +148,22 @@ make_choice!(EAugmentRarity for AugmentRarity {
     #[name = "3* Elite"] E,
     #[name = "4* SR"] SR,
 });
+
+make_choice!(EStatKind for StatKind {
+    HP, FP, TRP, AA, AVI, RLD, EVA, ACC, ASW, SPD, LCK,
+});
+
+make_choice!(EWeaponKind for WeaponKind {
+    #[name = "Main Gun"] MainGun,
+    #[name = "Auto Gun"] SubGun,
+    #[name = "Torpedo"] Torpedo,
+    #[name = "Torpedo (Manual)"] ManualTorpedo,
+    #[name = "Anti-Air"] AntiAir,
+    #[name = "Anti-Air (Fuze)"] AntiAirFuze,
+    #[name = "Aircraft"] StrikeAircraft,
+    #[name = "Aircraft (Anti-Sub)"] AntiSub,
+    #[name = "Main Gun (Bracketing)"] Bracketing,
+    #[name = "Depth Charge"] DepthCharge,
+    #[name = "Missile (Auto)"] AutoMissile,
+    #[name = "Missile (Manual)"] ManualMissile,
+});
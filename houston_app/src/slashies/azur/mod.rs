@@ -1,3 +1,7 @@
+use std::fmt::Write;
+
+use utils::Discard;
+
 use crate::prelude::*;
 use crate::buttons;
 
@@ -11,9 +15,10 @@ use choices::*;
 #[poise::command(
     slash_command,
     subcommands(
-        "ship", "search_ship",
+        "ship", "search_ship", "loadout", "line",
         "equip", "search_equip",
         "augment", "search_augment",
+        "search_weapon",
         "reload_time",
     ),
     subcommand_required
@@ -30,10 +35,50 @@ async fn ship(
     #[autocomplete = "autocomplete::ship_name"]
     name: String
 ) -> HResult {
-    let ship = find::ship(&ctx, &name)?;
+    let azur_lane = ctx.azur_lane();
+    let ship = find::ship(&azur_lane, &name)?;
 
-    let view = buttons::azur::ship::View::new(ship.group_id);
-    ctx.send(view.modify_with_ship(ctx.data(), ctx.create_reply(), ship, None)).await?;
+    let view = buttons::azur::ship::View::with_user_data(ship.group_id, &ctx.get_user_data());
+    ctx.send(view.modify_with_ship(ctx.data(), &azur_lane, ctx.create_reply(), ship, None)).await?;
+    Ok(())
+}
+
+/// Shows a specific line of a ship.
+#[poise::command(slash_command)]
+async fn line(
+    ctx: HContext<'_>,
+    #[description = "The ship's name. This supports auto completion."]
+    #[autocomplete = "autocomplete::ship_name"]
+    name: String,
+    #[description = "The kind of line to show."]
+    kind: ELineKind,
+    #[description = "Whether to show the EX line, if the skin has one. Defaults to false."]
+    extra: Option<bool>,
+) -> HResult {
+    let azur_lane = ctx.azur_lane();
+    let ship = find::ship(&azur_lane, &name)?;
+    let kind = kind.convert();
+
+    let skin = ship.skin_by_id(ship.default_skin_id)
+        .ok_or(HArgError("This ship has no skin data."))?;
+
+    let words = match (extra, &skin.words_extra) {
+        (Some(true), Some(words)) => words.as_ref(),
+        (Some(true), None) => return Err(HArgError("This ship has no EX lines.").into()),
+        _ => &skin.words,
+    };
+
+    let embed = match words.line(kind) {
+        Some(line) => CreateEmbed::new()
+            .color(ship.rarity.color_rgb())
+            .author(CreateEmbedAuthor::new(&ship.name))
+            .description(format!("- **{}:** {}", kind.name(), utils::text::escape_markdown(line))),
+        None => CreateEmbed::new()
+            .color(ERROR_EMBED_COLOR)
+            .description(format!("**{}** has no {} line.", ship.name, kind.name())),
+    };
+
+    ctx.send(ctx.create_reply().embed(embed)).await?;
     Ok(())
 }
 
@@ -52,7 +97,13 @@ async fn search_ship(
     rarity: Option<EShipRarity>,
     #[description = "Whether the ships have a unique augment."]
     #[rename = "has-augment"]
-    has_augment: Option<bool>
+    has_augment: Option<bool>,
+    #[description = "Whether the ships have a retrofit."]
+    #[rename = "has-retrofit"]
+    has_retrofit: Option<bool>,
+    #[description = "How the ships are enhanced, i.e. normal, research, or META."]
+    #[rename = "enhance-kind"]
+    enhance_kind: Option<EEnhanceKind>,
 ) -> HResult {
     use crate::buttons::azur::search_ship::*;
 
@@ -61,15 +112,33 @@ async fn search_ship(
         faction: faction.map(EFaction::convert),
         hull_type: hull_type.map(EHullType::convert),
         rarity: rarity.map(EShipRarity::convert),
-        has_augment
+        has_augment,
+        has_retrofit,
+        enhance_kind: enhance_kind.map(EEnhanceKind::convert),
     };
 
     let view = View::new(filter);
-    ctx.send(view.modify(ctx.data(), ctx.create_reply())).await?;
+    ctx.send(view.modify(ctx.data(), &ctx.get_user_data(), ctx.create_reply())).await?;
 
     Ok(())
 }
 
+/// Builds an equipment loadout for a ship.
+#[poise::command(slash_command)]
+async fn loadout(
+    ctx: HContext<'_>,
+    #[description = "The ship's name. This supports auto completion."]
+    #[autocomplete = "autocomplete::ship_name"]
+    name: String
+) -> HResult {
+    let azur_lane = ctx.azur_lane();
+    let ship = find::ship(&azur_lane, &name)?;
+
+    let view = buttons::azur::loadout::View::new(ship.group_id, None);
+    ctx.send(view.modify_with_ship(&azur_lane, ctx.create_reply(), ship, None)).await?;
+    Ok(())
+}
+
 /// Shows information about equipment.
 #[poise::command(slash_command)]
 async fn equip(
@@ -78,7 +147,8 @@ async fn equip(
     #[autocomplete = "autocomplete::equip_name"]
     name: String
 ) -> HResult {
-    let equip = find::equip(&ctx, &name)?;
+    let azur_lane = ctx.azur_lane();
+    let equip = find::equip(&azur_lane, &name)?;
 
     let view = buttons::azur::equip::View::new(equip.equip_id);
     ctx.send(view.modify_with_equip(ctx.create_reply(), equip)).await?;
@@ -96,7 +166,9 @@ async fn search_equip(
     #[description = "The kind to select."]
     kind: Option<EEquipKind>,
     #[description = "The rarity to select."]
-    rarity: Option<EEquipRarity>
+    rarity: Option<EEquipRarity>,
+    #[description = "The stat that the equipment must provide a bonus to."]
+    stat: Option<EStatKind>
 ) -> HResult {
     use crate::buttons::azur::search_equip::*;
 
@@ -105,10 +177,11 @@ async fn search_equip(
         faction: faction.map(EFaction::convert),
         kind: kind.map(EEquipKind::convert),
         rarity: rarity.map(EEquipRarity::convert),
+        stat: stat.map(EStatKind::convert),
     };
 
     let view = View::new(filter);
-    ctx.send(view.modify(ctx.data(), ctx.create_reply())).await?;
+    ctx.send(view.modify(ctx.data(), &ctx.get_user_data(), ctx.create_reply())).await?;
 
     Ok(())
 }
@@ -121,10 +194,11 @@ async fn augment(
     #[autocomplete = "autocomplete::augment_name"]
     name: String
 ) -> HResult {
-    let augment = find::augment(&ctx, &name)?;
+    let azur_lane = ctx.azur_lane();
+    let augment = find::augment(&azur_lane, &name)?;
 
     let view = buttons::azur::augment::View::new(augment.augment_id);
-    ctx.send(view.modify_with_augment(ctx.data(), ctx.create_reply(), augment)).await?;
+    ctx.send(view.modify_with_augment(&azur_lane, &ctx.get_user_data(), ctx.create_reply(), augment)).await?;
     Ok(())
 }
 
@@ -133,6 +207,7 @@ async fn augment(
 async fn search_augment(
     ctx: HContext<'_>,
     #[description = "A name to search for."]
+    #[autocomplete = "autocomplete::augment_search_name"]
     name: Option<String>,
     #[description = "The allowed hull type."]
     hull_type: Option<EHullType>,
@@ -146,7 +221,7 @@ async fn search_augment(
     use crate::buttons::azur::search_augment::*;
 
     let unique_ship_id = match for_ship {
-        Some(for_ship) => Some(find::ship(&ctx, &for_ship)?.group_id),
+        Some(for_ship) => Some(find::ship(&ctx.azur_lane(), &for_ship)?.group_id),
         None => None,
     };
 
@@ -158,7 +233,29 @@ async fn search_augment(
     };
 
     let view = View::new(filter);
-    ctx.send(view.modify(ctx.data(), ctx.create_reply())).await?;
+    ctx.send(view.modify(ctx.data(), &ctx.get_user_data(), ctx.create_reply())).await?;
+
+    Ok(())
+}
+
+/// Searches for weapons on equipment and shadow equipment.
+#[poise::command(slash_command, rename = "search-weapon")]
+async fn search_weapon(
+    ctx: HContext<'_>,
+    #[description = "A name to search for."]
+    name: Option<String>,
+    #[description = "The kind to select."]
+    kind: Option<EWeaponKind>,
+) -> HResult {
+    use crate::buttons::azur::search_weapon::*;
+
+    let filter = Filter {
+        name,
+        kind: kind.map(EWeaponKind::convert),
+    };
+
+    let view = View::new(filter);
+    ctx.send(view.modify(ctx.data(), &ctx.get_user_data(), ctx.create_reply())).await?;
 
     Ok(())
 }
@@ -174,14 +271,46 @@ async fn reload_time(
     #[min = 0.0] #[max = 60.0]
     #[rename = "weapon-fr"]
     weapon_reload: f64,
+    #[description = "Whether the weapon starts preloaded. Shows the timing of the first salvos."]
+    preloaded: Option<bool>,
+    #[description = "A ship to take the preload from. Requires the slot. Shows the timing of the first salvos."]
+    #[autocomplete = "autocomplete::ship_name"]
+    ship: Option<String>,
+    #[description = "The ship's equipment slot the weapon is in."]
+    #[min = 1] #[max = 5]
+    slot: Option<u8>,
 ) -> HResult {
     let reload_time = (200.0 / (100.0 + rld)).sqrt() * weapon_reload;
 
-    let description = format!(
+    let mut description = format!(
         "-# **Base Weapon FR:** {weapon_reload:.2}s \u{2E31} **`RLD:`**`{rld: >4}`\n\
          **Final FR:** {reload_time:.2}s"
     );
 
+    let preloaded = match (preloaded, ship) {
+        (Some(preloaded), _) => Some(preloaded),
+        (None, Some(ship)) => {
+            let azur_lane = ctx.azur_lane();
+            let ship = find::ship(&azur_lane, &ship)?;
+            let slot = slot.ok_or(HArgError("Specify the slot to take the preload from."))?;
+            Some(slot_preloaded(ship, slot).ok_or(HArgError("The ship has no weapon in that slot."))?)
+        },
+        (None, None) => None,
+    };
+
+    if let Some(preloaded) = preloaded {
+        let timeline = salvo_timeline(reload_time, preloaded, TIMELINE_SALVOS);
+
+        description.push_str("\n\n**Salvos:**");
+        for (index, time) in timeline.iter().enumerate() {
+            write!(description, "\n{}. {time:.2}s", index + 1).discard();
+        }
+
+        if preloaded {
+            description.push_str("\n-# Preloaded");
+        }
+    }
+
     let embed = CreateEmbed::new()
         .color(DEFAULT_EMBED_COLOR)
         .description(description);
@@ -189,3 +318,76 @@ async fn reload_time(
     ctx.send(ctx.create_reply().embed(embed)).await?;
     Ok(())
 }
+
+/// The amount of salvos shown in the `reload-time` timeline.
+const TIMELINE_SALVOS: usize = 5;
+
+/// Whether the weapon in a ship's equipment slot starts preloaded.
+///
+/// The slot is 1-based, like in game. Returns [`None`] if there is no weapon slot with that number.
+fn slot_preloaded(ship: &azur_lane::ship::ShipData, slot: u8) -> Option<bool> {
+    let index = usize::from(slot).checked_sub(1)?;
+    let mount = ship.equip_slots.get(index)?.mount.as_ref()?;
+    Some(mount.preload != 0)
+}
+
+/// Calculates the times in seconds at which the first `count` salvos fire.
+///
+/// A preloaded weapon fires its first salvo immediately.
+/// Every other salvo waits for a full reload after the previous one.
+fn salvo_timeline(reload_time: f64, preloaded: bool, count: usize) -> Vec<f64> {
+    let first = if preloaded { 0.0 } else { reload_time };
+    (0..count)
+        .map(|index| first + index as f64 * reload_time)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use azur_lane::ship::*;
+    use azur_lane::test_support;
+
+    use super::*;
+
+    /// Equipment slots laid out like a destroyer's in the game data,
+    /// where only the torpedo slot has a preload count.
+    fn destroyer() -> ShipData {
+        let mount = |preload| Some(EquipWeaponMount { efficiency: 1.0, mounts: 1, parallel: 1, preload });
+        let slot = |allowed, mount| EquipSlot { allowed: vec![allowed], mount };
+
+        ShipData {
+            equip_slots: vec![
+                slot(EquipKind::DestroyerGun, mount(0)),
+                slot(EquipKind::SurfaceTorpedo, mount(1)),
+                slot(EquipKind::AntiAirGun, mount(0)),
+                slot(EquipKind::Auxiliary, None),
+                slot(EquipKind::Auxiliary, None),
+            ],
+            ..test_support::ship()
+        }
+    }
+
+    #[test]
+    fn salvo_timeline_no_preload() {
+        assert_eq!(salvo_timeline(10.0, false, 3), [10.0, 20.0, 30.0]);
+    }
+
+    #[test]
+    fn salvo_timeline_preload() {
+        assert_eq!(salvo_timeline(10.0, true, 4), [0.0, 10.0, 20.0, 30.0]);
+        assert!(salvo_timeline(10.0, true, 0).is_empty());
+    }
+
+    #[test]
+    fn slot_preload_from_mount() {
+        let ship = destroyer();
+        assert_eq!(slot_preloaded(&ship, 1), Some(false));
+        assert_eq!(slot_preloaded(&ship, 2), Some(true));
+        assert_eq!(slot_preloaded(&ship, 3), Some(false));
+
+        // auxiliary slots and slots past the end have no weapon
+        assert_eq!(slot_preloaded(&ship, 4), None);
+        assert_eq!(slot_preloaded(&ship, 6), None);
+        assert_eq!(slot_preloaded(&ship, 0), None);
+    }
+}
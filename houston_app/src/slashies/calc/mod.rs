@@ -10,6 +10,8 @@ mod parse;
 pub async fn calc(
     ctx: HContext<'_>,
     mut expression: String,
+    #[description = "Show the result as a percentage."]
+    percent: Option<bool>,
 ) -> HResult {
     expression.make_ascii_lowercase();
 
@@ -21,10 +23,26 @@ pub async fn calc(
         };
     }
 
-    let embed = match eval_text(&expression) {
-        Ok(result) => CreateEmbed::new()
-            .description(format!("{expression} = **{result}**"))
-            .color(DEFAULT_EMBED_COLOR),
+    let env = Env { ans: ctx.get_user_data().last_calc_result };
+    let embed = match eval_text(&expression, &env) {
+        Ok(result) if !result.is_finite()
+            => error_embed!("{}", non_finite_message(result)),
+
+        Ok(result) => {
+            let mut data = ctx.get_user_data();
+            data.last_calc_result = Some(result);
+            ctx.set_user_data(data);
+
+            let result = if percent.unwrap_or(false) {
+                format!("{}%", format_result(result * 100.0))
+            } else {
+                format_result(result)
+            };
+
+            CreateEmbed::new()
+                .description(format!("{} = **{result}**", utils::text::as_code(&expression)))
+                .color(DEFAULT_EMBED_COLOR)
+        }
 
         Err(MathError::ExprExpected(Some(at)))
             => error_embed!("Expected expression at `{at}`.{}", at.error_fmt()),
@@ -52,6 +70,12 @@ pub async fn calc(
 
         Err(MathError::FunctionCallExpected(function))
             => error_embed!("`{function}` is a function and requires `(...)` after it.{}", function.error_fmt()),
+
+        Err(MathError::IntegerExpected(op))
+            => error_embed!("`{op}` requires integer operands in range.{}", op.error_fmt()),
+
+        Err(MathError::NoPreviousResult(ans))
+            => error_embed!("`{ans}` refers to your previous result, but there is none.{}", ans.error_fmt()),
     };
 
     ctx.send(ctx.create_reply().embed(embed)).await?;
@@ -89,24 +113,89 @@ enum MathError<'a> {
 
     /// Expected a function call.
     FunctionCallExpected(Token<'a>),
+
+    /// A bitwise operator was applied to a value that isn't an integer or is out of range.
+    /// Holds the operator token.
+    IntegerExpected(Token<'a>),
+
+    /// Referred to the previous result, but there is none.
+    /// Holds the token in question.
+    NoPreviousResult(Token<'a>),
+}
+
+/// Values provided to the evaluation from outside the expression.
+#[derive(Debug, Default, Clone)]
+struct Env {
+    /// The previous result, referred to as `ans`.
+    ans: Option<f64>,
 }
 
 /// Fully evaluates an equation text.
-fn eval_text(text: &str) -> Result<f64> {
+fn eval_text<'a>(text: &'a str, env: &Env) -> Result<'a, f64> {
     let mut tokens = parse::tokenize(text);
-    parse::read_expr(&mut tokens)
+    parse::read_expr(&mut tokens, env)
+}
+
+/// Gets a friendly message explaining a non-finite result.
+fn non_finite_message(value: f64) -> &'static str {
+    if value.is_nan() {
+        "The result is undefined. Does it divide `0` by `0`, or use a value outside a function's domain, like `sqrt(-1)`?"
+    } else {
+        "The result is infinite or too large. Does it divide by `0`?"
+    }
+}
+
+/// Non-zero values with a magnitude below this are shown in scientific notation.
+const SCIENTIFIC_BELOW: f64 = 1e-6;
+/// Values with a magnitude at or above this are shown in scientific notation.
+const SCIENTIFIC_FROM: f64 = 1e15;
+/// The amount of significant digits to show.
+const SIGNIFICANT_DIGITS: i32 = 15;
+
+/// Formats a result for display.
+///
+/// This rounds away floating point noise, trims trailing zeros,
+/// and switches to scientific notation for very large or small values.
+fn format_result(value: f64) -> String {
+    fn trim_fraction_zeros(text: &str) -> &str {
+        if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            text
+        }
+    }
+
+    let abs = value.abs();
+    if !value.is_finite() || abs == 0.0 {
+        return value.to_string();
+    }
+
+    if !(SCIENTIFIC_BELOW..SCIENTIFIC_FROM).contains(&abs) {
+        let decimals = usize::try_from(SIGNIFICANT_DIGITS - 1).unwrap_or(0);
+        let text = format!("{value:.decimals$e}");
+        let (mantissa, exponent) = text.split_once('e').expect("scientific notation must have an exponent");
+        return format!("{}e{exponent}", trim_fraction_zeros(mantissa));
+    }
+
+    #[allow(clippy::cast_possible_truncation)] // within the above range
+    let magnitude = abs.log10().floor() as i32;
+    let decimals = usize::try_from(SIGNIFICANT_DIGITS - 1 - magnitude).unwrap_or(0);
+    trim_fraction_zeros(&format!("{value:.decimals$}")).to_owned()
 }
 
 #[cfg(test)]
 mod test {
-    use super::eval_text;
+    use super::{eval_text, format_result, non_finite_message, Env, MathError};
 
     macro_rules! is_correct {
-        ($math:literal, $result:literal) => {{
+        ($math:literal, $result:literal) => {
+            is_correct!($math, $result, &Env::default())
+        };
+        ($math:literal, $result:literal, $env:expr) => {{
             const MIN: f64 = $result - 0.001;
             const MAX: f64 = $result + 0.001;
             let text = $math;
-            let res = eval_text(text);
+            let res = eval_text(text, $env);
             assert!(matches!(res, Ok(MIN..=MAX)), "`{text:?}` not in `{MIN}..={MAX}`, was {res:?}");
         }};
     }
@@ -120,4 +209,258 @@ mod test {
         is_correct!("min(2, max(-3, +5, 2), 21) * log(100, 10)", 4.0);
         is_correct!("min()", 0.0);
     }
+
+    #[test]
+    fn ans() {
+        let env = Env { ans: Some(6.0) };
+        is_correct!("ans", 6.0, &env);
+        is_correct!("ans * 2 + 1", 13.0, &env);
+        is_correct!("max(ans, 10)", 10.0, &env);
+
+        let res = eval_text("ans + 1", &Env::default());
+        assert!(matches!(res, Err(MathError::NoPreviousResult(_))), "expected error, was {res:?}");
+    }
+
+    #[test]
+    fn bitwise() {
+        is_correct!("12 & 10", 8.0);
+        is_correct!("12 | 3", 15.0);
+        is_correct!("12 xor 10", 6.0);
+        is_correct!("1 << 4", 16.0);
+        is_correct!("256 >> 2", 64.0);
+        is_correct!("~5", -6.0);
+        is_correct!("2 ^ 3", 8.0);
+
+        // shifts bind weaker than addition, and `&` weaker than shifts
+        is_correct!("1 << 2 + 1", 8.0);
+        is_correct!("7 & 1 << 1", 2.0);
+        is_correct!("1 | 2 xor 3 & 1", 3.0);
+    }
+
+    #[test]
+    fn bitwise_non_integer() {
+        for text in ["1.5 & 1", "1 | 0.5", "2.5 xor 1", "~0.5", "1 << 64", "1 >> -1"] {
+            let res = eval_text(text, &Env::default());
+            assert!(matches!(res, Err(MathError::IntegerExpected(_))), "`{text:?}` should fail, was {res:?}");
+        }
+    }
+
+    #[test]
+    fn comparison() {
+        is_correct!("1 < 2", 1.0);
+        is_correct!("2 < 1", 0.0);
+        is_correct!("3 > 2", 1.0);
+        is_correct!("2 <= 2", 1.0);
+        is_correct!("1 >= 2", 0.0);
+        is_correct!("2 == 2", 1.0);
+        is_correct!("2 != 2", 0.0);
+
+        // comparisons bind weaker than arithmetic
+        is_correct!("1 + 1 == 2", 1.0);
+        is_correct!("2 * 3 > 5", 1.0);
+        is_correct!("(7 > 5) * 10", 10.0);
+    }
+
+    #[test]
+    fn conditional() {
+        is_correct!("if(1, 2, 3)", 2.0);
+        is_correct!("if(0, 2, 3)", 3.0);
+        is_correct!("if(4 > 5, 1, -1) * 10", -10.0);
+        is_correct!("clamp(5, 0, 3)", 3.0);
+        is_correct!("clamp(-5, 0, 3)", 0.0);
+        is_correct!("clamp(2, 0, 3)", 2.0);
+    }
+
+    #[test]
+    fn implicit_mul() {
+        is_correct!("2pi", 6.283);
+        is_correct!("2(3)", 6.0);
+        is_correct!("3(4+1)", 15.0);
+        is_correct!("(1 + 1)(2 + 1)", 6.0);
+        is_correct!("2e", 5.437);
+        is_correct!("2e3", 2000.0);
+        is_correct!("2e-1", 0.2);
+        is_correct!("2sqrt(16)", 8.0);
+        is_correct!("1 + 2pi * 2", 13.566);
+
+        // calls stay calls
+        is_correct!("sin(0)", 0.0);
+        is_correct!("max(1, 2)(3)", 6.0);
+        is_correct!("2max(1, 2)", 4.0);
+    }
+
+    #[test]
+    fn function_call_expected() {
+        // functions taking multiple parameters need parenthesis
+        for text in ["max 1", "log 100", "clamp", "2 + min"] {
+            let res = eval_text(text, &Env::default());
+            assert!(matches!(res, Err(MathError::FunctionCallExpected(_))), "`{text:?}` should fail, was {res:?}");
+        }
+
+        // single-parameter functions are unary operators and don't
+        is_correct!("sin 0", 0.0);
+        is_correct!("sqrt 16 + 1", 5.0);
+    }
+
+    #[test]
+    fn non_finite() {
+        fn message(text: &str) -> &'static str {
+            let res = eval_text(text, &Env::default()).expect("should evaluate");
+            assert!(!res.is_finite(), "`{text:?}` should not be finite, was {res}");
+            non_finite_message(res)
+        }
+
+        let infinite = "The result is infinite or too large. Does it divide by `0`?";
+        assert_eq!(message("1/0"), infinite);
+        assert_eq!(message("-1/0"), infinite);
+        assert_eq!(message("10^400"), infinite);
+
+        let undefined = message("0/0");
+        assert!(undefined.starts_with("The result is undefined."));
+        assert!(undefined.contains("`0` by `0`"));
+        assert_eq!(message("log(-1, 10)"), undefined);
+        assert_eq!(message("sqrt(-1)"), undefined);
+    }
+
+    #[test]
+    fn format_integers() {
+        assert_eq!(format_result(2.0), "2");
+        assert_eq!(format_result(-42.0), "-42");
+        assert_eq!(format_result(0.0), "0");
+        assert_eq!(format_result(123_456_789.0), "123456789");
+    }
+
+    #[test]
+    fn format_scientific() {
+        assert_eq!(format_result(1e20), "1e20");
+        assert_eq!(format_result(-1.5e20), "-1.5e20");
+        assert_eq!(format_result(2.5e-7), "2.5e-7");
+        assert_eq!(format_result(f64::INFINITY), "inf");
+    }
+
+    #[test]
+    fn format_decimals() {
+        assert_eq!(format_result(1.0 / 3.0), "0.333333333333333");
+        assert_eq!(format_result(2.0 / 3.0), "0.666666666666667");
+        assert_eq!(format_result(0.1 + 0.2), "0.3");
+        assert_eq!(format_result(-4.5), "-4.5");
+    }
+
+    mod prop {
+        use proptest::prelude::*;
+
+        use super::super::{eval_text, Env};
+
+        /// A binary operator supported by the reference evaluation.
+        #[derive(Debug, Clone, Copy)]
+        enum Op {
+            Add,
+            Sub,
+            Mul,
+            Div,
+        }
+
+        impl Op {
+            fn text(self) -> &'static str {
+                match self {
+                    Op::Add => "+",
+                    Op::Sub => "-",
+                    Op::Mul => "*",
+                    Op::Div => "/",
+                }
+            }
+        }
+
+        /// Evaluates a chain of values and operators with the usual order of operations.
+        fn reference_eval(first: f64, rest: &[(Op, f64)]) -> f64 {
+            let mut sum = 0.0;
+            let mut sign = 1.0;
+            let mut term = first;
+
+            for &(op, value) in rest {
+                match op {
+                    Op::Mul => term *= value,
+                    Op::Div => term /= value,
+                    Op::Add | Op::Sub => {
+                        sum += sign * term;
+                        sign = if matches!(op, Op::Add) { 1.0 } else { -1.0 };
+                        term = value;
+                    },
+                }
+            }
+
+            sum + sign * term
+        }
+
+        fn op() -> impl Strategy<Value = Op> {
+            prop_oneof![Just(Op::Add), Just(Op::Sub), Just(Op::Mul), Just(Op::Div)]
+        }
+
+        /// Renders a chain of sub-expressions and evaluates it with [`reference_eval`].
+        fn chain(inner: impl Strategy<Value = (String, f64)> + Clone) -> impl Strategy<Value = (String, f64)> {
+            (inner.clone(), prop::collection::vec((op(), inner), 0..5))
+                .prop_map(|((mut text, first), rest)| {
+                    let values: Vec<_> = rest.iter().map(|(op, (_, value))| (*op, *value)).collect();
+                    for (op, (sub, _)) in &rest {
+                        text.push(' ');
+                        text.push_str(op.text());
+                        text.push(' ');
+                        text.push_str(sub);
+                    }
+
+                    (text, reference_eval(first, &values))
+                })
+        }
+
+        /// Random valid arithmetic expressions with their expected values.
+        ///
+        /// Values are never zero, but sub-expressions may be, so division by zero can occur.
+        fn expr() -> impl Strategy<Value = (String, f64)> {
+            let leaf = (1u32..100).prop_map(|n| (n.to_string(), f64::from(n)));
+            let grouped = leaf.prop_recursive(3, 32, 5, |inner| {
+                chain(inner).prop_map(|(text, value)| (format!("({text})"), value))
+            });
+
+            chain(grouped.boxed())
+        }
+
+        /// Random sequences of tokens, valid or not.
+        fn token_soup() -> impl Strategy<Value = String> {
+            const TOKENS: &[&str] = &[
+                "1", "2.5", "0", "1e3", "2e", "+", "-", "*", "/", "^", "%", "<<", ">>", "&", "|", "~",
+                "<", "==", "(", ")", ",", "sin", "sqrt", "max", "log", "if", "pi", "ans", "foo", " ",
+            ];
+
+            prop::collection::vec(prop::sample::select(TOKENS), 0..24)
+                .prop_map(|tokens| tokens.concat())
+        }
+
+        proptest! {
+            #[test]
+            fn matches_reference((text, expected) in expr()) {
+                let result = eval_text(&text, &Env::default());
+                let Ok(result) = result else {
+                    return Err(TestCaseError::fail(format!("`{text}` failed: {result:?}")));
+                };
+
+                if expected.is_finite() {
+                    let tolerance = 1e-9 * expected.abs().max(1.0);
+                    prop_assert!((result - expected).abs() <= tolerance, "`{text}` = {result}, expected {expected}");
+                } else {
+                    prop_assert!(!result.is_finite(), "`{text}` = {result}, expected {expected}");
+                }
+            }
+
+            #[test]
+            fn never_panics_on_token_soup(text in token_soup()) {
+                let env = Env { ans: Some(2.0) };
+                _ = eval_text(&text, &env);
+            }
+
+            #[test]
+            fn never_panics_on_any_text(text in "\\PC{0,32}") {
+                _ = eval_text(&text, &Env::default());
+            }
+        }
+    }
 }
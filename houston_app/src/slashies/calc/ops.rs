@@ -36,13 +36,24 @@ macro_rules! define_op_kind {
 
 define_op_kind! {
     /// A binary operator kind.
-    enum BinaryOp(lhs: f64, rhs: f64) -> f64 {
-        Add "+" => lhs + rhs,
-        Sub "-" => lhs - rhs,
-        Mul "*" => lhs * rhs,
-        Div "/" => lhs / rhs,
-        Mod "%" | "mod" => lhs % rhs,
-        Pow "^" | "pow" => lhs.powf(rhs),
+    enum BinaryOp['a](op: Token<'a>, lhs: f64, rhs: f64) -> Result<'a, f64> {
+        Add "+" => Ok(lhs + rhs),
+        Sub "-" => Ok(lhs - rhs),
+        Mul "*" => Ok(lhs * rhs),
+        Div "/" => Ok(lhs / rhs),
+        Mod "%" | "mod" => Ok(lhs % rhs),
+        Pow "^" | "pow" => Ok(lhs.powf(rhs)),
+        And "&" | "and" => Ok(from_int(to_int(lhs, op)? & to_int(rhs, op)?)),
+        Or "|" | "or" => Ok(from_int(to_int(lhs, op)? | to_int(rhs, op)?)),
+        Xor "xor" => Ok(from_int(to_int(lhs, op)? ^ to_int(rhs, op)?)),
+        Shl "<<" => Ok(from_int(to_int(lhs, op)? << to_shift(rhs, op)?)),
+        Shr ">>" => Ok(from_int(to_int(lhs, op)? >> to_shift(rhs, op)?)),
+        Lt "<" => Ok(from_bool(lhs < rhs)),
+        Gt ">" => Ok(from_bool(lhs > rhs)),
+        Le "<=" => Ok(from_bool(lhs <= rhs)),
+        Ge ">=" => Ok(from_bool(lhs >= rhs)),
+        Eq "==" => Ok(from_bool(lhs == rhs)),
+        Ne "!=" => Ok(from_bool(lhs != rhs)),
     }
 }
 
@@ -51,28 +62,34 @@ impl BinaryOp {
     /// Relevant for order-of-operations.
     pub const fn priority(self) -> isize {
         match self {
-            BinaryOp::Add | BinaryOp::Sub => 1,
-            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => 2,
-            BinaryOp::Pow => 3,
+            BinaryOp::Lt | BinaryOp::Gt | BinaryOp::Le | BinaryOp::Ge | BinaryOp::Eq | BinaryOp::Ne => 1,
+            BinaryOp::Or => 2,
+            BinaryOp::Xor => 3,
+            BinaryOp::And => 4,
+            BinaryOp::Shl | BinaryOp::Shr => 5,
+            BinaryOp::Add | BinaryOp::Sub => 6,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => 7,
+            BinaryOp::Pow => 8,
         }
     }
 }
 
 define_op_kind! {
     /// A unary operator kind.
-    enum UnaryOp(value: f64) -> f64 {
-        Plus "+" => value,
-        Minus "-" => -value,
-        Abs "abs" => value.abs(),
-        Sqrt "sqrt" => value.sqrt(),
-        Sin "sin" => value.sin(),
-        Cos "cos" => value.cos(),
-        Tan "tan" => value.tan(),
-        Asin "asin" => value.asin(),
-        Acos "acos" => value.acos(),
-        Atan "atan" => value.atan(),
-        Ln "ln" => value.ln(),
-        Exp "exp" => value.exp(),
+    enum UnaryOp['a](op: Token<'a>, value: f64) -> Result<'a, f64> {
+        Plus "+" => Ok(value),
+        Minus "-" => Ok(-value),
+        Not "~" | "not" => Ok(from_int(!to_int(value, op)?)),
+        Abs "abs" => Ok(value.abs()),
+        Sqrt "sqrt" => Ok(value.sqrt()),
+        Sin "sin" => Ok(value.sin()),
+        Cos "cos" => Ok(value.cos()),
+        Tan "tan" => Ok(value.tan()),
+        Asin "asin" => Ok(value.asin()),
+        Acos "acos" => Ok(value.acos()),
+        Atan "atan" => Ok(value.atan()),
+        Ln "ln" => Ok(value.ln()),
+        Exp "exp" => Ok(value.exp()),
     }
 }
 
@@ -85,6 +102,16 @@ define_op_kind! {
         },
         Min "min" => Ok(fold_values(values, f64::min)),
         Max "max" => Ok(fold_values(values, f64::max)),
+        // all arguments are evaluated before the call, so this doesn't short-circuit.
+        // that's fine since expressions have no side effects.
+        If "if" => {
+            let &[cond, a, b] = read_args(values, fn_name)?;
+            Ok(if cond != 0.0 { a } else { b })
+        },
+        Clamp "clamp" => {
+            let &[x, lo, hi] = read_args(values, fn_name)?;
+            Ok(x.max(lo).min(hi))
+        },
     }
 }
 
@@ -108,3 +135,37 @@ fn fold_values(
         .reduce(f)
         .unwrap_or(0.0)
 }
+
+/// Converts a value to an integer for bitwise operators.
+///
+/// Fails if the value isn't integral or doesn't fit.
+#[allow(clippy::cast_possible_truncation)] // checked by the range
+fn to_int(value: f64, op: Token<'_>) -> Result<'_, i64> {
+    const RANGE: std::ops::Range<f64> = (i64::MIN as f64)..(i64::MAX as f64);
+
+    if value.fract() == 0.0 && RANGE.contains(&value) {
+        Ok(value as i64)
+    } else {
+        Err(MathError::IntegerExpected(op))
+    }
+}
+
+/// Converts a value to a shift amount.
+///
+/// Fails if it isn't an integer within the bit width.
+fn to_shift(value: f64, op: Token<'_>) -> Result<'_, u32> {
+    u32::try_from(to_int(value, op)?)
+        .ok()
+        .filter(|&s| s < i64::BITS)
+        .ok_or(MathError::IntegerExpected(op))
+}
+
+/// Converts the result of a bitwise operator back to a value.
+fn from_int(value: i64) -> f64 {
+    value as f64
+}
+
+/// Converts the result of a comparison to `1.0` or `0.0`.
+fn from_bool(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use super::{MathError, Result};
+use super::{Env, MathError, Result};
 use super::ops::*;
 
 /// A singular equation token, as returned by the tokenizer.
@@ -54,10 +54,10 @@ struct ExprSuccess<'a> {
     terminator: Option<Token<'a>>,
 }
 
-/// Pair of a value and the following binary operator.
-struct ValuePair {
+/// Pair of a value and the following binary operator with its token.
+struct ValuePair<'a> {
     value: f64,
-    operator: Option<BinaryOp>,
+    operator: Option<(BinaryOp, Token<'a>)>,
 }
 
 /// A kind-of iterator for tokenizing.
@@ -81,29 +81,58 @@ pub trait Tokenizer<'a> {
 /// Returns an kind-of iterator to the tokens.
 pub fn tokenize(text: &str) -> impl Tokenizer<'_> {
     // - split by whitespace
-    // - split each fragment by special characters, including them at the end of the new fragments
-    // - split away the special characters also
+    // - split each fragment at special characters, each one being its own token
+    // - except for two-character operators like `<<` or `<=`, which are kept together
+    // - split numbers from directly following text, so `2pi` becomes `2` and `pi`
 
     fn is_special_char(c: u8) -> bool {
         // Note: each of these must be an ASCII character
-        matches!(c, b'+' | b'-' | b'*' | b'/' | b'%' | b'^' | b'(' | b')' | b',')
+        matches!(c, b'+' | b'-' | b'*' | b'/' | b'%' | b'^' | b'(' | b')' | b',' | b'&' | b'|' | b'~' | b'<' | b'>' | b'=' | b'!')
+    }
+
+    fn split_special(mut s: &[u8]) -> impl Iterator<Item = &[u8]> {
+        std::iter::from_fn(move || {
+            let len = match s {
+                [] => return None,
+                [b'<', b'<' | b'=', ..] | [b'>', b'>' | b'=', ..] | [b'=' | b'!', b'=', ..] => 2,
+                [c, ..] if is_special_char(*c) => 1,
+                [b'0'..=b'9', ..] => number_len(s),
+                _ => s.iter().position(|c| is_special_char(*c)).unwrap_or(s.len()),
+            };
+
+            let (token, rest) = s.split_at(len);
+            s = rest;
+            Some(token)
+        })
+    }
+
+    fn number_len(s: &[u8]) -> usize {
+        fn digits_len(s: &[u8], index: usize) -> usize {
+            s[index..].iter()
+                .position(|c| !matches!(c, b'0'..=b'9' | b'.'))
+                .map_or(s.len(), |p| index + p)
+        }
+
+        // only treat `e` as an exponent if digits follow, otherwise it's the constant
+        let len = digits_len(s, 0);
+        match s[len..] {
+            [b'e', b'0'..=b'9', ..] => digits_len(s, len + 1),
+            [b'e', b'+' | b'-', b'0'..=b'9', ..] => digits_len(s, len + 2),
+            _ => len,
+        }
     }
 
     unsafe fn token_from_utf8(token_index: usize, bytes: &[u8]) -> Token<'_> {
         debug_assert!(std::str::from_utf8(bytes).is_ok());
 
-        // SAFETY: only splitting on ASCII characters
+        // SAFETY: only splitting at ASCII characters
         let text = unsafe { std::str::from_utf8_unchecked(bytes) };
         Token { text, token_index }
     }
 
     let iter = text.as_bytes()
         .split(|c| c.is_ascii_whitespace())
-        .flat_map(|s| s.split_inclusive(|c| is_special_char(*c)))
-        .flat_map(|s| match s.split_last() {
-            Some((last, rest)) if is_special_char(*last) => std::iter::once(rest).chain(Some(std::slice::from_ref(last))),
-            _ => std::iter::once(s).chain(None),
-        })
+        .flat_map(split_special)
         .filter(|s| !s.is_empty())
         .enumerate()
         .map(|(i, s)| unsafe { token_from_utf8(i, s) });
@@ -150,8 +179,8 @@ pub fn tokenize(text: &str) -> impl Tokenizer<'_> {
 }
 
 /// Reads an expression. This will consume `tokens` until the end.
-pub fn read_expr<'a>(tokens: &mut impl Tokenizer<'a>) -> Result<'a, f64> {
-    read_expr_with_terminator(tokens, |t| t.is_none()).map(|e| e.value)
+pub fn read_expr<'a>(tokens: &mut impl Tokenizer<'a>, env: &Env) -> Result<'a, f64> {
+    read_expr_with_terminator(tokens, env, |t| t.is_none()).map(|e| e.value)
 }
 
 /// Reads an expression. This will consume `tokens` until it matches `terminate_on`
@@ -160,6 +189,7 @@ pub fn read_expr<'a>(tokens: &mut impl Tokenizer<'a>) -> Result<'a, f64> {
 /// If no more tokens are available before it finds the terminator, returns an error.
 fn read_expr_with_terminator<'a>(
     tokens: &mut impl Tokenizer<'a>,
+    env: &Env,
     terminate_on: fn(Option<Token<'a>>) -> bool,
 ) -> Result<'a, ExprSuccess<'a>> {
     // this is the main place where this allocates. the other is function parameters
@@ -168,14 +198,26 @@ fn read_expr_with_terminator<'a>(
     let mut pairs = Vec::new();
     loop {
         // read sub expressions until out of tokens
-        let value = read_sub_expr(tokens)?;
+        let value = read_sub_expr(tokens, env)?;
+
+        // a value directly followed by another one is multiplied, like `2pi`.
+        // this has the same priority as an explicit `*`.
+        if let Some(next) = tokens.peek().filter(|&t| starts_implicit_mul(t)) {
+            pairs.push(ValuePair {
+                value,
+                operator: Some((BinaryOp::Mul, next)),
+            });
+
+            continue;
+        }
+
         let token = tokens.next();
 
         // if this a terminator, finish the expression and return it
         if terminate_on(token) {
             let value = if !pairs.is_empty() {
                 pairs.push(ValuePair { value, operator: None });
-                merge_expr_pairs(pairs)
+                merge_expr_pairs(pairs)?
             } else {
                 value
             };
@@ -193,12 +235,12 @@ fn read_expr_with_terminator<'a>(
         };
 
         // expecting a binary operator here
-        let operator = BinaryOp::from_token(operator)
+        let kind = BinaryOp::from_token(operator)
             .ok_or(MathError::InvalidBinaryOperator(operator))?;
 
         pairs.push(ValuePair {
             value,
-            operator: Some(operator),
+            operator: Some((kind, operator)),
         });
     }
 }
@@ -208,7 +250,7 @@ fn read_expr_with_terminator<'a>(
 /// or an identifier.
 ///
 /// If no more tokens are available, returns an error.
-fn read_sub_expr<'a>(tokens: &mut impl Tokenizer<'a>) -> Result<'a, f64> {
+fn read_sub_expr<'a>(tokens: &mut impl Tokenizer<'a>, env: &Env) -> Result<'a, f64> {
     let Some(token) = tokens.next() else {
         return Err(tokens.expr_expected());
     };
@@ -216,13 +258,16 @@ fn read_sub_expr<'a>(tokens: &mut impl Tokenizer<'a>) -> Result<'a, f64> {
     // this match *returns* for non-Expr branches
     let expr = match token.text.as_bytes() {
         // start of parenthesis around child-expression
-        b"(" => read_expr_with_terminator(tokens, |t| matches_token!(t, ")"))?.value,
+        b"(" => read_expr_with_terminator(tokens, env, |t| matches_token!(t, ")"))?.value,
 
         // constants
         b"pi" => std::f64::consts::PI,
         b"e" => std::f64::consts::E,
         b"tau" => std::f64::consts::TAU,
 
+        // the previous result
+        b"ans" => env.ans.ok_or(MathError::NoPreviousResult(token))?,
+
         // anything starting with a digit is assumed to be a number
         [b'0'..=b'9', ..] => f64::from_str(token.text).map_err(|_| MathError::InvalidNumber(token))?,
 
@@ -231,9 +276,9 @@ fn read_sub_expr<'a>(tokens: &mut impl Tokenizer<'a>) -> Result<'a, f64> {
 
         // lastly, also check for unary operators and functions
         _ => if let Some(op) = UnaryOp::from_token(token) {
-            op.apply(read_sub_expr(tokens)?)
+            op.apply(token, read_sub_expr(tokens, env)?)?
         } else if let Some(call) = CallOp::from_token(token) {
-            read_call(tokens, call, token)?
+            read_call(tokens, env, call, token)?
         } else if matches_token!(tokens.peek(), "(") {
             return Err(MathError::InvalidFunction(token));
         } else if tokens.peek().is_some() {
@@ -246,10 +291,23 @@ fn read_sub_expr<'a>(tokens: &mut impl Tokenizer<'a>) -> Result<'a, f64> {
     Ok(expr)
 }
 
+/// Whether a token directly after a value starts another value it should be multiplied with,
+/// like `pi` in `2pi` or `(` in `3(4+1)`.
+///
+/// Numbers aren't included, so `2 3` stays an error.
+fn starts_implicit_mul(token: Token<'_>) -> bool {
+    match token.text.as_bytes() {
+        b"(" | b"pi" | b"e" | b"tau" | b"ans" => true,
+        [b'a'..=b'z', ..] => BinaryOp::from_token(token).is_none()
+            && (UnaryOp::from_token(token).is_some() || CallOp::from_token(token).is_some()),
+        _ => false,
+    }
+}
+
 /// Reads the parameters for a function call and evaluates it.
 ///
 /// This also checks that the next token is `(`.
-fn read_call<'a>(tokens: &mut impl Tokenizer<'a>, call_fn: CallOp, call_fn_token: Token<'a>) -> Result<'a, f64> {
+fn read_call<'a>(tokens: &mut impl Tokenizer<'a>, env: &Env, call_fn: CallOp, call_fn_token: Token<'a>) -> Result<'a, f64> {
     if !matches_token!(tokens.next(), "(") {
         return Err(MathError::FunctionCallExpected(call_fn_token));
     }
@@ -263,7 +321,7 @@ fn read_call<'a>(tokens: &mut impl Tokenizer<'a>, call_fn: CallOp, call_fn_token
     } else {
         // otherwise terminate when we hit a close in a terminator position
         loop {
-            let res = read_expr_with_terminator(tokens, terminate_on)?;
+            let res = read_expr_with_terminator(tokens, env, terminate_on)?;
             params.push(res.value);
             if matches_token!(res.terminator, ")") {
                 break;
@@ -276,10 +334,14 @@ fn read_call<'a>(tokens: &mut impl Tokenizer<'a>, call_fn: CallOp, call_fn_token
 
 /// Merges a list of expression pairs into a singular expression.
 ///
+/// # Errors
+///
+/// Returns an error if applying any operator fails.
+///
 /// # Panics
 ///
 /// Panics if `pairs` is empty or any operator other than the last is [`None`].
-fn merge_expr_pairs(mut pairs: Vec<ValuePair>) -> f64 {
+fn merge_expr_pairs(mut pairs: Vec<ValuePair<'_>>) -> Result<'_, f64> {
     while pairs.len() > 1 {
         // iterate over adjacent pairs (e.g. basically `pairs.windows(2)` but mutable).
         // the cell trick documented for `windows` could work, but it's harder to deal with and not any less code.
@@ -287,19 +349,19 @@ fn merge_expr_pairs(mut pairs: Vec<ValuePair>) -> f64 {
             let [lhs, rhs, ..] = &mut pairs[index..] else { unreachable!() };
 
             // None is only set for the last element
-            let kind = lhs.operator
+            let (kind, token) = lhs.operator
                 .expect("only last operator must be empty");
 
             // merge cells if the left-hand priority is greater or equal than the right
             // or if the right hand operator is None
-            if rhs.operator.map_or(true, |r| kind.priority() >= r.priority()) {
+            if rhs.operator.map_or(true, |(r, _)| kind.priority() >= r.priority()) {
                 // copy the values out since we'll need to put them elsewhere
                 let lhs_value = lhs.value;
                 let rhs_value = rhs.value;
 
                 // replace `lhs` with the new pair
                 *lhs = ValuePair {
-                    value: kind.apply(lhs_value, rhs_value),
+                    value: kind.apply(token, lhs_value, rhs_value)?,
                     operator: rhs.operator,
                 };
 
@@ -318,7 +380,9 @@ fn merge_expr_pairs(mut pairs: Vec<ValuePair>) -> f64 {
     }
 
     // pairs must not be empty
-    pairs.pop()
+    let value = pairs.pop()
         .expect("the pairs must not be empty")
-        .value
+        .value;
+
+    Ok(value)
 }
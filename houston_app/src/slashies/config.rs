@@ -1,9 +1,13 @@
+use azur_lane::Language;
+use poise::ChoiceParameter;
+
+use crate::buttons::azur::ship::ViewAffinity;
 use crate::prelude::*;
 
 /// Provides (temporary) configuration for this app.
 #[poise::command(
     slash_command,
-    subcommands("config_hide"),
+    subcommands("config_hide", "config_language", "config_compact", "config_stats"),
     subcommand_required
 )]
 pub async fn config(_: HContext<'_>) -> HResult {
@@ -33,3 +37,169 @@ async fn config_hide(
     ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
     Ok(())
 }
+
+/// Configures whether ship stats are shown as a compact list for narrow screens.
+#[poise::command(slash_command, rename = "compact")]
+async fn config_compact(
+    ctx: HContext<'_>,
+    #[description = "Whether stats are shown compactly. Starts at false."]
+    compact: Option<bool>
+) -> HResult {
+    let mut data = ctx.get_user_data();
+    data.compact_stats = compact.unwrap_or(!data.compact_stats);
+    ctx.set_user_data(data.clone());
+
+    let content = format!(
+        "Ship stats will now be shown **{}**.",
+        if data.compact_stats { "as a compact list" } else { "as a table" }
+    );
+
+    let embed = CreateEmbed::new()
+        .description(content)
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+    Ok(())
+}
+
+/// Configures the level and affinity that ship stats are shown at by default.
+#[poise::command(slash_command, rename = "stats")]
+async fn config_stats(
+    ctx: HContext<'_>,
+    #[description = "The level to show stats at. Starts at 120."]
+    level: Option<ELevel>,
+    #[description = "The affinity to show stats at. Starts at Love."]
+    affinity: Option<EAffinity>,
+) -> HResult {
+    let mut data = ctx.get_user_data();
+    if let Some(level) = level {
+        data.ship_level = level.convert();
+    }
+    if let Some(affinity) = affinity {
+        data.ship_affinity = affinity.convert();
+    }
+    ctx.set_user_data(data.clone());
+
+    let content = format!(
+        "Ship stats will now be shown at **Lv.{}** with **{}** affinity.",
+        data.ship_level, affinity_name(data.ship_affinity)
+    );
+
+    let embed = CreateEmbed::new()
+        .description(content)
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+    Ok(())
+}
+
+/// Configures the language used for Azur Lane game data.
+#[poise::command(slash_command, rename = "language")]
+async fn config_language(
+    ctx: HContext<'_>,
+    #[description = "The language to use. Falls back to English if no data is available."]
+    language: ELanguage
+) -> HResult {
+    let language = language.convert();
+
+    let mut data = ctx.get_user_data();
+    data.language = language;
+    ctx.set_user_data(data);
+
+    let content = if ctx.data().has_azur_lane_in(language) {
+        format!("Azur Lane data will now be shown in **{}**.", language.name())
+    } else {
+        format!("There is no Azur Lane data in **{}**. English will be shown instead.", language.name())
+    };
+
+    let embed = CreateEmbed::new()
+        .description(content)
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+    Ok(())
+}
+
+#[derive(ChoiceParameter)]
+enum ELanguage {
+    English,
+    Japanese,
+    #[name = "Chinese (Simplified)"] ChineseSimplified,
+    #[name = "Chinese (Traditional)"] ChineseTraditional,
+    Korean,
+}
+
+impl ELanguage {
+    const fn convert(self) -> Language {
+        match self {
+            Self::English => Language::English,
+            Self::Japanese => Language::Japanese,
+            Self::ChineseSimplified => Language::ChineseSimplified,
+            Self::ChineseTraditional => Language::ChineseTraditional,
+            Self::Korean => Language::Korean,
+        }
+    }
+}
+
+/// The levels that ship stats can be shown at by default.
+#[derive(ChoiceParameter)]
+enum ELevel {
+    #[name = "100"] Level100,
+    #[name = "120"] Level120,
+    #[name = "125"] Level125,
+}
+
+impl ELevel {
+    const fn convert(self) -> u8 {
+        match self {
+            Self::Level100 => 100,
+            Self::Level120 => 120,
+            Self::Level125 => 125,
+        }
+    }
+}
+
+#[derive(ChoiceParameter)]
+enum EAffinity {
+    Neutral,
+    Love,
+    Oath,
+}
+
+impl EAffinity {
+    const fn convert(self) -> ViewAffinity {
+        match self {
+            Self::Neutral => ViewAffinity::Neutral,
+            Self::Love => ViewAffinity::Love,
+            Self::Oath => ViewAffinity::Oath,
+        }
+    }
+}
+
+/// Gets the display name for an affinity.
+const fn affinity_name(affinity: ViewAffinity) -> &'static str {
+    match affinity {
+        ViewAffinity::Neutral => "Neutral",
+        ViewAffinity::Love => "Love",
+        ViewAffinity::Oath => "Oath",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn level_choices_match_names() {
+        // the names are what users pick from, so they must match the level that is used
+        let mut levels = Vec::new();
+        for index in 0..ELevel::list().len() {
+            let level = ELevel::from_index(index).unwrap();
+            assert_eq!(level.name(), level.convert().to_string());
+            levels.push(level.convert());
+        }
+
+        // otherwise, the default couldn't be chosen again after changing it
+        assert!(levels.contains(&crate::buttons::azur::ship::DEFAULT_LEVEL));
+    }
+}
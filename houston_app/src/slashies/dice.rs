@@ -2,8 +2,9 @@ use std::num::NonZero;
 use std::str::FromStr;
 use std::fmt::Write;
 
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng};
 use rand::distributions::Uniform;
+use rand::rngs::StdRng;
 
 use utils::Discard;
 
@@ -15,6 +16,8 @@ pub async fn dice(
     ctx: HContext<'_>,
     #[description = "The sets of dice to roll, in a format like '2d6', separated by spaces."]
     sets: DiceSetVec,
+    #[description = "A seed to make the rolls reproducible. Not suitable for anything security-related."]
+    seed: Option<u64>,
 ) -> HResult {
     let sets = sets.into_vec();
     let dice_count: u32 = sets.iter().map(|d| u32::from(d.count.get())).sum();
@@ -22,19 +25,28 @@ pub async fn dice(
         Err(HArgError("You can't roll more than 255 dice at once."))?;
     }
 
-    let (total_sum, content) = get_dice_roll_result(sets);
-    let embed = CreateEmbed::new()
+    // a seeded rng gives the same rolls for the same inputs, which is useful for sharing results.
+    // it is not meant to be unpredictable.
+    let (total_sum, content) = match seed {
+        Some(seed) => get_dice_roll_result(sets, &mut StdRng::seed_from_u64(seed)),
+        None => get_dice_roll_result(sets, &mut thread_rng()),
+    };
+
+    let mut embed = CreateEmbed::new()
         .title(format!("Total \u{2211}{}", total_sum))
         .description(content)
         .color(DEFAULT_EMBED_COLOR);
 
+    if let Some(seed) = seed {
+        embed = embed.footer(CreateEmbedFooter::new(format!("Seed: {seed}")));
+    }
+
     ctx.send(ctx.create_reply().embed(embed)).await?;
     Ok(())
 }
 
-fn get_dice_roll_result(sets: Vec<DiceSet>) -> (u32, String) {
+fn get_dice_roll_result(sets: Vec<DiceSet>, rng: &mut impl Rng) -> (u32, String) {
     let mut content = String::new();
-    let mut rng = thread_rng();
 
     // 32 bits are enough (max allowed input is 255*65535)
     // so we won't ever exceed the needed space
@@ -119,3 +131,17 @@ impl FromStr for DiceSetVec {
             .and_then(|v| DiceSetVec::from_vec(v).ok_or(DiceParseError(())))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_rolls() {
+        let sets = || DiceSetVec::from_str("3d6 2d20 1d100").unwrap().into_vec();
+
+        let first = get_dice_roll_result(sets(), &mut StdRng::seed_from_u64(1234));
+        let second = get_dice_roll_result(sets(), &mut StdRng::seed_from_u64(1234));
+        assert_eq!(first, second);
+    }
+}
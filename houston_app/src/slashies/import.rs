@@ -0,0 +1,35 @@
+use crate::buttons::{ButtonArgs, CustomData};
+use crate::prelude::*;
+
+/// Opens a view from a share code.
+#[poise::command(slash_command)]
+pub async fn import(
+    ctx: HContext<'_>,
+    #[description = "The share code, as provided by a view's share button."]
+    #[max_length = 400]
+    code: String,
+) -> HResult {
+    let data = CustomData::from_share_code(&code)
+        .map_err(|_| HArgError("That share code is invalid."))?;
+
+    // sentinels and nested share codes aren't views
+    if matches!(data.to_button_args()?, ButtonArgs::None(_) | ButtonArgs::Share(_)) {
+        Err(HArgError("That share code doesn't hold a view."))?
+    }
+
+    let embed = CreateEmbed::new()
+        .description("Share code imported. Click the button to open the view.")
+        .color(DEFAULT_EMBED_COLOR);
+
+    let components = CreateActionRow::Buttons(vec![
+        CreateButton::new(data.to_custom_id())
+            .label("Open")
+    ]);
+
+    let reply = ctx.create_reply()
+        .embed(embed)
+        .components(vec![components]);
+
+    ctx.send(reply).await?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use crate::fmt::discord::DisplayResolvedArgs;
 use crate::prelude::*;
@@ -8,7 +9,10 @@ mod calc;
 mod coin;
 mod config;
 mod dice;
+mod import;
 mod quote;
+mod reload;
+mod status;
 mod timestamp;
 mod upload;
 mod who;
@@ -21,20 +25,41 @@ pub fn get_commands(config: &crate::config::HBotConfig) -> Vec<poise::Command<Ar
         dice::dice(),
         calc::calc(),
         quote::quote(),
+        quote::random_quote(),
+        status::status(),
         timestamp::timestamp(),
         who::who(),
         upload::upload(),
+        import::import(),
     ];
 
     if config.azur_lane_data.is_some() {
         result.push(azur::azur());
+        result.push(reload::reload());
     }
 
     result
 }
 
+/// Command check that enforces the configured cooldowns.
+pub async fn command_check(ctx: HContext<'_>) -> Result<bool, HError> {
+    ctx.data().cooldowns()
+        .check(ctx.author().id, &ctx.command().qualified_name, Instant::now())
+        .map_err(CooldownError)?;
+
+    Ok(true)
+}
+
+utils::define_simple_error!(
+    CooldownError(std::time::Duration):
+    s => "You're using this command too often. Try again in {}s.", s.0.as_millis().div_ceil(1000)
+);
+
 /// Pre-command execution hook.
 pub async fn pre_command(ctx: HContext<'_>) {
+    ctx.set_invocation_data(Instant::now()).await;
+    ctx.data().metrics().inc_commands();
+
     log::info!("{}: /{} {}", ctx.author().name, ctx.command().qualified_name, match ctx {
         HContext::Application(ctx) => {
             ctx.interaction.data.target()
@@ -47,24 +72,45 @@ pub async fn pre_command(ctx: HContext<'_>) {
     })
 }
 
+/// Post-command execution hook.
+pub async fn post_command(ctx: HContext<'_>) {
+    if let Some(start) = ctx.invocation_data::<Instant>().await {
+        log::info!("{}: /{} completed in {:.2?}", ctx.author().name, ctx.command().qualified_name, start.elapsed());
+    }
+}
+
 /// Command execution error handler.
 #[cold]
 pub async fn error_handler(error: poise::FrameworkError<'_, Arc<HBotData>, HError>) {
     match &error {
         poise::FrameworkError::Command { error, ctx, .. } => {
+            // post_command isn't called for failed commands, so log the timing here
+            if let Some(start) = ctx.invocation_data::<Instant>().await {
+                log::info!("{}: /{} failed in {:.2?}", ctx.author().name, ctx.command().qualified_name, start.elapsed());
+            }
+
             command_error(ctx, error).await
         },
         poise::FrameworkError::ArgumentParse { error, input, ctx, .. } => {
             context_error(ctx, format!("Argument invalid: {}\nCaused by input: '{}'", error, input.as_deref().unwrap_or_default())).await
         },
+        poise::FrameworkError::CommandCheckFailed { error: Some(error), ctx, .. } => {
+            context_error(ctx, error.to_string()).await
+        },
         _ => log::error!("Oh noes, we got an error: {error:?}"),
     }
 
     async fn command_error(ctx: &HContext<'_>, err: &HError) {
+        ctx.data().metrics().inc_command_errors();
+
         let message = match err.downcast_ref::<HArgError>() {
             Some(err) => {
                 format!("Command error: ```{err}```")
             }
+            None if is_rate_limit_error(err) => {
+                log::info!("Rate-limited in command: {err}");
+                RATE_LIMITED_MESSAGE.to_owned()
+            }
             None => {
                 log::error!("Error in command: {err:?}");
                 format!("Internal error: ```{err}```")
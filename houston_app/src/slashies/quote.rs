@@ -1,8 +1,13 @@
+use rand::thread_rng;
+use rand::seq::IteratorRandom;
 use utils::time::*;
 
 use crate::prelude::*;
 use crate::fmt::discord::get_unique_username;
 
+/// The amount of recent messages to pick a random quote from.
+const RANDOM_QUOTE_BATCH: u8 = 100;
+
 /// Creates a copyable, quotable version of the message.
 #[poise::command(context_menu_command = "Get as Quote")]
 pub async fn quote(
@@ -13,17 +18,91 @@ pub async fn quote(
     message.channel_id = ctx.channel_id();
     message.guild_id = ctx.guild_id();
 
+    let embed = get_quote_embed(&message);
+    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+    Ok(())
+}
+
+/// Quotes a random recent message from a channel.
+#[poise::command(slash_command, guild_only, rename = "random-quote")]
+pub async fn random_quote(
+    ctx: HContext<'_>,
+    #[description = "The channel to pick from. Defaults to the current one."]
+    channel: Option<GuildChannel>,
+) -> HResult {
+    let (channel_id, guild_id) = match &channel {
+        Some(channel) => (channel.id, Some(channel.guild_id)),
+        None => (ctx.channel_id(), ctx.guild_id()),
+    };
+
+    // the bot may be able to read channels the user can't, so don't leak their messages
+    let required = Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY;
+    if !author_permissions(&ctx, channel_id).is_some_and(|p| p.contains(required)) {
+        Err(HArgError("You can't read the message history of that channel."))?;
+    }
+
+    // only a single batch is requested, so this stays well within rate limits
+    let messages = channel_id.messages(ctx.http(), GetMessages::new().limit(RANDOM_QUOTE_BATCH))
+        .await
+        .map_err(|err| if is_missing_access(&err) {
+            HArgError("I can't read the message history of that channel.").into()
+        } else {
+            HError::from(err)
+        })?;
+
+    let mut message = messages.into_iter()
+        .filter(|m| !m.author.bot && !m.content.trim().is_empty())
+        .choose(&mut thread_rng())
+        .ok_or(HArgError("There are no recent messages to quote in that channel."))?;
+
+    // not set for messages fetched via http
+    message.guild_id = guild_id;
+
+    let embed = get_quote_embed(&message);
+    ctx.send(ctx.create_reply().embed(embed)).await?;
+    Ok(())
+}
+
+/// Gets the invoking user's permissions in a channel, as provided with the interaction.
+///
+/// Returns [`None`] if they aren't known, such as for channels that weren't part of the interaction.
+fn author_permissions(ctx: &HContext<'_>, channel_id: ChannelId) -> Option<Permissions> {
+    let HContext::Application(ctx) = ctx else {
+        return None;
+    };
+
+    let interaction = ctx.interaction;
+    if interaction.channel_id == channel_id {
+        // the member data holds the permissions in the channel the command was used in
+        interaction.member.as_ref()?.permissions
+    } else {
+        interaction.data.resolved.channels.get(&channel_id)?.permissions
+    }
+}
+
+/// Creates the embed to show a quote in.
+fn get_quote_embed(message: &Message) -> CreateEmbed {
     let content = format!(
         "-# Quote: {t:x}\n```\n{t}\n```",
-        t = QuoteTarget::new(&message)
+        t = QuoteTarget::new(message)
     );
 
-    let embed = CreateEmbed::new()
+    CreateEmbed::new()
         .description(content)
-        .color(DEFAULT_EMBED_COLOR);
+        .color(DEFAULT_EMBED_COLOR)
+}
 
-    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
-    Ok(())
+/// Whether the error is Discord rejecting the request due to missing permissions.
+fn is_missing_access(err: &serenity::Error) -> bool {
+    // JSON error codes for "Missing Access" and "Missing Permissions"
+    const MISSING_ACCESS: isize = 50001;
+    const MISSING_PERMISSIONS: isize = 50013;
+
+    matches!(
+        err,
+        serenity::Error::Http(serenity::http::HttpError::UnsuccessfulRequest(res))
+            if matches!(res.error.code, MISSING_ACCESS | MISSING_PERMISSIONS)
+    )
 }
 
 struct QuoteTarget<'a> {
@@ -51,15 +130,10 @@ impl std::fmt::LowerHex for QuoteTarget<'_> {
 
 impl std::fmt::Display for QuoteTarget<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for line in self.message.content.lines() {
-            f.write_str("> ")?;
-            f.write_str(line)?;
-            f.write_str("\n")?;
-        }
-
         write!(
             f,
-            "-# \\- {name} @ {time} {link:x}",
+            "{quote}\n-# \\- {name} @ {time} {link:x}",
+            quote = utils::text::as_block_quote(&self.message.content),
             name = get_unique_username(&self.message.author),
             time = self.message.timestamp.short_date_time(),
             link = *self,
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// Reloads data without restarting the bot.
+#[poise::command(
+    slash_command,
+    subcommands("reload_azur"),
+    subcommand_required,
+    owners_only,
+    guild_only,
+    // only hides the command from non-admins, the owner check above is what restricts it
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn reload(_: HContext<'_>) -> HResult {
+    Ok(())
+}
+
+/// Reloads the Azur Lane game data from disk.
+#[poise::command(slash_command, rename = "azur", owners_only, guild_only)]
+async fn reload_azur(
+    ctx: HContext<'_>
+) -> HResult {
+    ctx.defer_ephemeral().await?;
+
+    // loading takes a while, so don't block the runtime
+    let data = Arc::clone(ctx.data());
+    match tokio::task::spawn_blocking(move || data.reload_azur_lane()).await? {
+        Ok(true) => {},
+        Ok(false) => Err(HArgError("Azur Lane data is not configured."))?,
+        Err(err) => {
+            log::error!("Failed to reload Azur Lane data: {err:?}");
+
+            let embed = CreateEmbed::new()
+                .title("Reload Failed")
+                .description(format!("The previously loaded data is still in use.\n```{err:#}```"))
+                .color(ERROR_EMBED_COLOR);
+
+            ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+            return Ok(());
+        },
+    }
+
+    let azur = ctx.data().azur_lane();
+    log::info!("Reloaded Azur Lane data.");
+
+    let content = format!(
+        "- **Ships:** {}\n\
+        - **Equipment:** {}\n\
+        - **Augment Modules:** {}",
+        azur.ships().len(),
+        azur.equips().len(),
+        azur.augments().len(),
+    );
+
+    let embed = CreateEmbed::new()
+        .title("Reloaded Azur Lane Data")
+        .description(content)
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+    Ok(())
+}
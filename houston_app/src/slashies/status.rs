@@ -0,0 +1,48 @@
+use std::fmt::Write;
+
+use utils::Discard;
+use utils::time::*;
+
+use crate::prelude::*;
+
+/// Shows the bot's current status.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn status(
+    ctx: HContext<'_>
+) -> HResult {
+    let data = ctx.data();
+    let startup = get_startup_time();
+
+    let uptime = uptime().unwrap_or_default();
+    let mut content = format!(
+        "**Started:** {} ({})\n**Uptime:** {}\n",
+        startup.short_date_time(),
+        startup.relative(),
+        format_duration_short(uptime),
+    );
+
+    if data.config().azur_lane_data.is_none() {
+        content.push_str("**Azur Lane Data:** Disabled\n");
+    } else if let Some(azur) = data.azur_lane_if_loaded() {
+        write!(
+            content,
+            "**Azur Lane Data:** Loaded\n\
+            - **Ships:** {}\n\
+            - **Equipment:** {}\n\
+            - **Augment Modules:** {}\n",
+            azur.ships().len(),
+            azur.equips().len(),
+            azur.augments().len(),
+        ).discard();
+    } else {
+        content.push_str("**Azur Lane Data:** Not yet loaded\n");
+    }
+
+    let embed = CreateEmbed::new()
+        .title("Status")
+        .description(content)
+        .color(DEFAULT_EMBED_COLOR);
+
+    ctx.send(ctx.create_ephemeral_reply().embed(embed)).await?;
+    Ok(())
+}
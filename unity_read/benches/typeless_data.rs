@@ -0,0 +1,72 @@
+//! Benchmarks for parsing large `TypelessData` arrays, such as texture and index buffers.
+//!
+//! `Vec<u8>` takes the single-block fast path, while `Vec<i8>` of the same length is still
+//! parsed element by element, so the two together show the gain from the fast path.
+
+// the regular dependencies of the crate are also linked here
+#![allow(unused_crate_dependencies)]
+
+use std::hint::black_box;
+use std::io::Cursor;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use unity_read::classes::UnityClass;
+use unity_read::serialized_file::TypeTreeNode;
+
+/// Buffer sizes in bytes. The largest roughly matches a big mesh's index buffer.
+const SIZES: &[usize] = &[4 * 1024, 256 * 1024, 4 * 1024 * 1024];
+
+fn node(type_name: &str, name: &str, size: i32, level: u8) -> TypeTreeNode {
+    TypeTreeNode {
+        type_name: type_name.to_owned(),
+        name: name.to_owned(),
+        size,
+        level,
+        ..TypeTreeNode::default()
+    }
+}
+
+fn typeless_data(element: &str) -> Vec<TypeTreeNode> {
+    vec![
+        node("TypelessData", "m_Data", -1, 0),
+        node("int", "size", 4, 1),
+        node(element, "data", 1, 1),
+    ]
+}
+
+/// Builds length-prefixed little-endian array data.
+fn array_data(len: usize) -> Vec<u8> {
+    let mut data = u32::try_from(len).unwrap().to_le_bytes().to_vec();
+    data.extend((0..=u8::MAX).cycle().take(len));
+    data
+}
+
+fn typeless(c: &mut Criterion) {
+    let u8_tree = typeless_data("UInt8");
+    let i8_tree = typeless_data("SInt8");
+
+    let mut group = c.benchmark_group("typeless_data");
+    for &size in SIZES {
+        let data = array_data(size);
+        group.throughput(Throughput::Bytes(u64::try_from(size).unwrap()));
+
+        group.bench_with_input(BenchmarkId::new("u8_block", size), &data, |b, data| {
+            b.iter(|| {
+                let r = &mut Cursor::new(black_box(&data[..]));
+                <Vec<u8>>::parse_tree(r, false, &u8_tree[0], &u8_tree[1..]).unwrap()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("i8_per_element", size), &data, |b, data| {
+            b.iter(|| {
+                let r = &mut Cursor::new(black_box(&data[..]));
+                <Vec<i8>>::parse_tree(r, false, &i8_tree[0], &i8_tree[1..]).unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, typeless);
+criterion_main!(benches);
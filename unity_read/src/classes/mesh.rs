@@ -95,7 +95,7 @@ pub struct MeshVertexData<'t> {
 
 impl Mesh {
     /// Reads the mesh's vertex data.
-    pub fn read_vertex_data<'t, 'fs: 't>(&'t self, fs: &'fs UnityFsFile<'fs>) -> anyhow::Result<MeshVertexData<'t>> {
+    pub fn read_vertex_data<'t, 'fs: 't>(&'t self, fs: &'fs UnityFsFile<'fs>) -> Result<MeshVertexData<'t>, UnityError> {
         Ok(MeshVertexData {
             mesh: self,
             data: self.stream_data.load_data_or_else(fs, || &self.vertex_data.data_size)?
@@ -105,7 +105,7 @@ impl Mesh {
 
 impl MeshVertexData<'_> {
     // Only assuming Unity 2018 and newer.
-    pub fn resolve_meshes(&self) -> anyhow::Result<Vec<ResolvedMesh>> {
+    pub fn resolve_meshes(&self) -> Result<Vec<ResolvedMesh>, UnityError> {
         // Would you believe me if this handles barely anything a mesh can store?
         let (index_size, index_buffer) = self.load_index_buffer()?;
         let streams = self.load_streams()?;
@@ -204,7 +204,7 @@ impl MeshVertexData<'_> {
             cursor
         }
 
-        fn read_f32_vector<const N: usize>(cursor: &mut Cursor<&[u8]>, t: u8) -> anyhow::Result<[f32; N]> {
+        fn read_f32_vector<const N: usize>(cursor: &mut Cursor<&[u8]>, t: u8) -> Result<[f32; N], UnityError> {
             match t {
                 0 => read_vector_of::<f32, N>(cursor),
                 1 => read_vector_of::<ReadF16, N>(cursor).map(NormFloat::to_f32_array),
@@ -218,7 +218,7 @@ impl MeshVertexData<'_> {
             }
         }
 
-        fn read_vector_of<T, const N: usize>(cursor: &mut Cursor<&[u8]>) -> anyhow::Result<[T; N]>
+        fn read_vector_of<T, const N: usize>(cursor: &mut Cursor<&[u8]>) -> Result<[T; N], UnityError>
         where
             T: Copy + Default + BinRead,
             for<'a> T::Args<'a>: Default,
@@ -232,7 +232,7 @@ impl MeshVertexData<'_> {
         }
     }
 
-    fn load_index_buffer(&self) -> anyhow::Result<(u32, Vec<u32>)> {
+    fn load_index_buffer(&self) -> Result<(u32, Vec<u32>), UnityError> {
         match self.mesh.index_format {
             0 => { // UInt16
                 Ok((2, self.mesh.index_buffer.chunks_exact(2)
@@ -250,7 +250,7 @@ impl MeshVertexData<'_> {
         }
     }
 
-    fn load_streams(&self) -> anyhow::Result<Vec<StreamInfo>> {
+    fn load_streams(&self) -> Result<Vec<StreamInfo>, UnityError> {
         let data_size: u32 = self.data.len().try_into()?;
         let vertex_data = &self.mesh.vertex_data;
 
@@ -326,6 +326,30 @@ impl ResolvedMesh {
             &self.vertices[t.2]
         ))
     }
+
+    /// Formats the mesh as a Wavefront OBJ.
+    ///
+    /// Every vertex is written as a `v` and `vt` line, and every triangle as an `f` line referencing both.
+    pub fn to_obj(&self) -> String {
+        use std::fmt::Write;
+
+        let mut result = String::new();
+        for v in &self.vertices {
+            writeln!(result, "v {} {} {}", v.pos.x, v.pos.y, v.pos.z).expect("writing to string cannot fail");
+        }
+
+        for v in &self.vertices {
+            writeln!(result, "vt {} {}", v.uv.x, v.uv.y).expect("writing to string cannot fail");
+        }
+
+        for &(a, b, c) in &self.triangle_data {
+            // OBJ indices are 1-based
+            let (a, b, c) = (a + 1, b + 1, c + 1);
+            writeln!(result, "f {a}/{a} {b}/{b} {c}/{c}").expect("writing to string cannot fail");
+        }
+
+        result
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -420,4 +444,41 @@ impl From<[f32; 3]> for Vector3f {
     fn from(value: [f32; 3]) -> Self {
         Vector3f { x: value[0], y: value[1], z: value[2] }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn vertex(x: f32, y: f32) -> Vertex {
+        Vertex {
+            pos: Vector3f { x, y, z: 0.0 },
+            uv: Vector3f { x, y, z: 0.0 },
+        }
+    }
+
+    #[test]
+    fn to_obj() {
+        let mesh = ResolvedMesh {
+            vertices: vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(1.0, 1.0),
+                vertex(0.0, 1.0),
+            ],
+            triangle_data: vec![(0, 1, 2), (0, 2, 3)],
+        };
+
+        assert_eq!(mesh.to_obj(), "\
+            v 0 0 0\n\
+            v 1 0 0\n\
+            v 1 1 0\n\
+            v 0 1 0\n\
+            vt 0 0\n\
+            vt 1 0\n\
+            vt 1 1\n\
+            vt 0 1\n\
+            f 1/1 2/2 3/3\n\
+            f 1/1 3/3 4/4\n");
+    }
+}
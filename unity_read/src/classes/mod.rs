@@ -1,6 +1,6 @@
 //! Provides access to Unity class/object data.
 
-use std::io::{Cursor, Seek, SeekFrom};
+use std::io::{Cursor, Read, Seek, SeekFrom};
 
 use crate::object::ObjectRef;
 use crate::unity_fs::SeekRead;
@@ -27,10 +27,24 @@ pub trait UnityClass: Default {
     /// Parses a tree into a structure.
     ///
     /// `tree` holds the necessary part of the tree to parse children.
-    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<Self>;
+    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Self, UnityError>;
+
+    /// Parses `len` consecutive elements of an array.
+    ///
+    /// By default, this parses every element individually.
+    /// Types with a more efficient way to read bulk data may override this.
+    #[doc(hidden)]
+    fn parse_array(r: &mut Cursor<&[u8]>, is_big_endian: bool, len: u32, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Vec<Self>, UnityError> {
+        let mut result = Vec::new();
+        for _ in 0 .. len {
+            result.push(Self::parse_tree(r, is_big_endian, root, tree)?);
+        }
+
+        Ok(result)
+    }
 
     /// Tries to load a structure from an object reference.
-    fn try_from_obj(obj: &ObjectRef) -> anyhow::Result<Self> {
+    fn try_from_obj(obj: &ObjectRef) -> Result<Self, UnityError> {
         let cursor = &mut Cursor::new(obj.data()?);
         if let Some((root, tree)) = obj.ser_type.type_tree.split_first() {
             Self::parse_tree(cursor, obj.is_big_endian(), root, tree)
@@ -41,13 +55,13 @@ pub trait UnityClass: Default {
 
     /// Aligns the reader to the next 4-byte boundary.
     #[doc(hidden)]
-    fn align_reader(r: &mut Cursor<&[u8]>) -> anyhow::Result<()> {
+    fn align_reader(r: &mut Cursor<&[u8]>) -> Result<(), UnityError> {
         Ok(r.align_to(4)?)
     }
 
     /// Skips the object the current reader is at.
     #[doc(hidden)]
-    fn skip(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<()> {
+    fn skip(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<(), UnityError> {
         if root.size >= 0 {
             r.seek(SeekFrom::Current(i64::from(root.size)))?;
         } else {
@@ -91,12 +105,12 @@ pub trait AutoUnityClass: UnityClass {
     const TYPE_NAME: &'static str;
 
     /// Like [`UnityClass::parse_tree`], but fills an existing object.
-    fn parse_tree_into(&mut self, r: &mut Cursor<&[u8]>, is_big_endian: bool, tree: &[TypeTreeNode]) -> anyhow::Result<()>;
+    fn parse_tree_into(&mut self, r: &mut Cursor<&[u8]>, is_big_endian: bool, tree: &[TypeTreeNode]) -> Result<(), UnityError>;
 }
 
 impl<T: AutoUnityClass> UnityClass for T {
     // blanket implementation for `define_unity_class` generates types.
-    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<Self> {
+    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Self, UnityError> {
         if root.type_name.as_str() != Self::TYPE_NAME {
             Err(UnityError::Mismatch(UnityMismatch {
                 expected: Self::TYPE_NAME.to_owned(),
@@ -179,7 +193,7 @@ macro_rules! define_unity_class {
         impl $crate::classes::AutoUnityClass for $Type {
             const TYPE_NAME: &'static str = $type_key;
 
-            fn parse_tree_into(&mut self, r: &mut ::std::io::Cursor<&[u8]>, is_big_endian: bool, tree: &[$crate::serialized_file::TypeTreeNode]) -> anyhow::Result<()> {
+            fn parse_tree_into(&mut self, r: &mut ::std::io::Cursor<&[u8]>, is_big_endian: bool, tree: &[$crate::serialized_file::TypeTreeNode]) -> ::core::result::Result<(), $crate::UnityError> {
                 let mut rest = tree;
                 while let Some((next, children, siblings)) = $crate::classes::split_tree(rest) {
                     match next.name.as_str() {
@@ -211,7 +225,7 @@ macro_rules! check_mismatch {
 }
 
 impl UnityClass for String {
-    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<Self> {
+    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Self, UnityError> {
         check_mismatch!(root, "string");
 
         // string should always have an Array of char nested
@@ -225,7 +239,7 @@ impl UnityClass for String {
 }
 
 impl<T: UnityClass> UnityClass for Option<T> {
-    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<Self> {
+    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Self, UnityError> {
         // Just delegates to the inner type and wraps it in Some
         // This is mostly intended for cases when the field might be missing in the data
         T::parse_tree(r, is_big_endian, root, tree).map(Some)
@@ -233,7 +247,7 @@ impl<T: UnityClass> UnityClass for Option<T> {
 }
 
 impl<T: UnityClass> UnityClass for Vec<T> {
-    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<Self> {
+    fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Self, UnityError> {
         if matches!(root.type_name.as_str(), "vector" | "string") {
             let (next, children) = tree.split_first()
                 .ok_or(UnityError::InvalidData("vector type data does not contain children"))?;
@@ -256,10 +270,7 @@ impl<T: UnityClass> UnityClass for Vec<T> {
             .and_then(|o| o.split_first())
             .ok_or(UnityError::InvalidData("array type data does not contain data element"))?;
 
-        let mut result = Vec::new();
-        for _ in 0 .. len {
-            result.push(T::parse_tree(r, is_big_endian, next, children)?);
-        }
+        let result = T::parse_array(r, is_big_endian, len, next, children)?;
 
         if root.needs_align_after() {
             Self::align_reader(r)?;
@@ -270,9 +281,9 @@ impl<T: UnityClass> UnityClass for Vec<T> {
 }
 
 macro_rules! impl_unity_class_primitive {
-    ($Type:ty, $expected:literal $(| $extra:literal)*) => {
+    ($Type:ty, $expected:literal $(| $extra:literal)* $(, { $($body:tt)* })?) => {
         impl UnityClass for $Type {
-            fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, _tree: &[TypeTreeNode]) -> anyhow::Result<Self> {
+            fn parse_tree(r: &mut Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, _tree: &[TypeTreeNode]) -> Result<Self, UnityError> {
                 check_mismatch!(root, $expected $(| $extra)*);
 
                 let value = <$Type>::read_endian(r, is_big_endian)?;
@@ -282,12 +293,35 @@ macro_rules! impl_unity_class_primitive {
 
                 Ok(value)
             }
+
+            $($($body)*)?
         }
     };
 }
 
 impl_unity_class_primitive!(i8, "SInt8");
-impl_unity_class_primitive!(u8, "UInt8" | "char");
+impl_unity_class_primitive!(u8, "UInt8" | "char", {
+    fn parse_array(r: &mut Cursor<&[u8]>, is_big_endian: bool, len: u32, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Vec<Self>, UnityError> {
+        check_mismatch!(root, "UInt8" | "char");
+
+        // Bytes that need alignment individually can't be read as one block.
+        // This shouldn't happen in practice, but fall back to per-element parsing.
+        if root.needs_align_after() {
+            return (0 .. len).map(|_| Self::parse_tree(r, is_big_endian, root, tree)).collect();
+        }
+
+        // Check the length up-front so corrupt data doesn't cause a huge allocation.
+        let len = usize::try_from(len)?;
+        let remaining = r.get_ref().len().saturating_sub(usize::try_from(r.position())?);
+        if len > remaining {
+            Err(UnityError::UnexpectedEof)?
+        }
+
+        let mut result = vec![0u8; len];
+        r.read_exact(&mut result)?;
+        Ok(result)
+    }
+});
 impl_unity_class_primitive!(i16, "SInt16" | "short");
 impl_unity_class_primitive!(u16, "UInt16" | "unsigned short");
 impl_unity_class_primitive!(i32, "SInt32" | "int");
@@ -296,3 +330,55 @@ impl_unity_class_primitive!(i64, "SInt64" | "long long");
 impl_unity_class_primitive!(u64, "UInt64" | "unsigned long long" | "FileSize");
 impl_unity_class_primitive!(f32, "float");
 impl_unity_class_primitive!(f64, "double");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn node(type_name: &str, name: &str, size: i32, level: u8) -> TypeTreeNode {
+        TypeTreeNode {
+            type_name: type_name.to_owned(),
+            name: name.to_owned(),
+            size,
+            level,
+            ..TypeTreeNode::default()
+        }
+    }
+
+    fn typeless_data(element: &str) -> Vec<TypeTreeNode> {
+        vec![
+            node("TypelessData", "m_Data", -1, 0),
+            node("int", "size", 4, 1),
+            node(element, "data", 1, 1),
+        ]
+    }
+
+    #[test]
+    fn vec_u8_block() {
+        let data = [4u8, 0, 0, 0, 1, 2, 3, 4, 5];
+        let tree = typeless_data("UInt8");
+        let r = &mut Cursor::new(&data[..]);
+
+        let result = <Vec<u8>>::parse_tree(r, false, &tree[0], &tree[1..]).unwrap();
+        assert_eq!(result, [1, 2, 3, 4]);
+        assert_eq!(r.position(), 8);
+    }
+
+    #[test]
+    fn vec_u8_eof() {
+        let data = [8u8, 0, 0, 0, 1, 2, 3, 4];
+        let tree = typeless_data("UInt8");
+        let r = &mut Cursor::new(&data[..]);
+
+        assert!(<Vec<u8>>::parse_tree(r, false, &tree[0], &tree[1..]).is_err());
+    }
+
+    #[test]
+    fn vec_u8_mismatch() {
+        let data = [1u8, 0, 0, 0, 1, 0];
+        let tree = typeless_data("UInt16");
+        let r = &mut Cursor::new(&data[..]);
+
+        assert!(<Vec<u8>>::parse_tree(r, false, &tree[0], &tree[1..]).is_err());
+    }
+}
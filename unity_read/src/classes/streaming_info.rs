@@ -16,7 +16,7 @@ define_unity_class! {
 pub struct Offset(pub u64);
 
 impl UnityClass for Offset {
-    fn parse_tree(r: &mut std::io::Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> anyhow::Result<Self> {
+    fn parse_tree(r: &mut std::io::Cursor<&[u8]>, is_big_endian: bool, root: &TypeTreeNode, tree: &[TypeTreeNode]) -> Result<Self, UnityError> {
         u32::parse_tree(r, is_big_endian, root, tree).map(u64::from)
             .or_else(|_| u64::parse_tree(r, is_big_endian, root, tree))
             .map(Offset)
@@ -29,7 +29,7 @@ impl StreamingInfo {
     }
 
     /// Loads the streaming data.
-    pub fn load_data<'a>(&self, fs: &'a UnityFsFile<'a>) -> anyhow::Result<&'a [u8]> {
+    pub fn load_data<'a>(&self, fs: &'a UnityFsFile<'a>) -> Result<&'a [u8], UnityError> {
         let path = self.path.split('/').last().ok_or(UnityError::InvalidData("streaming data path incorrect"))?;
         let node = fs.entries().find(|e| e.path().as_str() == path).ok_or(UnityError::InvalidData("streaming data file not found"))?;
 
@@ -43,7 +43,7 @@ impl StreamingInfo {
         Ok(slice)
     }
 
-    pub fn load_data_or_else<'t, 'fs: 't>(&self, fs: &'fs UnityFsFile<'fs>, fallback: impl FnOnce() -> &'t [u8]) -> anyhow::Result<&'t [u8]> {
+    pub fn load_data_or_else<'t, 'fs: 't>(&self, fs: &'fs UnityFsFile<'fs>, fallback: impl FnOnce() -> &'t [u8]) -> Result<&'t [u8], UnityError> {
         if self.path.is_empty() {
             Ok(fallback())
         } else {
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use crate::define_unity_class;
 
 define_unity_class! {
@@ -7,3 +9,82 @@ define_unity_class! {
         pub script: Vec<u8> = "m_Script",
     }
 }
+
+/// A best-effort guess at the format of a [`TextAsset`]'s script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAssetFormat {
+    /// Starts with `{` or `[`.
+    Json,
+    /// Starts with a comment or a `local` or `return` statement.
+    Lua,
+    /// Any other valid UTF-8 text.
+    Text,
+    /// The data isn't valid UTF-8.
+    Binary,
+}
+
+impl TextAsset {
+    /// Gets the script as text.
+    ///
+    /// If the script isn't valid UTF-8, invalid sequences are replaced.
+    pub fn script_str(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.script)
+    }
+
+    /// Whether the script is valid UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        std::str::from_utf8(&self.script).is_ok()
+    }
+
+    /// Guesses the format of the script by sniffing its start.
+    ///
+    /// A leading UTF-8 byte order mark and whitespace are ignored.
+    pub fn script_format(&self) -> TextAssetFormat {
+        let Ok(text) = std::str::from_utf8(&self.script) else {
+            return TextAssetFormat::Binary
+        };
+
+        let text = text.trim_start_matches('\u{FEFF}').trim_start();
+        if text.starts_with(['{', '[']) {
+            TextAssetFormat::Json
+        } else if text.starts_with("--") || text.starts_with("local") || text.starts_with("return") {
+            TextAssetFormat::Lua
+        } else {
+            TextAssetFormat::Text
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn asset(script: &[u8]) -> TextAsset {
+        TextAsset {
+            name: "test".to_owned(),
+            script: script.to_vec(),
+        }
+    }
+
+    #[test]
+    fn json() {
+        let asset = asset(b"\xEF\xBB\xBF\r\n  {\"key\": [1, 2]}");
+        assert!(asset.is_utf8());
+        assert_eq!(asset.script_format(), TextAssetFormat::Json);
+        assert_eq!(asset.script_str(), "\u{FEFF}\r\n  {\"key\": [1, 2]}");
+    }
+
+    #[test]
+    fn lua() {
+        let asset = asset(b"-- comment\nreturn {}");
+        assert_eq!(asset.script_format(), TextAssetFormat::Lua);
+    }
+
+    #[test]
+    fn binary() {
+        let asset = asset(b"\x1BLuaS\x00\x19\x93\r\n");
+        assert!(!asset.is_utf8());
+        assert_eq!(asset.script_format(), TextAssetFormat::Binary);
+        assert_eq!(asset.script_str(), "\u{1B}LuaS\0\u{19}\u{FFFD}\r\n");
+    }
+}
@@ -31,7 +31,7 @@ impl Texture2D {
     }
 
     /// Reads the texture data.
-    pub fn read_data<'t, 'fs: 't>(&'t self, fs: &'fs UnityFsFile<'fs>) -> anyhow::Result<Texture2DData<'t>> {
+    pub fn read_data<'t, 'fs: 't>(&'t self, fs: &'fs UnityFsFile<'fs>) -> Result<Texture2DData<'t>, UnityError> {
         Ok(Texture2DData {
             texture: self,
             data: self.stream_data.load_data_or_else(fs, || &self.image_data)?
@@ -46,7 +46,7 @@ impl Texture2DData<'_> {
     }
 
     /// Decodes the image data.
-    pub fn decode(&self) -> anyhow::Result<RgbaImage> {
+    pub fn decode(&self) -> Result<RgbaImage, UnityError> {
         let width = u32::try_from(self.texture.width)?;
         let height = u32::try_from(self.texture.height)?;
 
@@ -70,6 +70,19 @@ impl Texture2DData<'_> {
             ))?,
         }
     }
+
+    /// Whether an image decoded from this data has meaningful alpha.
+    ///
+    /// This is `false` if the texture format has no alpha channel or every pixel is fully opaque,
+    /// in which case the image can be encoded without alpha.
+    pub fn has_alpha(&self, image: &RgbaImage) -> bool {
+        self.texture.format().has_alpha_channel() && has_transparent_pixels(image)
+    }
+}
+
+/// Whether any pixel of the image isn't fully opaque.
+fn has_transparent_pixels(image: &RgbaImage) -> bool {
+    image.pixels().any(|p| p.0[3] != u8::MAX)
 }
 
 /// Stores validated image arguments.
@@ -81,7 +94,7 @@ struct Args {
 
 impl Args {
     /// Creates a new [`Args`], validating the width, height, and total size.
-    fn new(width: u32, height: u32) -> anyhow::Result<Args> {
+    fn new(width: u32, height: u32) -> Result<Args, UnityError> {
         let width = usize::try_from(width)?;
         let height = usize::try_from(height)?;
         let size = width.checked_mul(height)
@@ -93,7 +106,7 @@ impl Args {
     }
 
     /// Decodes the image with a given decoder function.
-    fn decode_with<F>(self, decode: F) -> anyhow::Result<RgbaImage>
+    fn decode_with<F>(self, decode: F) -> Result<RgbaImage, UnityError>
     where
         F: FnOnce(&Self, &mut [u32]) -> Result<(), UnityError>,
     {
@@ -135,6 +148,24 @@ impl Args {
     }
 }
 
+impl TextureFormat {
+    /// Whether the format stores an alpha channel.
+    ///
+    /// Unknown formats are assumed to have one.
+    pub fn has_alpha_channel(self) -> bool {
+        !matches!(
+            self,
+            Self::RGB24 | Self::RGB565 | Self::R16 | Self::DXT1 | Self::RHalf | Self::RGHalf
+                | Self::RFloat | Self::RGFloat | Self::YUY2 | Self::RGB9e5Float | Self::BC4 | Self::BC5
+                | Self::BC6H | Self::DXT1Crunched | Self::PVRTC_RGB2 | Self::PVRTC_RGB4 | Self::ETC_RGB4
+                | Self::ATC_RGB4 | Self::EAC_R | Self::EAC_R_SIGNED | Self::EAC_RG | Self::EAC_RG_SIGNED
+                | Self::ETC2_RGB | Self::ASTC_RGB_4x4 | Self::ASTC_RGB_5x5 | Self::ASTC_RGB_6x6
+                | Self::ASTC_RGB_8x8 | Self::ASTC_RGB_10x10 | Self::ASTC_RGB_12x12 | Self::ETC_RGB4_3DS
+                | Self::RG16 | Self::R8 | Self::ETC_RGB4Crunched
+        )
+    }
+}
+
 /// Well-known texture 2D formats.
 #[allow(non_camel_case_types, non_upper_case_globals)]
 #[derive(Debug, Eq, PartialEq, FromPrimitive, Clone, Copy, Default, Hash)]
@@ -207,3 +238,28 @@ pub enum TextureFormat {
     ASTC_HDR_10x10,
     ASTC_HDR_12x12,
 }
+
+#[cfg(test)]
+mod test {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn format_alpha_channel() {
+        assert!(TextureFormat::RGBA32.has_alpha_channel());
+        assert!(TextureFormat::ETC2_RGBA8.has_alpha_channel());
+        assert!(TextureFormat::UnknownType.has_alpha_channel());
+        assert!(!TextureFormat::RGB24.has_alpha_channel());
+        assert!(!TextureFormat::ETC2_RGB.has_alpha_channel());
+    }
+
+    #[test]
+    fn transparent_pixels() {
+        let mut image = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+        assert!(!has_transparent_pixels(&image));
+
+        image.put_pixel(2, 3, Rgba([10, 20, 30, 254]));
+        assert!(has_transparent_pixels(&image));
+    }
+}
@@ -15,12 +15,27 @@ pub mod serialized_file;
 mod unity_fs_common_str;
 pub mod unity_fs;
 
-#[derive(Debug, Clone)]
+// only used by the benchmarks
+#[cfg(test)]
+use criterion as _;
+
+/// The error type for reading UnityFS archives and their objects.
+#[derive(Debug)]
 pub enum UnityError {
     UnexpectedEof,
     InvalidData(&'static str),
     Mismatch(UnityMismatch),
     Unsupported(String),
+    /// An underlying IO error.
+    Io(std::io::Error),
+    /// An underlying binary parsing error.
+    Parse(binrw::Error),
+    /// A value in the data is out of range for its target integer type.
+    IntOutOfRange(std::num::TryFromIntError),
+    /// A string in the data isn't valid UTF-8.
+    Utf8(std::string::FromUtf8Error),
+    /// LZMA-compressed data couldn't be decompressed.
+    Lzma(lzma_rs::error::Error),
 }
 
 #[derive(Debug, Clone)]
@@ -31,28 +46,126 @@ pub struct UnityMismatch {
 
 impl Display for UnityError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Debug::fmt(self, f)
+        match self {
+            // the inner errors are exposed via `source`, so don't repeat them here
+            Self::Io(_) => f.write_str("IO error"),
+            Self::Parse(_) => f.write_str("binary parse error"),
+            Self::IntOutOfRange(_) => f.write_str("integer value out of range"),
+            Self::Utf8(_) => f.write_str("invalid UTF-8 string"),
+            Self::Lzma(_) => f.write_str("LZMA decompression error"),
+            _ => Debug::fmt(self, f),
+        }
     }
 }
 
-impl Error for UnityError {}
+impl Error for UnityError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            Self::IntOutOfRange(err) => Some(err),
+            Self::Utf8(err) => Some(err),
+            Self::Lzma(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for UnityError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<binrw::Error> for UnityError {
+    fn from(value: binrw::Error) -> Self {
+        match value {
+            binrw::Error::Io(err) => Self::Io(err),
+            err => Self::Parse(err),
+        }
+    }
+}
+
+impl From<std::num::TryFromIntError> for UnityError {
+    fn from(value: std::num::TryFromIntError) -> Self {
+        Self::IntOutOfRange(value)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for UnityError {
+    fn from(value: std::string::FromUtf8Error) -> Self {
+        Self::Utf8(value)
+    }
+}
+
+impl From<lzma_rs::error::Error> for UnityError {
+    fn from(value: lzma_rs::error::Error) -> Self {
+        match value {
+            lzma_rs::error::Error::IoError(err) => Self::Io(err),
+            err => Self::Lzma(err),
+        }
+    }
+}
 
 /// Extension type to allow specifying the endianness of the read with a bool.
+///
+/// This also covers fixed-size arrays like `[T; N]`, with the endianness applying to each element.
 trait BinReadEndian: Sized {
     /// Reads `Self` from the reader, given whether to read as big-endian.
-    fn read_endian<R: Read + Seek>(reader: &mut R, is_big_endian: bool) -> binrw::BinResult<Self>;
+    fn read_endian<R: Read + Seek>(reader: &mut R, is_big_endian: bool) -> Result<Self, UnityError>;
 }
 
 impl<T: binrw::BinRead> BinReadEndian for T
 where
     for<'a> T::Args<'a>: Default,
 {
-    fn read_endian<R: Read + Seek>(reader: &mut R, is_big_endian: bool) -> binrw::BinResult<Self> {
+    fn read_endian<R: Read + Seek>(reader: &mut R, is_big_endian: bool) -> Result<Self, UnityError> {
         let endian = match is_big_endian {
             true => binrw::Endian::Big,
             false => binrw::Endian::Little,
         };
 
-        T::read_options(reader, endian, T::Args::default())
+        Ok(T::read_options(reader, endian, T::Args::default())?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+    use std::io::Cursor;
+
+    use super::{BinReadEndian, UnityError};
+
+    #[test]
+    fn read_endian_array() {
+        let data = [0x12u8, 0x34, 0x56, 0x78];
+
+        let little = <[u16; 2]>::read_endian(&mut Cursor::new(&data), false).unwrap();
+        assert_eq!(little, [0x3412, 0x7856]);
+
+        let big = <[u16; 2]>::read_endian(&mut Cursor::new(&data), true).unwrap();
+        assert_eq!(big, [0x1234, 0x5678]);
+
+        let bytes = <[u8; 4]>::read_endian(&mut Cursor::new(&data), true).unwrap();
+        assert_eq!(bytes, data);
+    }
+
+    #[test]
+    fn int_out_of_range_source() {
+        let err = UnityError::from(u8::try_from(256u32).unwrap_err());
+        assert!(matches!(err, UnityError::IntOutOfRange(_)));
+        assert!(err.source().unwrap().is::<std::num::TryFromIntError>());
+        assert_eq!(err.to_string(), "integer value out of range");
+    }
+
+    #[test]
+    fn read_endian_eof_source() {
+        let data = [0x12u8];
+
+        let err = u32::read_endian(&mut Cursor::new(&data), false).unwrap_err();
+        assert!(matches!(err, UnityError::Io(_)));
+
+        let source = err.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(source.kind(), std::io::ErrorKind::UnexpectedEof);
     }
 }
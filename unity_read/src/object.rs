@@ -1,9 +1,11 @@
 //! Provides access to UnityFS object information.
 
+use std::io::Cursor;
+
 use num_enum::FromPrimitive;
 
 use crate::serialized_file::{SerializedFile, SerializedType};
-use crate::classes::{ClassID, UnityClass};
+use crate::classes::{split_tree, ClassID, UnityClass};
 use crate::UnityError;
 
 /// Internal struct with object data.
@@ -41,7 +43,10 @@ impl ObjectRef<'_> {
     }
 
     /// Gets the block of memory with the object data.
-    pub fn data(&self) -> anyhow::Result<&[u8]> {
+    ///
+    /// This is a sub-slice of the file's buffer and does not copy.
+    /// Computing it only involves bounds checks, so there is no need to cache it.
+    pub fn data(&self) -> Result<&[u8], UnityError> {
         let offset = usize::try_from(self.object.start + self.file.data_offset)?;
         let size = usize::try_from(self.object.size)?;
 
@@ -52,8 +57,61 @@ impl ObjectRef<'_> {
         Ok(data)
     }
 
+    /// Reads only the object's name, without parsing the rest of the object.
+    ///
+    /// Unity places `m_Name` as the first field for classes that have one.
+    /// If the first field is not named `m_Name`, returns [`None`].
+    pub fn read_name(&self) -> Result<Option<String>, UnityError> {
+        let Some((_, tree)) = self.ser_type.type_tree.split_first() else {
+            Err(UnityError::InvalidData("type tree is unexpectedly empty"))?
+        };
+
+        let Some((next, children, _)) = split_tree(tree) else {
+            return Ok(None)
+        };
+
+        if next.name != "m_Name" {
+            return Ok(None)
+        }
+
+        let cursor = &mut Cursor::new(self.data()?);
+        let name = String::parse_tree(cursor, self.is_big_endian(), next, children)?;
+        Ok(Some(name))
+    }
+
     /// Tries to read the object into the specified type.
-    pub fn try_into_class<T: UnityClass>(&self) -> anyhow::Result<T> {
+    pub fn try_into_class<T: UnityClass>(&self) -> Result<T, UnityError> {
         T::try_from_obj(self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn data_aliases_file() {
+        let buf = [0u8; 32];
+        let mut file = SerializedFile::default();
+        file.buf = &buf;
+        file.data_offset = 8;
+
+        let ser_type = SerializedType::default();
+        let obj = ObjectRef {
+            file: &file,
+            ser_type: &ser_type,
+            object: ObjectInfo { path_id: 1, start: 4, size: 16, type_id: 0, class_id: None },
+        };
+
+        let data = obj.data().unwrap();
+        assert_eq!(data.len(), 16);
+        assert!(std::ptr::eq(data, &buf[12..28]));
+
+        let obj = ObjectRef {
+            object: ObjectInfo { start: 12, ..obj.object.clone() },
+            ..obj
+        };
+
+        assert!(obj.data().is_err());
+    }
+}
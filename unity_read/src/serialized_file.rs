@@ -1,8 +1,10 @@
 //! Structs for using serialized files within UnityFS.
 
+use std::collections::HashMap;
 use std::io::{Cursor, Read};
 
 use binrw::{binread, BinRead, NullString};
+use once_cell::sync::OnceCell;
 
 use crate::object::{ObjectInfo, ObjectRef};
 use crate::unity_fs::SeekRead;
@@ -22,7 +24,9 @@ pub struct SerializedFile<'a> {
     enable_type_tree: bool,
     big_id_enabled: bool,
     types: Vec<SerializedType>,
-    objects: Vec<ObjectInfo>
+    objects: Vec<ObjectInfo>,
+    /// Maps path IDs to indices into `objects`. Built on first use.
+    path_id_index: OnceCell<HashMap<i64, usize>>,
 }
 
 /// Information about a serialized type.
@@ -50,15 +54,36 @@ pub struct TypeTreeNode {
 
 impl<'a> SerializedFile<'a> {
     /// Enumerates the objects listed within this file.
-    pub fn objects(&'a self) -> impl Iterator<Item = anyhow::Result<ObjectRef<'a>>> {
-        self.objects.iter().map(|obj| Ok(ObjectRef {
+    pub fn objects(&'a self) -> impl Iterator<Item = Result<ObjectRef<'a>, UnityError>> {
+        self.objects.iter().map(|obj| self.object_ref(obj))
+    }
+
+    /// Gets an object by its path ID.
+    ///
+    /// The first call builds an index over all objects, so later lookups don't need to
+    /// walk the object list. Returns [`None`] if there is no object with that path ID.
+    pub fn object_by_path_id(&'a self, path_id: i64) -> Option<Result<ObjectRef<'a>, UnityError>> {
+        let index = self.path_id_index.get_or_init(|| {
+            self.objects.iter()
+                .enumerate()
+                .map(|(index, obj)| (obj.path_id, index))
+                .collect()
+        });
+
+        let obj = self.objects.get(*index.get(&path_id)?)?;
+        Some(self.object_ref(obj))
+    }
+
+    /// Creates a reference to an object listed in this file.
+    fn object_ref(&'a self, obj: &ObjectInfo) -> Result<ObjectRef<'a>, UnityError> {
+        Ok(ObjectRef {
             file: self,
             ser_type: obj.class_id
                 .and_then(|c| self.types.iter().find(|t| t.class_id == i32::from(c)))
                 .or_else(|| self.types.get(usize::try_from(obj.type_id).ok()?))
                 .ok_or(UnityError::InvalidData("object data references invalid type"))?,
             object: obj.clone()
-        }))
+        })
     }
 
     /// Gets the serialized types.
@@ -90,12 +115,12 @@ impl<'a> SerializedFile<'a> {
     }
 
     /// Reads a buffer into a [`SerializedFile`] struct.
-    pub fn read(buf: &'a [u8]) -> anyhow::Result<Self> {
+    pub fn read(buf: &'a [u8]) -> Result<Self, UnityError> {
         let cursor = &mut Cursor::new(buf);
 
         let mut result = SerializedFile::default();
 
-        let main = HeaderMain::read(cursor)?;
+        let main = HeaderMain::read(cursor).map_err(UnityError::from)?;
         result.metadata_size = main.metadata_size;
         result.file_size = u64::from(main.file_size);
         result.version = main.version;
@@ -150,7 +175,7 @@ impl<'a> SerializedFile<'a> {
         Ok(result)
     }
 
-    fn read_serialized_type(&self, cursor: &mut LocalCursor, is_ref_type: bool) -> anyhow::Result<SerializedType> {
+    fn read_serialized_type(&self, cursor: &mut LocalCursor, is_ref_type: bool) -> Result<SerializedType, UnityError> {
         let mut result = SerializedType {
             class_id: i32::read_endian(cursor, self.is_big_endian)?,
             .. SerializedType::default()
@@ -168,11 +193,11 @@ impl<'a> SerializedFile<'a> {
             if (is_ref_type && result.script_type_index.is_some())
             || (self.version < 16 && result.class_id < 0)
             || (self.version >= 16 && result.class_id == 114 /* Script */) {
-                result.script_id = Some(BinRead::read(cursor)?);
+                result.script_id = Some(<[u8; 16]>::read_endian(cursor, self.is_big_endian)?);
             }
 
             // old type hash? Either way, 16 bytes to skip, we don't need this.
-            _ = <[u8; 16]>::read(cursor)?;
+            _ = <[u8; 16]>::read_endian(cursor, self.is_big_endian)?;
         }
 
         if self.enable_type_tree {
@@ -196,7 +221,7 @@ impl<'a> SerializedFile<'a> {
         Ok(result)
     }
 
-    fn read_type_tree_blob(&self, cursor: &mut LocalCursor) -> anyhow::Result<Vec<TypeTreeNode>> {
+    fn read_type_tree_blob(&self, cursor: &mut LocalCursor) -> Result<Vec<TypeTreeNode>, UnityError> {
         let node_count = u32::read_endian(cursor, self.is_big_endian)?;
         let str_buf_size = u32::read_endian(cursor, self.is_big_endian)?;
         let str_buf_size = usize::try_from(str_buf_size)?;
@@ -218,7 +243,7 @@ impl<'a> SerializedFile<'a> {
         let mut str_buf = vec![0u8; str_buf_size];
         cursor.read_exact(&mut str_buf)?;
 
-        fn read_str(cursor: &mut LocalCursor, offset: u32) -> anyhow::Result<String> {
+        fn read_str(cursor: &mut LocalCursor, offset: u32) -> Result<String, UnityError> {
             // If the last bit is set, the remainder indicates an index into a table
             // of common known strings rather than actually storing the data.
             Ok(if (offset & 0x8000_0000) == 0 {
@@ -242,12 +267,12 @@ impl<'a> SerializedFile<'a> {
                 version: u32::from(raw_node.version),
                 meta_flags: raw_node.meta_flags,
                 level: raw_node.level,
-            })).collect::<anyhow::Result<Vec<_>>>()?;
+            })).collect::<Result<Vec<_>, UnityError>>()?;
 
         Ok(nodes)
     }
 
-    fn read_type_tree(&self, cursor: &mut LocalCursor, level: u8) -> anyhow::Result<Vec<TypeTreeNode>> {
+    fn read_type_tree(&self, cursor: &mut LocalCursor, level: u8) -> Result<Vec<TypeTreeNode>, UnityError> {
         // this format is dogshit
         let mut node = TypeTreeNode {
             level,
@@ -282,7 +307,7 @@ impl<'a> SerializedFile<'a> {
         Ok(nodes)
     }
 
-    fn read_object_info(&self, cursor: &mut LocalCursor) -> anyhow::Result<ObjectInfo> {
+    fn read_object_info(&self, cursor: &mut LocalCursor) -> Result<ObjectInfo, UnityError> {
         let mut object = if self.big_id_enabled {
             // Big ID flag only exists from v7 to v13
             ObjectInfo::from(ObjectBlobBigId::read_endian(cursor, self.is_big_endian)?)
@@ -440,3 +465,60 @@ macro_rules! impl_obj_blob_to_info {
 impl_obj_blob_to_info!(ObjectBlob);
 impl_obj_blob_to_info!(ObjectBlobBigId);
 impl_obj_blob_to_info!(ObjectBlobV22);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates a version 9 serialized file with one type and objects with the given path IDs.
+    fn serialized_file(path_ids: &[i32]) -> Vec<u8> {
+        let mut meta = Vec::new();
+        meta.push(0); // empty unity_version
+        meta.extend(0u32.to_le_bytes()); // target_platform
+        meta.extend(1u32.to_le_bytes()); // type_count
+        meta.extend(28i32.to_le_bytes()); // class_id: Texture2D
+        meta.extend(0u32.to_le_bytes()); // big_id_enabled
+        meta.extend(u32::try_from(path_ids.len()).unwrap().to_le_bytes()); // object_count
+        for (index, path_id) in path_ids.iter().enumerate() {
+            meta.extend(path_id.to_le_bytes());
+            meta.extend(u32::try_from(index).unwrap().to_le_bytes()); // start
+            meta.extend(1u32.to_le_bytes()); // size
+            meta.extend(0u32.to_le_bytes()); // type_id
+            meta.extend(28i16.to_le_bytes()); // class_id
+            meta.extend(0u16.to_le_bytes()); // is_destroyed
+        }
+
+        let header_size = 20;
+        let data_offset = u32::try_from(header_size + meta.len()).unwrap();
+        let file_size = data_offset + u32::try_from(path_ids.len()).unwrap();
+
+        let mut buf = Vec::new();
+        buf.extend(u32::try_from(meta.len()).unwrap().to_be_bytes()); // metadata_size
+        buf.extend(file_size.to_be_bytes());
+        buf.extend(9u32.to_be_bytes()); // version
+        buf.extend(data_offset.to_be_bytes());
+        buf.extend([0u8; 4]); // little endian + reserved
+        buf.extend(meta);
+        buf.extend((0..path_ids.len()).map(|i| u8::try_from(i).unwrap())); // 1 byte of data per object
+        buf
+    }
+
+    #[test]
+    fn object_by_path_id() {
+        let buf = serialized_file(&[5, -3, 100]);
+        let file = SerializedFile::read(&buf).unwrap();
+
+        for (index, path_id) in [5i64, -3, 100].into_iter().enumerate() {
+            let obj = file.object_by_path_id(path_id).unwrap().unwrap();
+            assert_eq!(obj.path_id(), path_id);
+            assert_eq!(obj.data().unwrap(), [u8::try_from(index).unwrap()]);
+        }
+
+        assert!(file.object_by_path_id(0).is_none());
+        assert!(file.object_by_path_id(6).is_none());
+
+        // matches walking the objects
+        let walked: Vec<i64> = file.objects().map(|o| o.unwrap().path_id()).collect();
+        assert_eq!(walked, [5, -3, 100]);
+    }
+}
@@ -146,8 +146,8 @@ struct BlockOffset {
 
 impl<'a> UnityFsFile<'a> {
     /// Reads a UnityFS from a reader.
-    pub fn open(mut buf: &'a mut dyn SeekRead) -> anyhow::Result<Self> {
-        let header = UnityFsHeader::read(&mut buf)?;
+    pub fn open(mut buf: &'a mut dyn SeekRead) -> Result<Self, UnityError> {
+        let header = UnityFsHeader::read(&mut buf).map_err(UnityError::from)?;
 
         // Load blocks info
         let blocks_info = {
@@ -178,7 +178,7 @@ impl<'a> UnityFsFile<'a> {
             )?;
 
             let mut reader = Cursor::new(&*decompressed_data);
-            BlocksInfo::read(&mut reader)?
+            BlocksInfo::read(&mut reader).map_err(UnityError::from)?
         };
 
         let data_offset = buf.stream_position()?;
@@ -198,6 +198,18 @@ impl<'a> UnityFsFile<'a> {
         })
     }
 
+    /// Enumerates all serialized files within the file.
+    ///
+    /// Entries that aren't serialized files, such as resource data, are skipped.
+    /// Errors are yielded per entry and don't stop the iteration.
+    pub fn serialized_files(&'a self) -> impl Iterator<Item = Result<SerializedFile<'a>, UnityError>> {
+        self.entries().filter_map(|e| match e.read_raw() {
+            Ok(buf) if SerializedFile::is_serialized_file(buf) => Some(SerializedFile::read(buf)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+    }
+
     fn get_block_index_by_offset(&self, offset: u64) -> Option<BlockOffset> {
         let mut compressed_offset = 0u64;
         let mut uncompressed_offset = 0u64;
@@ -218,7 +230,7 @@ impl<'a> UnityFsFile<'a> {
 }
 
 impl<'a> UnityFsNode<'a> {
-    fn decompress(&self) -> anyhow::Result<Vec<u8>> {
+    fn decompress(&self) -> Result<Vec<u8>, UnityError> {
         let uncompressed_start = self.node.offset;
         let BlockOffset {
             index,
@@ -274,12 +286,12 @@ impl<'a> UnityFsNode<'a> {
     }
 
     /// Reads the raw binary data for this node.
-    pub fn read_raw(&self) -> anyhow::Result<&'a [u8]> {
+    pub fn read_raw(&self) -> Result<&'a [u8], UnityError> {
         Ok(self.node.uncompressed_cache.get_or_try_init(|| self.decompress())?)
     }
 
     /// Reads the data for this node.
-    pub fn read(&self) -> anyhow::Result<UnityFsData<'a>>{
+    pub fn read(&self) -> Result<UnityFsData<'a>, UnityError>{
         let buf = self.read_raw()?;
         if SerializedFile::is_serialized_file(buf) {
             Ok(UnityFsData::SerializedFile(SerializedFile::read(buf)?))
@@ -296,7 +308,7 @@ impl UnityFsNode<'_> {
     }
 }
 
-fn decompress_data(compressed_data: &[u8], compression: Compression, size: u32) -> anyhow::Result<Cow<[u8]>> {
+fn decompress_data(compressed_data: &[u8], compression: Compression, size: u32) -> Result<Cow<[u8]>, UnityError> {
     match compression {
         Compression::None => Ok(Cow::Borrowed(compressed_data)),
         Compression::Lz4 | Compression::Lz4Hc => Ok(Cow::Owned(lz4::block::decompress(compressed_data, Some(size.try_into()?))?)),
@@ -357,3 +369,123 @@ impl<T: std::fmt::Display> std::fmt::Display for DebugIgnore<T> {
         std::fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Creates a minimal version 9 serialized file without any types or objects.
+    fn serialized_file() -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend(17u32.to_be_bytes()); // metadata_size
+        buf.extend(37u32.to_be_bytes()); // file_size
+        buf.extend(9u32.to_be_bytes()); // version
+        buf.extend(37u32.to_be_bytes()); // data_offset
+        buf.extend([0u8; 4]); // little endian + reserved
+        buf.push(0); // empty unity_version
+        buf.extend(0u32.to_le_bytes()); // target_platform
+        buf.extend(0u32.to_le_bytes()); // type_count
+        buf.extend(0u32.to_le_bytes()); // big_id_enabled
+        buf.extend(0u32.to_le_bytes()); // object_count
+        assert!(buf.len() == 37);
+        buf
+    }
+
+    /// Creates a serialized file whose header is valid, but which is truncated after it.
+    fn truncated_serialized_file() -> Vec<u8> {
+        let mut buf = serialized_file();
+        buf.truncate(20);
+        buf[4..8].copy_from_slice(&20u32.to_be_bytes()); // file_size
+        buf[12..16].copy_from_slice(&20u32.to_be_bytes()); // data_offset
+        buf
+    }
+
+    /// Creates an uncompressed UnityFS file with one block holding all the nodes.
+    fn unity_fs(nodes: &[&[u8]]) -> Vec<u8> {
+        let data: Vec<u8> = nodes.concat();
+
+        let mut blocks_info = Vec::new();
+        blocks_info.extend([0u8; 16]); // data_hash
+        blocks_info.extend(1u32.to_be_bytes()); // blocks_count
+        blocks_info.extend(u32::try_from(data.len()).unwrap().to_be_bytes()); // uncompressed_size
+        blocks_info.extend(u32::try_from(data.len()).unwrap().to_be_bytes()); // compressed_size
+        blocks_info.extend(0u16.to_be_bytes()); // flags
+        blocks_info.extend(u32::try_from(nodes.len()).unwrap().to_be_bytes()); // nodes_count
+
+        let mut offset = 0u64;
+        for (index, node) in nodes.iter().enumerate() {
+            let size = u64::try_from(node.len()).unwrap();
+            blocks_info.extend(offset.to_be_bytes());
+            blocks_info.extend(size.to_be_bytes());
+            blocks_info.extend(0u32.to_be_bytes()); // flags
+            blocks_info.extend(format!("node{index}\0").as_bytes());
+            offset += size;
+        }
+
+        let blocks_info_size = u32::try_from(blocks_info.len()).unwrap().to_be_bytes();
+
+        let mut buf = Vec::new();
+        buf.extend(b"UnityFS\0");
+        buf.extend(6u32.to_be_bytes()); // version
+        buf.extend(b"5.x.x\0");
+        buf.extend(b"2021.3.0f1\0");
+        buf.extend(0i64.to_be_bytes()); // size
+        buf.extend(blocks_info_size); // compressed_blocks_info_size
+        buf.extend(blocks_info_size); // uncompressed_blocks_info_size
+        buf.extend(0u32.to_be_bytes()); // flags
+        buf.extend(blocks_info);
+        buf.extend(data);
+        buf
+    }
+
+    #[test]
+    fn serialized_files() {
+        let file = unity_fs(&[
+            &serialized_file(),
+            b"raw resource data",
+            &truncated_serialized_file(),
+            &serialized_file(),
+        ]);
+
+        let mut reader = Cursor::new(file);
+        let unity_fs = UnityFsFile::open(&mut reader).unwrap();
+        assert!(unity_fs.entries().count() == 4);
+
+        let results: Vec<bool> = unity_fs.serialized_files().map(|r| r.is_ok()).collect();
+        assert_eq!(results, [true, false, true]);
+    }
+
+    #[test]
+    fn streamed_resource_data() {
+        use crate::classes::{Offset, StreamingInfo};
+
+        let file = unity_fs(&[
+            &serialized_file(),
+            b"..pixel data..",
+        ]);
+
+        let mut reader = Cursor::new(file);
+        let unity_fs = UnityFsFile::open(&mut reader).unwrap();
+
+        // the path refers to another node within the same bundle
+        let info = StreamingInfo {
+            offset: Offset(2),
+            size: 10,
+            path: "archive:/node0/node1".to_owned(),
+        };
+
+        assert_eq!(info.load_data(&unity_fs).unwrap(), b"pixel data");
+        assert_eq!(info.load_data_or_else(&unity_fs, || b"inline".as_slice()).unwrap(), b"pixel data");
+
+        // data without a path is stored inline
+        let inline = StreamingInfo::default();
+        assert_eq!(inline.load_data_or_else(&unity_fs, || b"inline".as_slice()).unwrap(), b"inline");
+
+        // out of bounds or unknown files are errors, not panics
+        let out_of_bounds = StreamingInfo { size: 100, ..info.clone() };
+        assert!(out_of_bounds.load_data(&unity_fs).is_err());
+
+        let missing = StreamingInfo { path: "archive:/node0/missing.resS".to_owned(), ..info };
+        assert!(missing.load_data(&unity_fs).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Benchmarks for [`utils::fuzzy::Search`].
+//!
+//! The corpus is synthetic, but roughly matches the amount and shape of
+//! the ship and equipment names the bot searches through.
+
+// the regular dependencies of the crate are also linked here
+#![allow(unused_crate_dependencies)]
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use utils::fuzzy::Search;
+
+/// Roughly the amount of ships and equipment combined.
+const CORPUS_SIZE: usize = 2500;
+
+const SYLLABLES: &[&str] = &[
+    "ka", "ga", "ri", "ne", "to", "su", "mi", "ha", "ru", "ko",
+    "en", "ter", "pri", "se", "wa", "shi", "ro", "ya", "ma", "to",
+    "ber", "lin", "ton", "al", "vo", "dre", "ark", "ham", "pe", "ny",
+];
+
+const SUFFIXES: &[&str] = &[
+    "", "", "", " (Retrofit)", " META", " II", " Kai",
+    " 120mm Twin Gun", " 533mm Quadruple Torpedo", " Dive Bomber", " T3",
+];
+
+/// Deterministically builds a list of name-like texts.
+fn corpus() -> Vec<String> {
+    // simple LCG so runs are comparable without an extra dependency
+    let mut state = 0x2545_F491_u64;
+    let mut next = move |bound: usize| {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        usize::try_from(state >> 33).unwrap_or(0) % bound
+    };
+
+    (0..CORPUS_SIZE)
+        .map(|_| {
+            let mut name = String::new();
+            for _ in 0..2 + next(3) {
+                name.push_str(SYLLABLES[next(SYLLABLES.len())]);
+            }
+
+            name[..1].make_ascii_uppercase();
+            name.push_str(SUFFIXES[next(SUFFIXES.len())]);
+            name
+        })
+        .collect()
+}
+
+fn build<const MIN: usize, const MAX: usize>(corpus: &[String]) -> Search<(), MIN, MAX> {
+    let mut search = Search::new();
+    for name in corpus {
+        search.insert(name, ());
+    }
+
+    search.shrink_to_fit();
+    search
+}
+
+/// Queries of varying lengths. Autocomplete usually sees the shorter ones.
+fn queries(corpus: &[String]) -> Vec<(&'static str, String)> {
+    let long = &corpus[CORPUS_SIZE / 2];
+    vec![
+        ("3", long.chars().take(3).collect()),
+        ("8", long.chars().take(8).collect()),
+        ("full", long.clone()),
+        ("typo", long.replacen('a', "e", 1)),
+    ]
+}
+
+macro_rules! bench_config {
+    ($c:expr, $corpus:expr, $MIN:literal, $MAX:literal) => {{
+        let corpus: &[String] = $corpus;
+        let config = concat!("min", $MIN, "-max", $MAX);
+
+        $c.bench_function(&format!("insert/{config}"), |b| {
+            b.iter(|| build::<$MIN, $MAX>(black_box(corpus)))
+        });
+
+        let search = build::<$MIN, $MAX>(corpus);
+        let mut group = $c.benchmark_group(format!("search/{config}"));
+        for (len, query) in queries(corpus) {
+            group.bench_with_input(BenchmarkId::from_parameter(len), &query, |b, query| {
+                b.iter(|| search.search(black_box(query)).take(25).count())
+            });
+        }
+
+        group.finish();
+    }};
+}
+
+fn fuzzy(c: &mut Criterion) {
+    let corpus = corpus();
+    bench_config!(c, &corpus, 1, 3);
+    bench_config!(c, &corpus, 2, 4);
+    bench_config!(c, &corpus, 2, 6);
+    bench_config!(c, &corpus, 3, 6);
+}
+
+criterion_group!(benches, fuzzy);
+criterion_main!(benches);
@@ -1,5 +1,7 @@
 //! Provides traits and macros to refer to a field of a struct.
 
+use std::marker::PhantomData;
+
 /// Represents access to a field of a struct.
 ///
 /// Use the [`field`] macro to obtain instances.
@@ -7,6 +9,46 @@ pub trait Field<S: ?Sized, F: ?Sized> {
     /// Gets a reference to the field.
     #[must_use]
     fn get<'r>(&self, obj: &'r S) -> &'r F;
+
+    /// Composes this field with a field of its value, creating a field that refers to the nested value.
+    ///
+    /// The result implements [`FieldMut`] if both fields do.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use utils::fields::{Field, FieldMut};
+    ///
+    /// struct Chest {
+    ///     lock: Lock
+    /// }
+    ///
+    /// struct Lock {
+    ///     locked: bool
+    /// }
+    ///
+    /// let field = utils::field_mut!(Chest: lock).then(utils::field_mut!(Lock: locked));
+    /// let mut chest = Chest {
+    ///     lock: Lock {
+    ///         locked: true
+    ///     }
+    /// };
+    ///
+    /// *field.get_mut(&mut chest) = false;
+    /// assert_eq!(field.get(&chest), &false);
+    /// ```
+    fn then<N, G>(self, next: G) -> FieldChain<Self, G, F>
+    where
+        Self: Sized,
+        N: ?Sized,
+        G: Field<F, N>,
+    {
+        FieldChain {
+            first: self,
+            second: next,
+            _inner: PhantomData,
+        }
+    }
 }
 
 /// Represents mutable access to a field of a struct.
@@ -37,6 +79,68 @@ where
     }
 }
 
+/// A [`Field`] into a nested value, composed of two other fields.
+///
+/// Use [`Field::then`] to obtain instances.
+///
+/// The intermediate field type `M` has to be `'static`, i.e. it can't borrow data,
+/// since the signature of [`Field::get`] gives no way to tie its lifetime to the input.
+#[must_use]
+pub struct FieldChain<A, B, M: ?Sized> {
+    first: A,
+    second: B,
+    _inner: PhantomData<fn(&M) -> &M>,
+}
+
+impl<A: Clone, B: Clone, M: ?Sized> Clone for FieldChain<A, B, M> {
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first.clone(),
+            second: self.second.clone(),
+            _inner: PhantomData,
+        }
+    }
+}
+
+impl<A: Copy, B: Copy, M: ?Sized> Copy for FieldChain<A, B, M> {}
+
+impl<A: std::fmt::Debug, B: std::fmt::Debug, M: ?Sized> std::fmt::Debug for FieldChain<A, B, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldChain")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
+
+impl<S, M, F, A, B> Field<S, F> for FieldChain<A, B, M>
+where
+    S: ?Sized,
+    M: ?Sized + 'static,
+    F: ?Sized,
+    A: Field<S, M>,
+    B: Field<M, F>,
+{
+    #[inline]
+    fn get<'r>(&self, obj: &'r S) -> &'r F {
+        self.second.get(self.first.get(obj))
+    }
+}
+
+impl<S, M, F, A, B> FieldMut<S, F> for FieldChain<A, B, M>
+where
+    S: ?Sized,
+    M: ?Sized + 'static,
+    F: ?Sized,
+    A: FieldMut<S, M>,
+    B: FieldMut<M, F>,
+{
+    #[inline]
+    fn get_mut<'r>(&self, obj: &'r mut S) -> &'r mut F {
+        self.second.get_mut(self.first.get_mut(obj))
+    }
+}
+
 /// Provides a [`Field`] implementation that uses lambdas.
 ///
 /// This type isn't publicly available and hidden via `impl` in return position.
@@ -115,6 +219,8 @@ where
 
 /// Gets a [`Field`] that refers to the provided info.
 ///
+/// The path may be a field name, a tuple index, or a nested path like `inner.0`.
+///
 /// # Example
 ///
 /// ```
@@ -142,6 +248,8 @@ macro_rules! field {
 
 /// Gets a [`FieldMut`] that refers to the provided info.
 ///
+/// The path may be a field name, a tuple index, or a nested path like `inner.0`.
+///
 /// # Example
 ///
 /// ```
@@ -168,3 +276,43 @@ macro_rules! field_mut {
         )
     }};
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Field, FieldMut};
+
+    struct Pair(u32, &'static str);
+
+    struct Outer {
+        pair: Pair,
+    }
+
+    #[test]
+    fn tuple_field() {
+        let first = crate::field!(Pair: 0);
+        let second = crate::field!(Pair: 1);
+        let pair = Pair(5, "five");
+
+        assert_eq!(first.get(&pair), &5);
+        assert_eq!(second.get(&pair), &"five");
+    }
+
+    #[test]
+    fn tuple_field_mut() {
+        let first = crate::field_mut!(Pair: 0);
+        let mut pair = Pair(5, "five");
+
+        *first.get_mut(&mut pair) = 6;
+        assert_eq!(pair.0, 6);
+    }
+
+    #[test]
+    fn nested_tuple_field_mut() {
+        let field = crate::field_mut!(Outer: pair.1);
+        let mut outer = Outer { pair: Pair(5, "five") };
+
+        *field.get_mut(&mut outer) = "six";
+        assert_eq!(field.get(&outer), &"six");
+        assert_eq!(outer.pair.1, "six");
+    }
+}
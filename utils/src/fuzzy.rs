@@ -37,8 +37,14 @@
 //! - `"Hello World!"`
 //! - `hello-world`
 //! - `(hELLO)(wORLD)`
+//!
+//! # Match Spans
+//!
+//! [`Search::search_with_spans`] additionally returns which parts of the searched text
+//! had matching fragments. Since this needs to allocate, [`Search::search`] doesn't.
 
 use std::collections::HashMap;
+use std::ops::Range;
 use std::ptr::NonNull;
 
 use arrayvec::ArrayVec;
@@ -143,20 +149,40 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
     /// Check [`Match::score`] for more details.
     pub fn search<'st>(&'st self, value: &str) -> MatchIter<'st, T> {
         let norm = norm_str(value);
-        let mut results = MatchIter::new_empty();
+        self.search_norm(&norm).0
+    }
 
-        if norm.len() >= MIN {
-            let upper = MAX.min(norm.len());
+    /// Searches for a given text and also returns the matched spans.
+    ///
+    /// This returns the same matches in the same order as [`Search::search`].
+    /// Check [`SpanMatch::spans`] for more details.
+    pub fn search_with_spans<'st>(&'st self, value: &str) -> Vec<SpanMatch<'st, T>> {
+        let (norm, offsets) = norm_str_with_offsets(value);
+        let (results, size) = self.search_norm(&norm);
+
+        results.map(|m| {
+            let mut spans: Vec<Range<usize>> = Vec::new();
+            for (pos, segment) in iter_segments::<MAX>(&norm, size).enumerate() {
+                let matched = self.match_map
+                    .get(&segment)
+                    .is_some_and(|e| e.iter().any(|&i| i as usize == m.index));
+
+                if !matched {
+                    continue;
+                }
 
-            for size in (MIN..=upper).rev() {
-                results = self.find_with_segment_size(&norm, size);
-                if !results.is_empty() {
-                    break;
+                let start = offsets[pos];
+                let end = offsets.get(pos + size).copied().unwrap_or(value.len());
+
+                // offsets are ascending, so only the last span may overlap
+                match spans.last_mut() {
+                    Some(last) if last.end >= start => last.end = last.end.max(end),
+                    _ => spans.push(start..end),
                 }
             }
-        }
 
-        results
+            SpanMatch { inner: m, spans }
+        }).collect()
     }
 
     /// Shrinks the internal capacity as much as possible.
@@ -186,6 +212,24 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
         }
     }
 
+    /// Searches the normalized text, starting with the largest segment size.
+    ///
+    /// Also returns the segment size that had results, or `0` if there were none.
+    fn search_norm<'st>(&'st self, norm: &[u16]) -> (MatchIter<'st, T>, usize) {
+        if norm.len() >= MIN {
+            let upper = MAX.min(norm.len());
+
+            for size in (MIN..=upper).rev() {
+                let results = self.find_with_segment_size(norm, size);
+                if !results.is_empty() {
+                    return (results, size);
+                }
+            }
+        }
+
+        (MatchIter::new_empty(), 0)
+    }
+
     fn find_with_segment_size<'st>(&'st self, norm: &[u16], size: usize) -> MatchIter<'st, T> {
         const MAX_MATCHES: usize = 32;
 
@@ -233,6 +277,25 @@ impl<T, const MIN: usize, const MAX: usize> Search<T, MIN, MAX> {
     }
 }
 
+/// Inserts every text with its associated data, in order.
+///
+/// This reserves capacity based on the iterator's size hint,
+/// but is otherwise equivalent to calling [`Search::insert`] for each item.
+impl<S: AsRef<str>, T, const MIN: usize, const MAX: usize> Extend<(S, T)> for Search<T, MIN, MAX> {
+    fn extend<I: IntoIterator<Item = (S, T)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+
+        // most segments are shared between entries, so this is only a rough lower bound
+        self.values.reserve(lower);
+        self.match_map.reserve(lower);
+
+        for (value, data) in iter {
+            self.insert(value.as_ref(), data);
+        }
+    }
+}
+
 impl<T, const MIN: usize, const MAX: usize> Default for Search<T, MIN, MAX> {
     fn default() -> Self {
         Self::new()
@@ -263,6 +326,20 @@ impl<'st, T> Clone for Match<'st, T> {
     }
 }
 
+/// A matched value from [`Search::search_with_spans`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SpanMatch<'st, T> {
+    /// The match itself.
+    pub inner: Match<'st, T>,
+
+    /// The byte ranges of the searched text that had matching fragments.
+    ///
+    /// These are sorted and don't overlap. Since fragments may span separators,
+    /// ranges may include non-alphanumeric characters between words.
+    pub spans: Vec<Range<usize>>,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct MatchInfo {
     count: MatchIndex,
@@ -389,29 +466,47 @@ fn iter_segments<const N: usize>(slice: &[u16], size: usize) -> impl Iterator<It
 
 fn norm_str(str: &str) -> SmallVec<[u16; 20]> {
     let mut out = SmallVec::new();
+    norm_str_with(str, |u, _| out.push(u));
+    out
+}
+
+/// Like [`norm_str`], but also returns the byte offset in `str` for every normalized unit.
+fn norm_str_with_offsets(str: &str) -> (SmallVec<[u16; 20]>, Vec<usize>) {
+    let mut out = SmallVec::new();
+    let mut offsets = Vec::new();
+    norm_str_with(str, |u, offset| {
+        out.push(u);
+        offsets.push(offset);
+    });
+
+    (out, offsets)
+}
+
+fn norm_str_with(str: &str, mut push: impl FnMut(u16, usize)) {
     let mut whitespace = true;
 
-    out.push(1u16);
+    push(1u16, 0);
 
-    for c in str.chars() {
+    for (offset, c) in str.char_indices() {
         if c.is_alphanumeric() {
             let lowercase = c.to_lowercase()
                 .filter(|c| c.is_alphanumeric())
                 .map(|c| c as u16);
 
-            out.extend(lowercase);
+            for u in lowercase {
+                push(u, offset);
+            }
+
             whitespace = false;
         } else if !whitespace {
-            out.push(1);
+            push(1, offset);
             whitespace = true;
         }
     }
 
     if !whitespace {
-        out.push(1u16);
+        push(1u16, str.len());
     }
-
-    out
 }
 
 #[cfg(test)]
@@ -437,6 +532,49 @@ mod test {
         assert_eq!(&just_data(search.search("non")), &[4]);
     }
 
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)] // the spans are vecs of ranges
+    fn search_with_spans() {
+        let search = {
+            let mut search = TSearch::new().with_min_match_score(0.2);
+            search.insert("Hello World!", 1u8);
+            search.insert("Hello There.", 2);
+            search.insert("Nonmatch", 3);
+            search
+        };
+
+        let plain: Vec<u8> = search.search("hello world").map(|m| *m.data).collect();
+        let spanned = search.search_with_spans("hello world");
+        assert_eq!(spanned.iter().map(|m| *m.inner.data).collect::<Vec<_>>(), plain);
+
+        let spans = |data: u8| spanned.iter().find(|m| *m.inner.data == data).unwrap().spans.clone();
+        assert_eq!(spans(1), vec![0..11]);
+        assert_eq!(spans(2), vec![0..6]);
+
+        let spanned = search.search_with_spans("xx world");
+        assert_eq!(spanned.len(), 1);
+        assert_eq!(spanned[0].spans, vec![2..8]);
+    }
+
+    #[test]
+    fn extend_matches_insert() {
+        let items = [("Hello World!", 1u8), ("Hello There.", 2), ("World Welcome", 3), ("Nonmatch", 4)];
+
+        let mut inserted = TSearch::new().with_min_match_score(0.2);
+        for (value, data) in items {
+            inserted.insert(value, data);
+        }
+
+        let mut extended = TSearch::new().with_min_match_score(0.2);
+        extended.extend(items);
+
+        for query in ["ello", "world", "el e", "non", "xyz"] {
+            let inserted: Vec<_> = inserted.search(query).map(|m| (m.index, *m.data)).collect();
+            let extended: Vec<_> = extended.search(query).map(|m| (m.index, *m.data)).collect();
+            assert_eq!(inserted, extended, "{query}");
+        }
+    }
+
     fn just_data(v: MatchIter<'_, u8>) -> Vec<u8> {
         let mut v: Vec<u8> = v.map(|p| *p.data).collect();
         v.sort();
@@ -12,6 +12,10 @@ pub mod time;
 
 mod private;
 
+// only used by the benchmarks
+#[cfg(test)]
+use criterion as _;
+
 /// Convenience method to calculate the hash of a value with the [`std::hash::DefaultHasher`].
 #[must_use]
 #[inline]
@@ -27,6 +31,43 @@ pub fn hash<T: std::hash::Hash + ?Sized, H: std::hash::Hasher>(value: &T, mut ha
     hasher.finish()
 }
 
+/// A [`Hasher`](std::hash::Hasher) implementing 64-bit FNV-1a.
+///
+/// Unlike [`std::hash::DefaultHasher`], the algorithm is fixed, so the result of hashing
+/// the same bytes is stable across builds and may be persisted.
+///
+/// Note that the [`Hash`](std::hash::Hash) implementations of std types aren't guaranteed
+/// to be stable. Use [`write`](std::hash::Hasher::write) with explicit bytes if that matters.
+#[derive(Debug, Clone, Copy)]
+pub struct Fnv1aHasher(u64);
+
+impl Fnv1aHasher {
+    /// Creates a new hasher with the FNV offset basis.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(0xCBF2_9CE4_8422_2325)
+    }
+}
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::hash::Hasher for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 /// Trait that allows discarding values.
 pub trait Discard {
     /// Consumes and discards the value.
@@ -257,8 +298,11 @@ macro_rules! impl_op_via_assign {
 
 #[cfg(test)]
 mod test {
+    use std::hash::Hasher;
     use std::ops::{AddAssign, Add, SubAssign, Sub};
 
+    use super::Fnv1aHasher;
+
     #[derive(Clone, Copy, PartialEq, Eq)]
     struct Num(i32);
 
@@ -346,4 +390,17 @@ mod test {
         assert!(sub_assign(Num(6), &3) == Num(3));
         assert!(sub_assign(Num(8), 4) == Num(4));
     }
+
+    #[test]
+    fn fnv1a_known_values() {
+        fn fnv1a(bytes: &[u8]) -> u64 {
+            let mut hasher = Fnv1aHasher::new();
+            hasher.write(bytes);
+            hasher.finish()
+        }
+
+        assert_eq!(fnv1a(b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xAF63_DC4C_8601_EC8C);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_F739_67E8);
+    }
 }
@@ -1,6 +1,6 @@
 //! Provides helper functions to work with blocks of memory.
 //!
-//! For example, this allows const-time conversion of slices into arrays via [`as_sized`].
+//! For example, this allows const-time conversion of slices into arrays via [`as_sized`] and [`copy_into_array`].
 
 /// Converts a slice to an array reference of size `N`.
 /// This is a const-friendly alternative to `<&[T; N]>::try_from`.
@@ -58,6 +58,32 @@ pub const fn try_as_sized<T, const N: usize>(slice: &[T]) -> Option<&[T; N]> {
     }
 }
 
+/// Tries to copy a slice into an array of size `N`.
+/// This is a const-friendly alternative to `<[T; N]>::try_from`.
+///
+/// Returns [`None`] if the slice isn't exactly `N` long.
+///
+/// # Examples
+///
+/// ```
+/// let x: &[u8] = &[1, 2, 3, 4];
+///
+/// let exact = utils::mem::copy_into_array::<u8, 4>(x);
+/// let small = utils::mem::copy_into_array::<u8, 2>(x);
+/// let large = utils::mem::copy_into_array::<u8, 6>(x);
+///
+/// assert_eq!(exact, Some([1, 2, 3, 4]));
+/// assert_eq!(small, None);
+/// assert_eq!(large, None);
+/// ```
+#[inline]
+pub const fn copy_into_array<T: Copy, const N: usize>(slice: &[T]) -> Option<[T; N]> {
+    match try_as_sized(slice) {
+        Some(array) => Some(*array),
+        None => None,
+    }
+}
+
 /// Transmutes a slice of some type into one of another.
 ///
 /// The length of the new slice is adjusted to cover the same memory region without
@@ -149,3 +175,33 @@ pub const unsafe fn as_bytes<T>(slice: &[T]) -> &[u8] {
         transmute_slice(slice)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn copy_into_array_exact() {
+        let x: &[u32] = &[1, 2, 3];
+        assert_eq!(copy_into_array::<u32, 3>(x), Some([1, 2, 3]));
+        assert_eq!(copy_into_array::<u32, 0>(&[]), Some([]));
+    }
+
+    #[test]
+    fn copy_into_array_short() {
+        let x: &[u32] = &[1, 2];
+        assert_eq!(copy_into_array::<u32, 3>(x), None);
+    }
+
+    #[test]
+    fn copy_into_array_long() {
+        let x: &[u32] = &[1, 2, 3, 4];
+        assert_eq!(copy_into_array::<u32, 3>(x), None);
+    }
+
+    #[test]
+    fn copy_into_array_const() {
+        const ARRAY: Option<[u8; 2]> = copy_into_array(b"ab");
+        assert_eq!(ARRAY, Some(*b"ab"));
+    }
+}
@@ -15,6 +15,14 @@
 //!
 //! Via [`to_b65536`] and [`from_b65536`]:
 //! Encodes pairs of bytes as one [`char`] of the output with a unique code point for each possible input.
+//!
+//! ## Base64 URL
+//!
+//! Via [`to_base64url`] and [`from_base64url`]:
+//! Encodes the bytes with the URL-safe base64 alphabet, without padding.
+//! This is far less compact than base 65536, but only uses printable ASCII characters,
+//! so it is readable and can be copied from logs or put into URLs.
+//! Unlike the other formats, no delimiters are added since the output never contains white-space.
 
 crate::define_simple_error!(
     /// Error decoding base 256 data in [`from_b256`].
@@ -28,6 +36,12 @@ crate::define_simple_error!(
     "base65536 data is invalid"
 );
 
+crate::define_simple_error!(
+    /// Error decoding base64 URL data in [`from_base64url`].
+    Base64UrlError(()):
+    "base64url data is invalid"
+);
+
 /// Converts the bytes to "base 256".
 ///
 /// Each byte will be mapped to the UTF-8 character with the equivalent code.
@@ -129,6 +143,67 @@ pub fn from_b65536(str: &str) -> Result<Vec<u8>, Base65536Error> {
     Ok(result)
 }
 
+/// Converts the bytes to unpadded base64 with the URL-safe alphabet.
+#[must_use]
+pub fn to_base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buf = [0u8; 4];
+        buf[1..=chunk.len()].copy_from_slice(chunk);
+
+        // every 3 input bytes are split into 4 groups of 6 bits
+        // a partial chunk of N bytes only outputs N+1 characters
+        let int = u32::from_be_bytes(buf);
+        let chars = [int >> 18, int >> 12, int >> 6, int]
+            .map(|i| char::from(ALPHABET[(i & 0x3F) as usize]));
+
+        result.extend(&chars[..=chunk.len()]);
+    }
+
+    result
+}
+
+/// Reverses the operation done by [`to_base64url`].
+///
+/// If the data contains characters outside the alphabet or has an impossible length, returns an error.
+pub fn from_base64url(str: &str) -> Result<Vec<u8>, Base64UrlError> {
+    fn decode_char(c: u8) -> Result<u32, Base64UrlError> {
+        let value = match c {
+            b'A' ..= b'Z' => c - b'A',
+            b'a' ..= b'z' => c - b'a' + 26,
+            b'0' ..= b'9' => c - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => return Err(Base64UrlError(())),
+        };
+
+        Ok(u32::from(value))
+    }
+
+    let str = str.as_bytes();
+
+    // a single character in the last group cannot encode a full byte
+    if str.len() % 4 == 1 {
+        return Err(Base64UrlError(()));
+    }
+
+    let mut result = Vec::with_capacity(str.len() / 4 * 3 + 2);
+    for chunk in str.chunks(4) {
+        let mut int = 0u32;
+        for (index, &c) in chunk.iter().enumerate() {
+            int |= decode_char(c)? << (18 - 6 * index);
+        }
+
+        // a partial chunk of N characters only holds N-1 bytes
+        let bytes = int.to_be_bytes();
+        result.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+
+    Ok(result)
+}
+
 const OFFSET: u32 = 0xE000 - 0xD800;
 
 fn char_to_bytes(c: char) -> Result<[u8; 2], Base65536Error> {
@@ -206,6 +281,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn round_trip_base64url() {
+        for skip in 0..3 {
+            round_trip_core(
+                &DATA[skip..],
+                to_base64url,
+                from_base64url
+            );
+        }
+    }
+
+    #[test]
+    fn known_base64url() {
+        assert_eq!(to_base64url(b""), "");
+        assert_eq!(to_base64url(b"f"), "Zg");
+        assert_eq!(to_base64url(b"fo"), "Zm8");
+        assert_eq!(to_base64url(b"foo"), "Zm9v");
+        assert_eq!(to_base64url(&[0xFB, 0xFF]), "-_8");
+
+        assert_eq!(from_base64url("Zm9vYg").expect("decoding failed"), b"foob");
+        assert_eq!(from_base64url("-_8").expect("decoding failed"), &[0xFB, 0xFF]);
+    }
+
+    #[test]
+    fn invalid_base64url_fails() {
+        from_base64url(black_box("Zm9+")).expect_err("'+' is not in the alphabet");
+        from_base64url(black_box("Zm9vY")).expect_err("length is impossible");
+    }
+
     #[test]
     fn min_b256() {
         let encoded = black_box("#\u{0078}&");
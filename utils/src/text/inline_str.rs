@@ -8,6 +8,9 @@
 /// Generally, [`String`] is more useful but this is can be useful
 /// for working with strings in a const context.
 ///
+/// Since the length is part of the type, there is no capacity to push into.
+/// Use [`join`](Self::join) to concatenate two values into a longer one instead.
+///
 // Note: These derives are fine since `str` itself only delegates to `as_bytes` for `Eq` and `Ord`.
 // `Debug` and `Hash` are manually implemented to delegate to `as_str` to give the right `Borrow` semantics.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -116,6 +119,24 @@ impl<const LEN: usize> InlineStr<LEN> {
     ///
     /// Panics if the FINAL length doesn't match the total length of the inputs.
     /// This will happen at compile time rather than runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use utils::text::InlineStr;
+    /// const HELLO: InlineStr<5> = match InlineStr::from_utf8(*b"hello") {
+    ///     Ok(s) => s,
+    ///     Err(_) => panic!(),
+    /// };
+    ///
+    /// const WORLD: InlineStr<6> = match InlineStr::from_utf8(*b" world") {
+    ///     Ok(s) => s,
+    ///     Err(_) => panic!(),
+    /// };
+    ///
+    /// const JOINED: InlineStr<11> = HELLO.join(WORLD);
+    /// assert_eq!(JOINED.as_str(), "hello world");
+    /// ```
     #[must_use]
     pub const fn join<const OTHER: usize, const FINAL: usize>(self, other: InlineStr<OTHER>) -> InlineStr<FINAL> {
         const { assert!(LEN + OTHER == FINAL, "length of inputs doesn't match result length"); }
@@ -192,3 +213,28 @@ impl<'a, const LEN: usize> TryFrom<&'a str> for &'a InlineStr<LEN> {
         InlineStr::from_str(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::InlineStr;
+
+    #[test]
+    fn from_str_len() {
+        assert!(<&InlineStr<3>>::try_from("abc").is_ok());
+        assert!(<&InlineStr<3>>::try_from("ab").is_err());
+        assert!(<&InlineStr<3>>::try_from("abcd").is_err());
+    }
+
+    #[test]
+    fn join() {
+        let a: InlineStr<2> = *InlineStr::from_str("ab").unwrap();
+        let b: InlineStr<3> = *InlineStr::from_str("äc").unwrap();
+
+        let joined: InlineStr<5> = a.join(b);
+        assert_eq!(joined.as_str(), "abäc");
+
+        let empty: InlineStr<0> = *InlineStr::from_str("").unwrap();
+        let joined: InlineStr<2> = a.join(empty);
+        assert_eq!(joined, a);
+    }
+}
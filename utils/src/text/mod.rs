@@ -1,5 +1,7 @@
 //! Provides helper methods to work with displayed text.
 
+use std::borrow::Cow;
+
 pub mod __private;
 mod inline_str;
 mod truncate_impl;
@@ -7,6 +9,106 @@ mod truncate_impl;
 pub use inline_str::InlineStr;
 pub use truncate_impl::{Truncate, truncate};
 
+/// Escapes Discord markdown in the text, so it is displayed as-is.
+///
+/// This escapes `\`, `*`, `_`, `~`, `|`, and backticks anywhere, and `>` at the start of a line.
+/// If nothing needs to be escaped, the text is returned without allocating.
+///
+/// # Examples
+///
+/// ```
+/// let text = utils::text::escape_markdown("**bold** and _italic_");
+/// assert_eq!(text, r"\*\*bold\*\* and \_italic\_");
+/// ```
+#[must_use]
+pub fn escape_markdown(text: &str) -> Cow<'_, str> {
+    fn is_markdown(c: char) -> bool {
+        matches!(c, '\\' | '*' | '_' | '~' | '|' | '`')
+    }
+
+    if !text.contains(is_markdown) && !text.lines().any(|l| l.starts_with('>')) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len() + 8);
+    let mut line_start = true;
+    for c in text.chars() {
+        if is_markdown(c) || (line_start && c == '>') {
+            result.push('\\');
+        }
+
+        result.push(c);
+        line_start = c == '\n';
+    }
+
+    Cow::Owned(result)
+}
+
+/// Wraps the text in a Discord spoiler.
+///
+/// Pipes within the text are escaped so they can't end the spoiler early.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(utils::text::as_spoiler("secret"), "||secret||");
+/// assert_eq!(utils::text::as_spoiler("a||b"), r"||a\|\|b||");
+/// ```
+#[must_use]
+pub fn as_spoiler(text: &str) -> String {
+    format!("||{}||", text.replace('|', "\\|"))
+}
+
+/// Formats the text as a block quote by prefixing every line with `> `.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(utils::text::as_block_quote("first\nsecond"), "> first\n> second");
+/// ```
+#[must_use]
+pub fn as_block_quote(text: &str) -> String {
+    if text.is_empty() {
+        return "> ".to_owned();
+    }
+
+    let mut result = String::with_capacity(text.len() + 2);
+    for (index, line) in text.lines().enumerate() {
+        if index != 0 {
+            result.push('\n');
+        }
+
+        result.push_str("> ");
+        result.push_str(line);
+    }
+
+    result
+}
+
+/// Formats the text as inline code.
+///
+/// The fence is made one backtick longer than the longest run of backticks within the text,
+/// and the text is padded with spaces if it starts or ends with a backtick, so any text is shown as-is.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(utils::text::as_code("a + b"), "`a + b`");
+/// assert_eq!(utils::text::as_code("a`b"), "``a`b``");
+/// assert_eq!(utils::text::as_code("`a`"), "`` `a` ``");
+/// ```
+#[must_use]
+pub fn as_code(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+
+    let fence = "`".repeat(longest_run + 1);
+    let pad = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+    format!("{fence}{pad}{text}{pad}{fence}")
+}
+
 /// Given a `SNAKE_CASE` string, converts it to title case (i.e. `Snake Case`).
 ///
 /// # Examples
@@ -169,3 +271,59 @@ unsafe impl<const N: usize> MutStrLike for InlineStr<N> {
         unsafe { self.as_bytes_mut() }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use super::{as_block_quote, as_code, as_spoiler, escape_markdown};
+
+    #[test]
+    fn escape_markdown_chars() {
+        assert_eq!(escape_markdown("*a*"), r"\*a\*");
+        assert_eq!(escape_markdown("_a_"), r"\_a\_");
+        assert_eq!(escape_markdown("~~a~~"), r"\~\~a\~\~");
+        assert_eq!(escape_markdown("||a||"), r"\|\|a\|\|");
+        assert_eq!(escape_markdown("`a`"), r"\`a\`");
+        assert_eq!(escape_markdown(r"a\b"), r"a\\b");
+    }
+
+    #[test]
+    fn escape_markdown_quote() {
+        assert_eq!(escape_markdown("> a"), r"\> a");
+        assert_eq!(escape_markdown("a\n> b"), "a\n\\> b");
+        assert_eq!(escape_markdown("a > b"), "a > b");
+    }
+
+    #[test]
+    fn escape_markdown_clean() {
+        let text = "Nothing to see here.\nReally.";
+        assert!(matches!(escape_markdown(text), Cow::Borrowed(t) if t == text));
+    }
+
+    #[test]
+    fn spoiler() {
+        assert_eq!(as_spoiler(""), "||||");
+        assert_eq!(as_spoiler("a|b"), r"||a\|b||");
+    }
+
+    #[test]
+    fn block_quote() {
+        assert_eq!(as_block_quote(""), "> ");
+        assert_eq!(as_block_quote("a"), "> a");
+        assert_eq!(as_block_quote("a\n\nb"), "> a\n> \n> b");
+        assert_eq!(as_block_quote("a\r\nb\n"), "> a\n> b");
+    }
+
+    #[test]
+    fn code_fence() {
+        assert_eq!(as_code(""), "``");
+        assert_eq!(as_code("plain"), "`plain`");
+        assert_eq!(as_code("a`b"), "``a`b``");
+        assert_eq!(as_code("a``b"), "```a``b```");
+        assert_eq!(as_code("a`b``c"), "```a`b``c```");
+        assert_eq!(as_code("`"), "`` ` ``");
+        assert_eq!(as_code("``a"), "``` ``a ```");
+        assert_eq!(as_code("a`"), "`` a` ``");
+    }
+}
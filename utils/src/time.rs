@@ -1,5 +1,8 @@
 //! Convenience module for dealing with times and timestamps.
 
+use std::sync::OnceLock;
+use std::time::Duration;
+
 use chrono::prelude::*;
 
 use crate::private::cell::SyncUnsafeCell;
@@ -15,6 +18,9 @@ const DISCORD_EPOCH: u64 = 1_420_070_400_000;
 // Either way, it's still UB to Rust, so we treat it with the appropriate care.
 static STARTUP_TIME: SyncUnsafeCell<DateTime<Utc>> = SyncUnsafeCell::new(DateTime::UNIX_EPOCH);
 
+/// Stores the startup time set by [`init_startup_time`].
+static STARTUP_TIME_ONCE: OnceLock<DateTime<Utc>> = OnceLock::new();
+
 /// Marks the current time as the startup time of the application.
 ///
 /// This should be called once at the start of your `main` entry point.
@@ -23,20 +29,47 @@ static STARTUP_TIME: SyncUnsafeCell<DateTime<Utc>> = SyncUnsafeCell::new(DateTim
 ///
 /// This function is unsafe as the underlying memory is static.
 /// This must not be called concurrently with itself or [`get_startup_time`].
+#[deprecated = "use `init_startup_time` instead"]
 pub unsafe fn mark_startup_time() {
     // SAFETY: Caller guarantees exclusive access
     unsafe { *STARTUP_TIME.get() = Utc::now(); }
 }
 
+/// Marks the current time as the startup time of the application.
+///
+/// This should be called at the start of your `main` entry point.
+/// Only the first call has an effect, subsequent calls do nothing.
+pub fn init_startup_time() {
+    STARTUP_TIME_ONCE.get_or_init(Utc::now);
+}
+
 /// Gets the marked startup time of the application.
 ///
-/// If the program setup never called [`mark_startup_time`], this will be the unix epoch.
+/// If the program setup never called [`init_startup_time`] or the deprecated [`mark_startup_time`],
+/// this will be the unix epoch.
 #[must_use]
 pub fn get_startup_time() -> DateTime<Utc> {
+    if let Some(&time) = STARTUP_TIME_ONCE.get() {
+        return time;
+    }
+
     // SAFETY: only concurrent reads
     unsafe { *STARTUP_TIME.get() }
 }
 
+/// Gets the time since the startup time returned by [`get_startup_time`].
+///
+/// Returns [`None`] if neither [`init_startup_time`] nor the deprecated [`mark_startup_time`] was called.
+#[must_use]
+pub fn uptime() -> Option<Duration> {
+    let startup = get_startup_time();
+    if startup == DateTime::UNIX_EPOCH {
+        return None;
+    }
+
+    Some((Utc::now() - startup).to_std().unwrap_or_default())
+}
+
 /// Gets the creation time from a snowflake
 #[must_use]
 pub fn get_creation_time(snowflake: u64) -> Option<DateTime<Utc>> {
@@ -45,6 +78,47 @@ pub fn get_creation_time(snowflake: u64) -> Option<DateTime<Utc>> {
     DateTime::from_timestamp_millis(((snowflake >> 22) + DISCORD_EPOCH) as i64)
 }
 
+/// Formats a duration with compact units, like `2d 3h` or `45s`.
+///
+/// Only the two largest non-zero units of days, hours, minutes, and seconds are shown.
+/// Durations shorter than a second are shown in milliseconds instead.
+#[must_use]
+pub fn format_duration_short(duration: Duration) -> String {
+    use std::fmt::Write;
+
+    use crate::Discard;
+
+    const UNITS: [(u64, char); 4] = [
+        (24 * 60 * 60, 'd'),
+        (60 * 60, 'h'),
+        (60, 'm'),
+        (1, 's'),
+    ];
+
+    let mut secs = duration.as_secs();
+    if secs == 0 {
+        return format!("{}ms", duration.subsec_millis());
+    }
+
+    let mut result = String::new();
+    let mut count = 0;
+    for (size, unit) in UNITS {
+        let value = secs / size;
+        secs %= size;
+
+        if value != 0 && count < 2 {
+            if count != 0 {
+                result.push(' ');
+            }
+
+            write!(result, "{value}{unit}").discard();
+            count += 1;
+        }
+    }
+
+    result
+}
+
 /// Allows mentioning a timestamp in Discord messages.
 pub trait TimeMentionable {
     /// Formats a mention for a timestamp.
@@ -124,3 +198,54 @@ const DATE_TIME_FORMATS: &[DateTimeFormat] = &[
     make_date_format!("%Y-%m-%d %H:%M"),
     make_date_format!("%B %d, %Y %H:%M")
 ];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn init_startup_time_is_idempotent() {
+        init_startup_time();
+        let first = get_startup_time();
+        assert_ne!(first, DateTime::UNIX_EPOCH);
+        assert!(uptime().is_some());
+
+        std::thread::sleep(Duration::from_millis(5));
+        init_startup_time();
+        assert_eq!(get_startup_time(), first);
+        assert!(uptime().is_some_and(|u| u >= Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn duration_sub_second() {
+        assert_eq!(format_duration_short(Duration::ZERO), "0ms");
+        assert_eq!(format_duration_short(Duration::from_millis(450)), "450ms");
+        assert_eq!(format_duration_short(Duration::from_micros(999_999)), "999ms");
+    }
+
+    #[test]
+    fn duration_single_unit() {
+        assert_eq!(format_duration_short(Duration::from_secs(45)), "45s");
+        assert_eq!(format_duration_short(Duration::from_millis(45_900)), "45s");
+        assert_eq!(format_duration_short(Duration::from_secs(5 * 60)), "5m");
+        assert_eq!(format_duration_short(Duration::from_secs(3 * 60 * 60)), "3h");
+        assert_eq!(format_duration_short(Duration::from_secs(2 * 24 * 60 * 60)), "2d");
+    }
+
+    #[test]
+    fn duration_two_units() {
+        assert_eq!(format_duration_short(Duration::from_secs(4 * 60 + 5)), "4m 5s");
+        assert_eq!(format_duration_short(Duration::from_secs(3 * 60 * 60 + 4 * 60)), "3h 4m");
+        assert_eq!(format_duration_short(Duration::from_secs(400 * 24 * 60 * 60 + 60 * 60)), "400d 1h");
+    }
+
+    #[test]
+    fn duration_truncates_to_two_units() {
+        let secs = 2 * 24 * 60 * 60 + 3 * 60 * 60 + 4 * 60 + 5;
+        assert_eq!(format_duration_short(Duration::from_secs(secs)), "2d 3h");
+
+        // zero units are skipped rather than counted
+        let secs = 2 * 24 * 60 * 60 + 4 * 60 + 5;
+        assert_eq!(format_duration_short(Duration::from_secs(secs)), "2d 4m");
+    }
+}